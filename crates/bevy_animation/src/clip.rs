@@ -0,0 +1,250 @@
+use bevy_math::{Quat, Vec3, Vec4};
+
+/// How values are computed between the keyframes of an [AnimationChannel]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    /// Values are linearly interpolated between keyframes. Interpolated rotations are normalized.
+    Linear,
+    /// Values stay constant until the next keyframe
+    Step,
+    /// Values are interpolated using a cubic hermite spline
+    CubicSpline,
+}
+
+/// The keyframe values of an [AnimationChannel].
+///
+/// For [Interpolation::CubicSpline] channels every keyframe is made up of three consecutive values: the in-tangent,
+/// the value and the out-tangent.
+#[derive(Debug, Clone)]
+pub enum Keyframes {
+    Translation(Vec<Vec3>),
+    Rotation(Vec<Quat>),
+    Scale(Vec<Vec3>),
+}
+
+/// A single sampled value of an [AnimationChannel]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyframeValue {
+    Translation(Vec3),
+    Rotation(Quat),
+    Scale(Vec3),
+}
+
+/// Animates one property of the entity labeled with `target`
+#[derive(Debug, Clone)]
+pub struct AnimationChannel {
+    pub target: String,
+    pub timestamps: Vec<f32>,
+    pub keyframes: Keyframes,
+    pub interpolation: Interpolation,
+}
+
+impl AnimationChannel {
+    /// Samples the channel at the given time in seconds. Times outside of the channel's keyframes are clamped.
+    pub fn sample(&self, time: f32) -> Option<KeyframeValue> {
+        Some(match &self.keyframes {
+            Keyframes::Translation(values) => KeyframeValue::Translation(sample(
+                &self.timestamps,
+                values,
+                self.interpolation,
+                time,
+            )?),
+            Keyframes::Rotation(values) => KeyframeValue::Rotation(
+                sample(&self.timestamps, values, self.interpolation, time)?.normalize(),
+            ),
+            Keyframes::Scale(values) => {
+                KeyframeValue::Scale(sample(&self.timestamps, values, self.interpolation, time)?)
+            }
+        })
+    }
+
+    /// The time of the channel's last keyframe
+    pub fn duration(&self) -> f32 {
+        self.timestamps.last().cloned().unwrap_or(0.0)
+    }
+}
+
+/// A named collection of [AnimationChannel]s that are played together
+#[derive(Debug, Clone, Default)]
+pub struct AnimationClip {
+    pub name: Option<String>,
+    pub channels: Vec<AnimationChannel>,
+    pub duration: f32,
+}
+
+impl AnimationClip {
+    pub fn new(name: Option<String>, channels: Vec<AnimationChannel>) -> Self {
+        let duration = channels
+            .iter()
+            .map(|channel| channel.duration())
+            .fold(0.0, f32::max);
+        AnimationClip {
+            name,
+            channels,
+            duration,
+        }
+    }
+}
+
+/// A keyframe value that can be interpolated
+trait Interpolate: Copy {
+    fn lerp(self, other: Self, t: f32) -> Self;
+    fn hermite(v0: Self, out_tangent: Self, v1: Self, in_tangent: Self, t: f32, dt: f32) -> Self;
+}
+
+fn hermite_weights(t: f32) -> (f32, f32, f32, f32) {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (
+        2.0 * t3 - 3.0 * t2 + 1.0,
+        t3 - 2.0 * t2 + t,
+        -2.0 * t3 + 3.0 * t2,
+        t3 - t2,
+    )
+}
+
+impl Interpolate for Vec3 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn hermite(v0: Self, out_tangent: Self, v1: Self, in_tangent: Self, t: f32, dt: f32) -> Self {
+        let (a, b, c, d) = hermite_weights(t);
+        v0 * a + out_tangent * (b * dt) + v1 * c + in_tangent * (d * dt)
+    }
+}
+
+impl Interpolate for Quat {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Quat::lerp(self, other, t)
+    }
+
+    fn hermite(v0: Self, out_tangent: Self, v1: Self, in_tangent: Self, t: f32, dt: f32) -> Self {
+        let (a, b, c, d) = hermite_weights(t);
+        let value = Vec4::from(v0) * a
+            + Vec4::from(out_tangent) * (b * dt)
+            + Vec4::from(v1) * c
+            + Vec4::from(in_tangent) * (d * dt);
+        Quat::from(value)
+    }
+}
+
+fn sample<T: Interpolate>(
+    timestamps: &[f32],
+    values: &[T],
+    interpolation: Interpolation,
+    time: f32,
+) -> Option<T> {
+    // cubic spline keyframes are stored as (in-tangent, value, out-tangent) triplets
+    let value = |keyframe: usize| match interpolation {
+        Interpolation::CubicSpline => values.get(keyframe * 3 + 1).cloned(),
+        _ => values.get(keyframe).cloned(),
+    };
+
+    let last = timestamps.len().checked_sub(1)?;
+    if time <= timestamps[0] {
+        return value(0);
+    }
+    if time >= timestamps[last] {
+        return value(last);
+    }
+
+    let next = timestamps.iter().position(|timestamp| *timestamp > time)?;
+    let previous = next - 1;
+    let dt = timestamps[next] - timestamps[previous];
+    let t = (time - timestamps[previous]) / dt;
+    match interpolation {
+        Interpolation::Step => value(previous),
+        Interpolation::Linear => Some(value(previous)?.lerp(value(next)?, t)),
+        Interpolation::CubicSpline => Some(T::hermite(
+            value(previous)?,
+            *values.get(previous * 3 + 2)?,
+            value(next)?,
+            *values.get(next * 3)?,
+            t,
+            dt,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation_channel(interpolation: Interpolation, values: Vec<Vec3>) -> AnimationChannel {
+        AnimationChannel {
+            target: "node".to_string(),
+            timestamps: vec![0.0, 1.0, 2.0],
+            keyframes: Keyframes::Translation(values),
+            interpolation,
+        }
+    }
+
+    #[test]
+    fn sample_linear() {
+        let channel = translation_channel(
+            Interpolation::Linear,
+            vec![Vec3::zero(), Vec3::new(2.0, 0.0, 0.0), Vec3::zero()],
+        );
+        assert_eq!(
+            channel.sample(0.5),
+            Some(KeyframeValue::Translation(Vec3::new(1.0, 0.0, 0.0)))
+        );
+        assert_eq!(
+            channel.sample(1.5),
+            Some(KeyframeValue::Translation(Vec3::new(1.0, 0.0, 0.0)))
+        );
+        assert_eq!(
+            channel.sample(3.0),
+            Some(KeyframeValue::Translation(Vec3::zero()))
+        );
+    }
+
+    #[test]
+    fn sample_step() {
+        let channel = translation_channel(
+            Interpolation::Step,
+            vec![Vec3::zero(), Vec3::new(2.0, 0.0, 0.0), Vec3::zero()],
+        );
+        assert_eq!(
+            channel.sample(0.9),
+            Some(KeyframeValue::Translation(Vec3::zero()))
+        );
+        assert_eq!(
+            channel.sample(1.0),
+            Some(KeyframeValue::Translation(Vec3::new(2.0, 0.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn sample_cubic_spline() {
+        // zero tangents, so the spline passes through the keyframe values
+        let mut values = Vec::new();
+        for value in [0.0, 2.0, 4.0].iter() {
+            values.push(Vec3::zero());
+            values.push(Vec3::new(*value, 0.0, 0.0));
+            values.push(Vec3::zero());
+        }
+        let channel = translation_channel(Interpolation::CubicSpline, values);
+        assert_eq!(
+            channel.sample(1.0),
+            Some(KeyframeValue::Translation(Vec3::new(2.0, 0.0, 0.0)))
+        );
+        assert_eq!(
+            channel.sample(1.5),
+            Some(KeyframeValue::Translation(Vec3::new(3.0, 0.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn clip_duration() {
+        let clip = AnimationClip::new(
+            None,
+            vec![translation_channel(
+                Interpolation::Linear,
+                vec![Vec3::zero(); 3],
+            )],
+        );
+        assert_eq!(clip.duration, 2.0);
+    }
+}
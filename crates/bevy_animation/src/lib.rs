@@ -0,0 +1,63 @@
+mod clip;
+mod player;
+mod skinning;
+
+pub use clip::*;
+pub use player::*;
+pub use skinning::*;
+
+pub mod prelude {
+    pub use crate::{AnimationClip, AnimationPlayer, SkinnedMesh};
+}
+
+/// The names of animation stages in an App Schedule
+pub mod stage {
+    pub const ANIMATE: &str = "animate";
+}
+
+/// the names of animation render graph nodes
+pub mod node {
+    pub const JOINT_MATRICES: &str = "joint_matrices";
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AddAsset;
+use bevy_ecs::IntoQuerySystem;
+use bevy_render::{
+    render_graph::{base, RenderGraph, RenderResourcesNode},
+    shader,
+};
+use bevy_type_registry::RegisterType;
+
+/// Adds skeletal animation and GPU skinning to Apps
+#[derive(Default)]
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_asset::<AnimationClip>()
+            .register_component::<SkinnedMesh>()
+            .add_stage_before(bevy_app::stage::POST_UPDATE, stage::ANIMATE)
+            .add_system_to_stage(stage::ANIMATE, animation_player_system.system())
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                skinned_mesh_setup_system.system(),
+            )
+            // runs after transform propagation, which is added to POST_UPDATE by TransformPlugin
+            .add_system_to_stage(bevy_app::stage::POST_UPDATE, joint_matrices_system.system())
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                shader::shader_defs_system::<JointMatrices>.system(),
+            );
+
+        let resources = app.resources();
+        let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
+        render_graph.add_system_node(
+            node::JOINT_MATRICES,
+            RenderResourcesNode::<JointMatrices>::new(true),
+        );
+        render_graph
+            .add_node_edge(node::JOINT_MATRICES, base::node::MAIN_PASS)
+            .unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::clip::{AnimationClip, KeyframeValue};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{Labels, Time};
+use bevy_ecs::prelude::*;
+use bevy_math::Mat4;
+use bevy_transform::prelude::{Children, Transform};
+use bevy_utils::HashMap;
+
+/// Plays an [AnimationClip] on an entity and its descendants.
+///
+/// Each channel of the clip animates the [Transform] of the entity (or descendant) whose [Labels] contain the
+/// channel's target.
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    clip: Option<Handle<AnimationClip>>,
+    elapsed: f32,
+    paused: bool,
+    /// Playback speed. Negative values play the clip backwards
+    pub speed: f32,
+    /// Whether the clip starts over once it reaches its end
+    pub repeat: bool,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        AnimationPlayer {
+            clip: None,
+            elapsed: 0.0,
+            paused: false,
+            speed: 1.0,
+            repeat: true,
+        }
+    }
+}
+
+impl AnimationPlayer {
+    /// Starts playing the given clip from the beginning
+    pub fn play(&mut self, clip: Handle<AnimationClip>) -> &mut Self {
+        self.clip = Some(clip);
+        self.elapsed = 0.0;
+        self.paused = false;
+        self
+    }
+
+    /// Stops playing the current clip. The animated entities keep their current pose
+    pub fn stop(&mut self) -> &mut Self {
+        self.clip = None;
+        self
+    }
+
+    pub fn pause(&mut self) -> &mut Self {
+        self.paused = true;
+        self
+    }
+
+    pub fn resume(&mut self) -> &mut Self {
+        self.paused = false;
+        self
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn clip(&self) -> Option<&Handle<AnimationClip>> {
+        self.clip.as_ref()
+    }
+
+    /// The time in seconds into the current clip
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+}
+
+/// Advances every [AnimationPlayer] and applies its clip to the animated [Transform]s
+pub fn animation_player_system(
+    time: Res<Time>,
+    clips: Res<Assets<AnimationClip>>,
+    mut player_query: Query<(Entity, &mut AnimationPlayer)>,
+    mut target_query: Query<(Option<&Labels>, Option<&Children>, &mut Transform)>,
+) {
+    for (entity, mut player) in &mut player_query.iter() {
+        let clip = match player.clip.as_ref().and_then(|clip| clips.get(clip)) {
+            Some(clip) => clip,
+            None => continue,
+        };
+
+        if !player.paused {
+            let elapsed = player.elapsed + time.delta_seconds * player.speed;
+            player.elapsed = if player.repeat && clip.duration > 0.0 {
+                elapsed.rem_euclid(clip.duration)
+            } else {
+                elapsed.max(0.0).min(clip.duration)
+            };
+        }
+
+        let targets = find_targets(entity, &mut target_query);
+        for channel in clip.channels.iter() {
+            let target = match targets.get(channel.target.as_str()) {
+                Some(target) => *target,
+                None => continue,
+            };
+            let value = match channel.sample(player.elapsed) {
+                Some(value) => value,
+                None => continue,
+            };
+            if let Ok(mut transform) = target_query.get_mut::<Transform>(target) {
+                let mut translation = transform.translation();
+                let mut rotation = transform.rotation();
+                let mut scale = transform.scale();
+                match value {
+                    KeyframeValue::Translation(value) => translation = value,
+                    KeyframeValue::Rotation(value) => rotation = value,
+                    KeyframeValue::Scale(value) => scale = value,
+                }
+                *transform.value_mut() =
+                    Mat4::from_scale_rotation_translation(scale, rotation, translation);
+            }
+        }
+    }
+}
+
+/// Maps the labels of `root` and its descendants to their entities
+fn find_targets(
+    root: Entity,
+    target_query: &mut Query<(Option<&Labels>, Option<&Children>, &mut Transform)>,
+) -> HashMap<String, Entity> {
+    let mut targets = HashMap::default();
+    let mut entities = vec![root];
+    while let Some(entity) = entities.pop() {
+        if let Ok(labels) = target_query.get::<Labels>(entity) {
+            for label in labels.iter() {
+                targets.insert(label.to_string(), entity);
+            }
+        }
+        if let Ok(children) = target_query.get::<Children>(entity) {
+            entities.extend(children.iter().cloned());
+        }
+    }
+
+    targets
+}
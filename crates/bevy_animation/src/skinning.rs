@@ -0,0 +1,116 @@
+use bevy_asset::Handle;
+use bevy_core::Bytes;
+use bevy_ecs::prelude::*;
+use bevy_math::Mat4;
+use bevy_property::Properties;
+use bevy_render::{
+    impl_render_resource_bytes,
+    renderer::{RenderResource, RenderResourceType, RenderResources},
+    shader::{ShaderDefIterator, ShaderDefs},
+    texture::Texture,
+};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::HashSet;
+use smallvec::SmallVec;
+
+/// The maximum number of joints a [SkinnedMesh] can be deformed by
+pub const MAX_JOINTS: usize = 64;
+
+/// Deforms the entity's mesh using the [GlobalTransform]s of a skeleton of joint entities.
+///
+/// Vertices are bound to joints using the `SkinnedVertex_JointIndices` and `SkinnedVertex_JointWeights` mesh attributes.
+#[derive(Debug, Clone, Default, Properties)]
+pub struct SkinnedMesh {
+    pub joints: SmallVec<[Entity; 8]>,
+    /// Transforms mesh vertices from model space into the local space of the joint with the same index
+    pub inverse_bind_matrices: Vec<Mat4>,
+}
+
+/// The world space joint matrices of a [SkinnedMesh], which are used to skin its mesh on the GPU
+#[derive(Clone, RenderResources)]
+#[render_resources(from_self)]
+pub struct JointMatrices {
+    pub matrices: [Mat4; MAX_JOINTS],
+}
+
+impl Default for JointMatrices {
+    fn default() -> Self {
+        JointMatrices {
+            matrices: [Mat4::identity(); MAX_JOINTS],
+        }
+    }
+}
+
+impl Bytes for JointMatrices {
+    fn write_bytes(&self, buffer: &mut [u8]) {
+        let matrix_len = std::mem::size_of::<Mat4>();
+        for (matrix, bytes) in self.matrices.iter().zip(buffer.chunks_mut(matrix_len)) {
+            matrix.write_bytes(bytes);
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        std::mem::size_of::<Mat4>() * MAX_JOINTS
+    }
+}
+
+impl_render_resource_bytes!(JointMatrices);
+
+impl ShaderDefs for JointMatrices {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            Some("SKINNED")
+        } else {
+            None
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
+}
+
+/// Adds [JointMatrices] to [SkinnedMesh] entities that don't have them yet
+pub fn skinned_mesh_setup_system(
+    mut commands: Commands,
+    mut query: Query<Without<JointMatrices, (Entity, &SkinnedMesh)>>,
+) {
+    for (entity, _skinned_mesh) in &mut query.iter() {
+        commands.insert_one(entity, JointMatrices::default());
+    }
+}
+
+/// Updates [JointMatrices] from the [GlobalTransform]s of their [SkinnedMesh]'s joints. Joints past [MAX_JOINTS]
+/// can't be uploaded to the GPU, so they are ignored and an error is logged once per entity.
+pub fn joint_matrices_system(
+    mut reported_entities: Local<HashSet<Entity>>,
+    mut query: Query<(Entity, &SkinnedMesh, &mut JointMatrices)>,
+    joint_query: Query<&GlobalTransform>,
+) {
+    for (entity, skinned_mesh, mut joint_matrices) in &mut query.iter() {
+        if skinned_mesh.joints.len() > MAX_JOINTS && reported_entities.insert(entity) {
+            log::error!(
+                "SkinnedMesh of {:?} has {} joints, but at most {} are supported. The remaining joints are ignored.",
+                entity,
+                skinned_mesh.joints.len(),
+                MAX_JOINTS
+            );
+        }
+        for (i, joint) in skinned_mesh.joints.iter().take(MAX_JOINTS).enumerate() {
+            let joint_transform = match joint_query.get::<GlobalTransform>(*joint) {
+                Ok(joint_transform) => *joint_transform.value(),
+                Err(_) => continue,
+            };
+            let inverse_bind_matrix = skinned_mesh
+                .inverse_bind_matrices
+                .get(i)
+                .cloned()
+                .unwrap_or_else(Mat4::identity);
+            joint_matrices.matrices[i] = joint_transform * inverse_bind_matrix;
+        }
+    }
+}
@@ -6,7 +6,12 @@ use crate::{
     plugin::Plugin,
     stage, startup_stage,
 };
-use bevy_ecs::{FromResources, IntoQuerySystem, Resources, System, World};
+use bevy_ecs::{
+    state_system, BoxedSystem, Component, FromResources, IntoQuerySystem, IntoThreadLocalSystem,
+    RefMut, Resources, RunCriteria, ScheduleError, State, StateSystems, StorageType,
+    StorageTypeError, SystemDescriptor, World,
+};
+use std::hash::Hash;
 
 /// Configure [App]s using the builder pattern
 pub struct AppBuilder {
@@ -40,7 +45,18 @@ impl AppBuilder {
         &mut self.app.resources
     }
 
+    /// Orders the systems in the app's schedules, returning an error if their ordering constraints refer to unknown
+    /// labels or form a cycle. [AppBuilder::run] calls this before any system runs.
+    pub fn order_systems(&mut self) -> Result<&mut Self, ScheduleError> {
+        self.app.startup_schedule.order_systems()?;
+        self.app.schedule.order_systems()?;
+        Ok(self)
+    }
+
     pub fn run(&mut self) {
+        if let Err(err) = self.order_systems() {
+            panic!("Failed to order the app's systems: {}", err);
+        }
         let app = std::mem::take(&mut self.app);
         app.run();
     }
@@ -50,6 +66,16 @@ impl AppBuilder {
         self
     }
 
+    /// Sets how the app's world stores components of type `T`. Plugins should call this while the app is being built,
+    /// because it fails once `T` components were added to the world.
+    pub fn set_storage_type<T: Component>(
+        &mut self,
+        storage_type: StorageType,
+    ) -> Result<&mut Self, StorageTypeError> {
+        self.app.world.set_storage_type::<T>(storage_type)?;
+        Ok(self)
+    }
+
     pub fn add_stage(&mut self, stage_name: &'static str) -> &mut Self {
         self.app.schedule.add_stage(stage_name);
         self
@@ -96,25 +122,22 @@ impl AppBuilder {
         self
     }
 
-    pub fn add_system(&mut self, system: Box<dyn System>) -> &mut Self {
+    pub fn add_system(&mut self, system: impl Into<SystemDescriptor>) -> &mut Self {
         self.add_system_to_stage(stage::UPDATE, system)
     }
 
-    pub fn add_systems(&mut self, systems: Vec<Box<dyn System>>) -> &mut Self {
+    pub fn add_systems(&mut self, systems: Vec<BoxedSystem>) -> &mut Self {
         self.add_systems_to_stage(stage::UPDATE, systems)
     }
 
-    pub fn init_system(
-        &mut self,
-        build: impl FnMut(&mut Resources) -> Box<dyn System>,
-    ) -> &mut Self {
+    pub fn init_system(&mut self, build: impl FnMut(&mut Resources) -> BoxedSystem) -> &mut Self {
         self.init_system_to_stage(stage::UPDATE, build)
     }
 
     pub fn init_system_to_stage(
         &mut self,
         stage: &'static str,
-        mut build: impl FnMut(&mut Resources) -> Box<dyn System>,
+        mut build: impl FnMut(&mut Resources) -> BoxedSystem,
     ) -> &mut Self {
         let system = build(&mut self.app.resources);
         self.add_system_to_stage(stage, system)
@@ -123,7 +146,7 @@ impl AppBuilder {
     pub fn add_startup_system_to_stage(
         &mut self,
         stage_name: &'static str,
-        system: Box<dyn System>,
+        system: impl Into<SystemDescriptor>,
     ) -> &mut Self {
         self.app
             .startup_schedule
@@ -134,7 +157,7 @@ impl AppBuilder {
     pub fn add_startup_systems_to_stage(
         &mut self,
         stage_name: &'static str,
-        systems: Vec<Box<dyn System>>,
+        systems: Vec<BoxedSystem>,
     ) -> &mut Self {
         for system in systems {
             self.app
@@ -144,20 +167,20 @@ impl AppBuilder {
         self
     }
 
-    pub fn add_startup_system(&mut self, system: Box<dyn System>) -> &mut Self {
+    pub fn add_startup_system(&mut self, system: impl Into<SystemDescriptor>) -> &mut Self {
         self.app
             .startup_schedule
             .add_system_to_stage(startup_stage::STARTUP, system);
         self
     }
 
-    pub fn add_startup_systems(&mut self, systems: Vec<Box<dyn System>>) -> &mut Self {
+    pub fn add_startup_systems(&mut self, systems: Vec<BoxedSystem>) -> &mut Self {
         self.add_startup_systems_to_stage(startup_stage::STARTUP, systems)
     }
 
     pub fn init_startup_system(
         &mut self,
-        build: impl FnMut(&mut Resources) -> Box<dyn System>,
+        build: impl FnMut(&mut Resources) -> BoxedSystem,
     ) -> &mut Self {
         self.init_startup_system_to_stage(startup_stage::STARTUP, build)
     }
@@ -165,7 +188,7 @@ impl AppBuilder {
     pub fn init_startup_system_to_stage(
         &mut self,
         stage: &'static str,
-        mut build: impl FnMut(&mut Resources) -> Box<dyn System>,
+        mut build: impl FnMut(&mut Resources) -> BoxedSystem,
     ) -> &mut Self {
         let system = build(&mut self.app.resources);
         self.add_startup_system_to_stage(stage, system)
@@ -182,10 +205,20 @@ impl AppBuilder {
             .add_stage(stage::LAST)
     }
 
+    /// Sets the [RunCriteria] that decides whether the given stage runs
+    pub fn set_run_criteria(
+        &mut self,
+        stage_name: &'static str,
+        run_criteria: impl RunCriteria,
+    ) -> &mut Self {
+        self.app.schedule.set_run_criteria(stage_name, run_criteria);
+        self
+    }
+
     pub fn add_system_to_stage(
         &mut self,
         stage_name: &'static str,
-        system: Box<dyn System>,
+        system: impl Into<SystemDescriptor>,
     ) -> &mut Self {
         self.app.schedule.add_system_to_stage(stage_name, system);
         self
@@ -194,7 +227,7 @@ impl AppBuilder {
     pub fn add_system_to_stage_front(
         &mut self,
         stage_name: &'static str,
-        system: Box<dyn System>,
+        system: impl Into<SystemDescriptor>,
     ) -> &mut Self {
         self.app
             .schedule
@@ -205,7 +238,7 @@ impl AppBuilder {
     pub fn add_systems_to_stage(
         &mut self,
         stage_name: &'static str,
-        systems: Vec<Box<dyn System>>,
+        systems: Vec<BoxedSystem>,
     ) -> &mut Self {
         for system in systems {
             self.app.schedule.add_system_to_stage(stage_name, system);
@@ -221,6 +254,68 @@ impl AppBuilder {
             .add_system_to_stage(stage::EVENT_UPDATE, Events::<T>::update_system.system())
     }
 
+    /// Adds a [State] with the given initial state. Its transitions are applied and its systems run in the
+    /// [stage::UPDATE] stage.
+    pub fn add_state<T>(&mut self, initial: T) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.add_state_to_stage(stage::UPDATE, initial)
+    }
+
+    /// Adds a [State] with the given initial state. Its transitions are applied and its systems run in the given
+    /// stage.
+    pub fn add_state_to_stage<T>(&mut self, stage_name: &'static str, initial: T) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.add_resource(State::new(initial))
+            .add_resource(StateSystems::<T>::default())
+            .add_system_to_stage(stage_name, state_system::<T>.thread_local_system())
+    }
+
+    pub fn on_state_enter<T>(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.state_systems::<T>().on_enter(state, system);
+        self
+    }
+
+    pub fn on_state_update<T>(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.state_systems::<T>().on_update(state, system);
+        self
+    }
+
+    pub fn on_state_resume<T>(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.state_systems::<T>().on_resume(state, system);
+        self
+    }
+
+    pub fn on_state_exit<T>(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.state_systems::<T>().on_exit(state, system);
+        self
+    }
+
+    fn state_systems<T>(&mut self) -> RefMut<StateSystems<T>>
+    where
+        T: Clone + Eq + Hash + Send + Sync + 'static,
+    {
+        self.app
+            .resources
+            .get_mut::<StateSystems<T>>()
+            .expect("State does not exist. Add it with AppBuilder::add_state first")
+    }
+
     pub fn add_resource<T>(&mut self, resource: T) -> &mut Self
     where
         T: Send + Sync + 'static,
@@ -1,17 +1,27 @@
 use crate::{
-    filesystem_watcher::FilesystemWatcher, AssetLoadError, AssetLoadRequestHandler, AssetLoader,
-    Assets, Handle, HandleId, LoadRequest,
+    get_meta_path,
+    meta::{get_loader_name, get_meta_asset_path, load_asset_meta},
+    processor::load_source_bytes,
+    AssetImporter, AssetIo, AssetIoError, AssetLoadError, AssetLoadRequestHandler, AssetLoader,
+    AssetMeta, AssetMetaError, AssetPath, AssetPathId, AssetProcessor, AssetResult, AssetSaver,
+    AssetSenders, Assets, Handle, HandleId, LabelId, LoadContext, LoadRequest, RefChange,
+    RefChangeSenders, SourcePathId,
 };
 use anyhow::Result;
 use bevy_ecs::{Res, Resource, Resources};
 use bevy_tasks::TaskPool;
 use bevy_utils::{HashMap, HashSet};
-use crossbeam_channel::TryRecvError;
+use crossbeam_channel::Sender;
+use futures_lite::future;
 use parking_lot::RwLock;
 use std::{
-    env, fs, io,
+    any::{type_name, TypeId},
+    io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 use thiserror::Error;
@@ -36,16 +46,77 @@ pub enum AssetServerError {
     Io(#[from] io::Error),
     #[error("Failed to watch asset folder.")]
     AssetWatchError { path: PathBuf },
+    #[error("Encountered an error while reading an asset.")]
+    AssetIoError(#[from] AssetIoError),
+    #[error("Encountered an error while reading an asset's meta file.")]
+    AssetMetaError(#[from] AssetMetaError),
+    #[error("No AssetLoader found with the name given in the asset's meta file.")]
+    UnknownLoader(String),
+    #[error(
+        "The AssetLoader named in the asset's meta file can't load the asset once it is processed."
+    )]
+    LoaderCannotLoadProcessedAsset {
+        loader: String,
+        processed_extension: String,
+    },
+    #[error("No AssetSaver found for the given extension.")]
+    MissingAssetSaver,
+    #[error("The asset to save does not exist.")]
+    MissingAsset,
+    #[error("The loaded file doesn't contain an asset with the requested label.")]
+    MissingLabeledAsset { label: String },
+    #[error("The requested asset has a different type than the handle it was requested with.")]
+    IncorrectAssetType { label: String },
+    #[error("This asset's saver encountered an error while saving.")]
+    SaverError(#[source] anyhow::Error),
 }
 
-/// Info about a specific asset, such as its path and its current load state
+/// Info about an asset source file, such as its path, its current load state, the labeled assets it contains and
+/// the assets they depend on
 #[derive(Clone, Debug)]
-pub struct AssetInfo {
-    pub handle_id: HandleId,
+pub struct SourceInfo {
     pub path: PathBuf,
     pub load_state: LoadState,
+    /// The labels of the assets that were loaded from the file. The file's main asset has the label `None`.
+    pub(crate) asset_labels: HashSet<LabelId>,
+    /// The labels of the loaded assets that have been added to their [Assets] collection
+    pub(crate) committed_assets: HashSet<LabelId>,
+    /// The types of the committed assets, which are used to notify them when one of their dependencies changes
+    pub(crate) asset_types: HashMap<LabelId, TypeId>,
+    /// The assets the file's assets depend on
+    pub(crate) dependencies: HashSet<AssetPath<'static>>,
+    /// Whether the file has a meta file, which is watched for changes along with the file
+    pub(crate) has_meta: bool,
 }
 
+impl SourceInfo {
+    fn is_loaded(&self) -> bool {
+        !self.asset_labels.is_empty() && self.committed_assets.len() == self.asset_labels.len()
+    }
+
+    /// The load state of the asset with the given label, ignoring its dependencies
+    fn get_asset_load_state(&self, label_id: LabelId) -> LoadState {
+        match self.load_state {
+            LoadState::Loaded(version) if !self.asset_labels.contains(&label_id) => {
+                // the file was loaded but it doesn't contain the requested asset
+                LoadState::Failed(version)
+            }
+            LoadState::Loading(version) if self.committed_assets.contains(&label_id) => {
+                LoadState::Loaded(version)
+            }
+            ref load_state => load_state.clone(),
+        }
+    }
+
+    fn depends_on(&self, source_path_id: SourcePathId) -> bool {
+        self.dependencies
+            .iter()
+            .any(|dependency| dependency.get_id().source_path_id() == source_path_id)
+    }
+}
+
+pub(crate) type AssetSources = Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>;
+
 /// The load state of an asset
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LoadState {
@@ -64,34 +135,58 @@ impl LoadState {
     }
 }
 
-/// Loads assets from the filesystem on background threads
+/// Loads assets from an [AssetIo] source on background threads
 pub struct AssetServer {
     asset_folders: RwLock<Vec<PathBuf>>,
-    asset_handlers: Arc<RwLock<Vec<Box<dyn AssetLoadRequestHandler>>>>,
+    asset_io: Arc<dyn AssetIo>,
+    importer: Option<Arc<AssetImporter>>,
+    asset_handlers: Arc<RwLock<Vec<Arc<dyn AssetLoadRequestHandler>>>>,
     // TODO: this is a hack to enable retrieving generic AssetLoader<T>s. there must be a better way!
     loaders: Vec<Resources>,
+    savers: Vec<Resources>,
     task_pool: TaskPool,
     extension_to_handler_index: HashMap<String, usize>,
     extension_to_loader_index: HashMap<String, usize>,
-    asset_info: RwLock<HashMap<HandleId, AssetInfo>>,
-    asset_info_paths: RwLock<HashMap<PathBuf, HandleId>>,
-    #[cfg(feature = "filesystem_watcher")]
-    filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+    extension_to_saver_index: HashMap<String, usize>,
+    loader_name_to_index: HashMap<String, usize>,
+    /// Files that assets were saved to, whose next change isn't reloaded because the assets are already up to date.
+    /// Only recorded while watching for changes, because otherwise the change is never reported.
+    saved_paths: RwLock<HashSet<PathBuf>>,
+    watching_for_changes: AtomicBool,
+    asset_sources: AssetSources,
+    asset_senders: AssetSenders,
+    /// Assets whose dependencies were reloaded, grouped by asset type
+    modified_dependents: RwLock<HashMap<TypeId, Vec<HandleId>>>,
+    /// The senders used to create strong handles, grouped by asset type
+    ref_change_senders: RefChangeSenders,
 }
 
 impl AssetServer {
-    pub fn new(task_pool: TaskPool) -> Self {
+    /// Creates an AssetServer that reads assets from `asset_io`. If there is an `importer`, assets are processed with
+    /// the server's [AssetProcessor](crate::AssetProcessor)s.
+    pub fn new(
+        asset_io: Arc<dyn AssetIo>,
+        importer: Option<AssetImporter>,
+        task_pool: TaskPool,
+    ) -> Self {
         AssetServer {
             asset_folders: Default::default(),
+            asset_io,
+            importer: importer.map(Arc::new),
             asset_handlers: Default::default(),
             loaders: Default::default(),
+            savers: Default::default(),
             extension_to_handler_index: Default::default(),
             extension_to_loader_index: Default::default(),
-            asset_info_paths: Default::default(),
-            asset_info: Default::default(),
+            extension_to_saver_index: Default::default(),
+            loader_name_to_index: Default::default(),
+            saved_paths: Default::default(),
+            watching_for_changes: AtomicBool::new(false),
+            asset_sources: Default::default(),
+            asset_senders: Default::default(),
+            modified_dependents: Default::default(),
+            ref_change_senders: Default::default(),
             task_pool,
-            #[cfg(feature = "filesystem_watcher")]
-            filesystem_watcher: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -106,7 +201,7 @@ impl AssetServer {
                 .insert(extension.to_string(), handler_index);
         }
 
-        asset_handlers.push(Box::new(asset_handler));
+        asset_handlers.push(Arc::new(asset_handler));
     }
 
     pub fn add_loader<TLoader, TAsset>(&mut self, loader: TLoader)
@@ -119,81 +214,163 @@ impl AssetServer {
             self.extension_to_loader_index
                 .insert(extension.to_string(), loader_index);
         }
+        self.loader_name_to_index.insert(
+            get_loader_name(type_name::<TLoader>()).to_string(),
+            loader_index,
+        );
 
         let mut resources = Resources::default();
         resources.insert::<Box<dyn AssetLoader<TAsset>>>(Box::new(loader));
         self.loaders.push(resources);
     }
 
-    pub fn load_asset_folder<P: AsRef<Path>>(
-        &self,
-        path: P,
-    ) -> Result<Vec<HandleId>, AssetServerError> {
-        let root_path = self.get_root_path()?;
-        let asset_folder = root_path.join(path);
-        let handle_ids = self.load_assets_in_folder_recursive(&asset_folder)?;
-        self.asset_folders.write().push(asset_folder);
-        Ok(handle_ids)
+    pub fn add_saver<TSaver, TAsset>(&mut self, saver: TSaver)
+    where
+        TSaver: AssetSaver<TAsset>,
+        TAsset: 'static,
+    {
+        let saver_index = self.savers.len();
+        for extension in saver.extensions().iter() {
+            self.extension_to_saver_index
+                .insert(extension.to_string(), saver_index);
+        }
+
+        let mut resources = Resources::default();
+        resources.insert::<Box<dyn AssetSaver<TAsset>>>(Box::new(saver));
+        self.savers.push(resources);
     }
 
-    pub fn get_handle<T, P: AsRef<Path>>(&self, path: P) -> Option<Handle<T>> {
-        self.asset_info_paths
-            .read()
-            .get(path.as_ref())
-            .map(|handle_id| Handle::from(*handle_id))
+    /// Adds a processor for source files with the processor's extensions. Processors are only used if asset
+    /// processing is enabled with an [AssetProcessingConfig](crate::AssetProcessingConfig).
+    pub fn add_processor<P: AssetProcessor>(&self, processor: P) {
+        if let Some(importer) = self.importer.as_ref() {
+            importer.add_processor(processor);
+        }
     }
 
-    #[cfg(feature = "filesystem_watcher")]
-    fn watch_path_for_changes<P: AsRef<Path>>(
-        filesystem_watcher: &mut Option<FilesystemWatcher>,
-        path: P,
-    ) -> Result<(), AssetServerError> {
-        if let Some(watcher) = filesystem_watcher {
-            watcher
-                .watch(&path)
-                .map_err(|_error| AssetServerError::AssetWatchError {
-                    path: path.as_ref().to_owned(),
-                })?;
+    /// The extension that selects the loader for the file at the given path. This is the extension of the processed
+    /// file if the file is processed.
+    fn get_load_extension(&self, path: &Path) -> Option<String> {
+        if let Some(extension) = self
+            .importer
+            .as_ref()
+            .and_then(|importer| importer.get_processed_extension(path))
+        {
+            return Some(extension);
         }
 
-        Ok(())
+        path.extension()
+            .map(|extension| {
+                extension
+                    .to_str()
+                    .expect("Extension should be a valid string.")
+            })
+            .map(|extension| extension.to_string())
     }
 
-    #[cfg(feature = "filesystem_watcher")]
-    pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
-        let mut filesystem_watcher = self.filesystem_watcher.write();
+    /// Registers the [AssetChannel](crate::AssetChannel) sender of an asset type, which is used to send labeled
+    /// assets of that type to their [Assets] collection
+    pub(crate) fn add_asset_sender<T: Send + 'static>(&self, sender: Sender<AssetResult<T>>) {
+        self.asset_senders
+            .write()
+            .insert(TypeId::of::<T>(), Box::new(sender));
+    }
 
-        let _ = filesystem_watcher.get_or_insert_with(FilesystemWatcher::default);
-        // watch current files
-        let asset_info_paths = self.asset_info_paths.read();
-        for asset_path in asset_info_paths.keys() {
-            Self::watch_path_for_changes(&mut filesystem_watcher, asset_path)?;
+    /// Registers the sender of an [Assets] collection, which is used to create strong handles to its assets
+    pub(crate) fn add_ref_change_sender<T: 'static>(&self, sender: Sender<RefChange>) {
+        self.ref_change_senders
+            .write()
+            .insert(TypeId::of::<T>(), sender);
+    }
+
+    /// Gets a strong handle to the asset with the given id. The handle is weak if `T` was not added with
+    /// [AddAsset::add_asset](crate::AddAsset::add_asset).
+    fn get_strong_handle<T: 'static>(&self, id: HandleId) -> Handle<T> {
+        match self.ref_change_senders.read().get(&TypeId::of::<T>()) {
+            Some(sender) => Handle::strong(id, sender.clone()),
+            None => Handle::from_id(id),
         }
+    }
 
-        Ok(())
+    pub(crate) fn asset_sources(&self) -> AssetSources {
+        self.asset_sources.clone()
+    }
+
+    pub(crate) fn asset_senders(&self) -> AssetSenders {
+        self.asset_senders.clone()
+    }
+
+    pub(crate) fn ref_change_senders(&self) -> RefChangeSenders {
+        self.ref_change_senders.clone()
+    }
+
+    /// The source that assets are read from
+    pub fn asset_io(&self) -> Arc<dyn AssetIo> {
+        self.asset_io.clone()
+    }
+
+    /// The importer that processes assets, if asset processing is enabled
+    pub fn importer(&self) -> Option<Arc<AssetImporter>> {
+        self.importer.clone()
+    }
+
+    pub fn load_asset_folder<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<HandleId>, AssetServerError> {
+        let asset_folder = path.as_ref();
+        let handle_ids = self.load_assets_in_folder_recursive(asset_folder)?;
+        self.asset_folders.write().push(asset_folder.to_owned());
+        Ok(handle_ids)
     }
 
-    fn get_root_path(&self) -> Result<PathBuf, AssetServerError> {
-        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-            Ok(PathBuf::from(manifest_dir))
+    /// Gets a strong handle to the asset at the given path, if its file has been loaded (or is currently loading).
+    /// Labeled assets can be retrieved using paths of the form `path/to/file.ext#Label`.
+    pub fn get_handle<'a, T: 'static, P: Into<AssetPath<'a>>>(&self, path: P) -> Option<Handle<T>> {
+        let asset_path_id = path.into().get_id();
+        if self
+            .asset_sources
+            .read()
+            .contains_key(&asset_path_id.source_path_id())
+        {
+            Some(self.get_strong_handle(HandleId::from(asset_path_id)))
         } else {
-            match env::current_exe() {
-                Ok(exe_path) => exe_path
-                    .parent()
-                    .ok_or(AssetServerError::InvalidRootPath)
-                    .map(|exe_parent_path| exe_parent_path.to_owned()),
-                Err(err) => Err(AssetServerError::Io(err)),
+            None
+        }
+    }
+
+    /// Reloads assets when their files or meta files change. Whether this is supported depends on the [AssetIo].
+    pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
+        self.asset_io.watch_for_changes()?;
+        self.watching_for_changes.store(true, Ordering::Relaxed);
+        // watch current files
+        let asset_sources = self.asset_sources.read();
+        for source_info in asset_sources.values() {
+            self.asset_io.watch_path_for_changes(&source_info.path)?;
+            if source_info.has_meta {
+                self.asset_io
+                    .watch_path_for_changes(&get_meta_path(&source_info.path))?;
             }
         }
+
+        Ok(())
     }
 
+    /// Starts loading the file at the given path and returns the handle of the requested asset. Labeled assets can be
+    /// loaded using paths of the form `path/to/file.ext#Label`.
     // TODO: add type checking here. people shouldn't be able to request a Handle<Texture> for a Mesh asset
-    pub fn load<T, P: AsRef<Path>>(&self, path: P) -> Result<Handle<T>, AssetServerError> {
-        self.load_untyped(self.get_root_path()?.join(path))
-            .map(Handle::from)
+    pub fn load<'a, T: 'static, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+    ) -> Result<Handle<T>, AssetServerError> {
+        self.load_untyped(path)
+            .map(|handle_id| self.get_strong_handle(handle_id))
     }
 
-    pub fn load_sync<T: Resource, P: AsRef<Path>>(
+    /// Loads the file at the given path on the calling thread and adds its main asset to `assets`. Labeled assets of
+    /// the file are added to their [Assets] collections the next time assets are loaded, so the handle of a labeled
+    /// path refers to an asset that doesn't exist yet.
+    pub fn load_sync<'a, T: Resource, P: Into<AssetPath<'a>>>(
         &self,
         assets: &mut Assets<T>,
         path: P,
@@ -201,91 +378,203 @@ impl AssetServer {
     where
         T: 'static,
     {
-        let path = path.as_ref();
-        if let Some(ref extension) = path.extension() {
-            if let Some(index) = self.extension_to_loader_index.get(
-                extension
-                    .to_str()
-                    .expect("extension should be a valid string"),
-            ) {
-                let mut asset_info_paths = self.asset_info_paths.write();
-                let handle_id = HandleId::new();
-                let resources = &self.loaders[*index];
-                let loader = resources.get::<Box<dyn AssetLoader<T>>>().unwrap();
-                let asset = loader.load_from_file(path)?;
-                let handle = Handle::from(handle_id);
-
-                assets.set(handle, asset);
-                asset_info_paths.insert(path.to_owned(), handle_id);
-                Ok(handle)
-            } else {
-                Err(AssetServerError::MissingAssetHandler)
+        let asset_path = path.into();
+        let source_path = asset_path.path();
+        let meta = future::block_on(load_asset_meta(&*self.asset_io, source_path))?;
+        let index = match meta.as_ref().and_then(|meta| meta.loader()) {
+            Some(loader) => *self
+                .loader_name_to_index
+                .get(loader)
+                .ok_or_else(|| AssetServerError::UnknownLoader(loader.to_string()))?,
+            None => *self
+                .get_load_extension(source_path)
+                .and_then(|extension| self.extension_to_loader_index.get(&extension))
+                .ok_or(AssetServerError::MissingAssetHandler)?,
+        };
+        let loader = self.loaders[index]
+            .get::<Box<dyn AssetLoader<T>>>()
+            .ok_or(AssetServerError::MissingAssetLoader)?;
+        if let Some(loader_name) = meta.as_ref().and_then(|meta| meta.loader()) {
+            check_processed_loader(
+                self.importer.as_deref(),
+                source_path,
+                loader_name,
+                loader.extensions(),
+            )?;
+        }
+        let meta = meta.unwrap_or_default();
+        let mut load_context = LoadContext::with_asset_io(source_path, self.asset_io.clone())
+            .with_meta(meta.clone())
+            .with_ref_change_senders(self.ref_change_senders.clone());
+        let asset = future::block_on(async {
+            let bytes = load_source_bytes(
+                &*self.asset_io,
+                self.importer.as_deref(),
+                source_path,
+                &meta,
+            )
+            .await?;
+            loader
+                .load(bytes, &mut load_context)
+                .await
+                .map_err(AssetLoadError::LoaderError)
+        })?;
+        if let Some(label) = asset_path.label() {
+            let (_, type_id) = load_context
+                .iter()
+                .find(|(asset_label, _)| *asset_label == label)
+                .ok_or_else(|| AssetServerError::MissingLabeledAsset {
+                    label: label.to_string(),
+                })?;
+            if type_id != TypeId::of::<T>() {
+                return Err(AssetServerError::IncorrectAssetType {
+                    label: label.to_string(),
+                });
             }
-        } else {
-            Err(AssetServerError::MissingAssetHandler)
         }
+        let version = self.start_loading(source_path);
+        self.set_asset_labels(source_path, &load_context, version);
+
+        assets.set(
+            HandleId::from(AssetPath::new_ref(source_path, None).get_id()),
+            asset,
+        );
+        self.commit_asset(
+            &AssetPath::new_ref(source_path, None),
+            TypeId::of::<T>(),
+            version,
+        );
+        // labeled assets are added to their Assets collections the next time assets are loaded
+        load_context.send(&self.asset_senders, version);
+
+        Ok(assets.get_handle(HandleId::from(asset_path.get_id())))
     }
 
-    pub fn load_untyped<P: AsRef<Path>>(&self, path: P) -> Result<HandleId, AssetServerError> {
-        let path = path.as_ref();
-        if let Some(ref extension) = path.extension() {
-            if let Some(index) = self.extension_to_handler_index.get(
-                extension
-                    .to_str()
-                    .expect("Extension should be a valid string."),
-            ) {
-                let mut new_version = 0;
-                let handle_id = {
-                    let mut asset_info = self.asset_info.write();
-                    let mut asset_info_paths = self.asset_info_paths.write();
-                    if let Some(asset_info) = asset_info_paths
-                        .get(path)
-                        .and_then(|handle_id| asset_info.get_mut(&handle_id))
-                    {
-                        asset_info.load_state =
-                            if let LoadState::Loaded(_version) = asset_info.load_state {
-                                new_version += 1;
-                                LoadState::Loading(new_version)
-                            } else {
-                                LoadState::Loading(new_version)
-                            };
-                        asset_info.handle_id
-                    } else {
-                        let handle_id = HandleId::new();
-                        asset_info.insert(
-                            handle_id,
-                            AssetInfo {
-                                handle_id,
-                                path: path.to_owned(),
-                                load_state: LoadState::Loading(new_version),
-                            },
-                        );
-                        asset_info_paths.insert(path.to_owned(), handle_id);
-                        handle_id
-                    }
-                };
+    /// Saves the asset to the file at the given path on the calling thread, using the [AssetSaver] for the path's
+    /// extension. Saving an asset to the file it was loaded from doesn't reload it when the file is watched for
+    /// changes.
+    pub fn save<'a, T: Resource, P: Into<AssetPath<'a>>>(
+        &self,
+        assets: &Assets<T>,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Result<(), AssetServerError> {
+        let asset_path = path.into();
+        let path = asset_path.path();
+        let asset = assets.get(handle).ok_or(AssetServerError::MissingAsset)?;
+        let index = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| self.extension_to_saver_index.get(extension))
+            .ok_or(AssetServerError::MissingAssetSaver)?;
+        let saver = self.savers[*index]
+            .get::<Box<dyn AssetSaver<T>>>()
+            .ok_or(AssetServerError::MissingAssetSaver)?;
+        let bytes = saver
+            .to_bytes(path, asset)
+            .map_err(AssetServerError::SaverError)?;
 
-                let load_request = LoadRequest {
-                    handle_id,
-                    path: path.to_owned(),
+        let source_path_id = SourcePathId::from(path);
+        let is_source = self.asset_sources.read().contains_key(&source_path_id)
+            && handle.id == HandleId::from(AssetPath::new_ref(path, None).get_id());
+        if is_source && self.watching_for_changes.load(Ordering::Relaxed) {
+            self.saved_paths.write().insert(path.to_owned());
+        }
+        if let Err(err) = future::block_on(self.asset_io.save_path(path, bytes)) {
+            self.saved_paths.write().remove(path);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Marks the file at the given path as loading and returns the version of the new load
+    fn start_loading(&self, path: &Path) -> AssetVersion {
+        let mut asset_sources = self.asset_sources.write();
+        let source_info = asset_sources
+            .entry(SourcePathId::from(path))
+            .and_modify(|source_info| {
+                let version = source_info.load_state.get_version() + 1;
+                source_info.load_state = LoadState::Loading(version);
+                source_info.asset_labels.clear();
+                source_info.committed_assets.clear();
+            })
+            .or_insert_with(|| SourceInfo {
+                path: path.to_owned(),
+                load_state: LoadState::Loading(0),
+                asset_labels: Default::default(),
+                committed_assets: Default::default(),
+                asset_types: Default::default(),
+                dependencies: Default::default(),
+                has_meta: false,
+            });
+        source_info.load_state.get_version()
+    }
+
+    /// Records which assets were loaded from the file at the given path and which assets they depend on
+    pub(crate) fn set_asset_labels(
+        &self,
+        path: &Path,
+        load_context: &LoadContext,
+        version: AssetVersion,
+    ) {
+        set_asset_labels(&self.asset_sources, path, load_context, version);
+    }
+
+    pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+    ) -> Result<HandleId, AssetServerError> {
+        let asset_path = path.into();
+        let source_path = asset_path.path();
+        if let Some(extension) = self.get_load_extension(source_path) {
+            if let Some(index) = self.extension_to_handler_index.get(&extension) {
+                let version = self.start_loading(source_path);
+                let mut load_request = LoadRequest {
+                    handle_id: HandleId::from(AssetPath::new_ref(source_path, None).get_id()),
+                    asset_path: AssetPath::new(source_path.to_owned(), None),
                     handler_index: *index,
-                    version: new_version,
+                    version,
+                    meta: AssetMeta::default(),
                 };
 
+                let asset_io = self.asset_io.clone();
+                let importer = self.importer.clone();
                 let asset_handlers = self.asset_handlers.clone();
+                let asset_sources = self.asset_sources.clone();
                 self.task_pool
                     .spawn(async move {
-                        let handlers = asset_handlers.read();
-                        let request_handler = &handlers[load_request.handler_index];
-                        request_handler.handle_request(&load_request);
+                        if let Err(err) = read_request_meta(
+                            &*asset_io,
+                            importer.as_deref(),
+                            &asset_handlers,
+                            &asset_sources,
+                            &mut load_request,
+                        )
+                        .await
+                        {
+                            log::error!(
+                                "Failed to load asset {}: {:?}",
+                                load_request.asset_path,
+                                err
+                            );
+                            set_load_state(
+                                &asset_sources,
+                                load_request.handle_id,
+                                LoadState::Failed(load_request.version),
+                            );
+                            return;
+                        }
+
+                        let request_handler =
+                            asset_handlers.read()[load_request.handler_index].clone();
+                        request_handler.handle_request(&load_request).await;
                     })
                     .detach();
 
                 // TODO: watching each asset explicitly is a simpler implementation, its possible it would be more efficient to watch
                 // folders instead (when possible)
-                #[cfg(feature = "filesystem_watcher")]
-                Self::watch_path_for_changes(&mut self.filesystem_watcher.write(), path)?;
-                Ok(handle_id)
+                self.asset_io.watch_path_for_changes(source_path)?;
+                Ok(HandleId::from(asset_path.get_id()))
             } else {
                 Err(AssetServerError::MissingAssetHandler)
             }
@@ -294,25 +583,116 @@ impl AssetServer {
         }
     }
 
-    pub fn set_load_state(&self, handle_id: HandleId, load_state: LoadState) {
-        if let Some(asset_info) = self.asset_info.write().get_mut(&handle_id) {
-            if load_state.get_version() >= asset_info.load_state.get_version() {
-                asset_info.load_state = load_state;
+    /// Marks an asset as added to its [Assets] collection. Once all of a file's assets have been committed, the
+    /// file is considered loaded.
+    ///
+    /// Committing a file's main asset starts loading the file's dependencies. Once a reloaded file is loaded, the
+    /// assets that depend on it are marked as modified.
+    pub(crate) fn commit_asset(
+        &self,
+        asset_path: &AssetPath,
+        type_id: TypeId,
+        version: AssetVersion,
+    ) {
+        let asset_path_id = asset_path.get_id();
+        let source_path_id = asset_path_id.source_path_id();
+        let mut dependencies = Vec::new();
+        let mut reloaded = false;
+        {
+            let mut asset_sources = self.asset_sources.write();
+            if let Some(source_info) = asset_sources.get_mut(&source_path_id) {
+                // ignore assets from outdated loads
+                if source_info.load_state.get_version() != version {
+                    return;
+                }
+                source_info
+                    .committed_assets
+                    .insert(asset_path_id.label_id());
+                source_info
+                    .asset_types
+                    .insert(asset_path_id.label_id(), type_id);
+                if asset_path.label().is_none() {
+                    dependencies.extend(source_info.dependencies.iter().cloned());
+                }
+                if source_info.is_loaded() {
+                    source_info.load_state = LoadState::Loaded(version);
+                    reloaded = version > 0;
+                }
+            }
+
+            dependencies.retain(|dependency| {
+                !asset_sources.contains_key(&dependency.get_id().source_path_id())
+            });
+            if reloaded {
+                self.mark_dependents_modified(&asset_sources, source_path_id);
+            }
+        }
+
+        for dependency in dependencies {
+            if let Err(err) = self.load_untyped(&dependency) {
+                log::warn!("Failed to load asset dependency {}: {:?}", dependency, err);
             }
         }
     }
 
+    /// Marks every asset that directly or indirectly depends on the given file as modified
+    fn mark_dependents_modified(
+        &self,
+        asset_sources: &HashMap<SourcePathId, SourceInfo>,
+        source_path_id: SourcePathId,
+    ) {
+        let mut modified_dependents = self.modified_dependents.write();
+        let mut visited = HashSet::default();
+        let mut sources = vec![source_path_id];
+        while let Some(source_path_id) = sources.pop() {
+            for (dependent_id, dependent_info) in asset_sources.iter() {
+                if !dependent_info.depends_on(source_path_id) || !visited.insert(*dependent_id) {
+                    continue;
+                }
+                for (label_id, type_id) in dependent_info.asset_types.iter() {
+                    let handle_id = HandleId::from(AssetPathId::new(*dependent_id, *label_id));
+                    modified_dependents
+                        .entry(*type_id)
+                        .or_insert_with(Vec::new)
+                        .push(handle_id);
+                }
+                sources.push(*dependent_id);
+            }
+        }
+    }
+
+    /// Takes the assets of type `T` that need to be marked as modified because one of their dependencies was reloaded
+    pub(crate) fn take_modified_dependents<T: 'static>(&self) -> Vec<HandleId> {
+        self.modified_dependents
+            .write()
+            .remove(&TypeId::of::<T>())
+            .unwrap_or_default()
+    }
+
+    /// Sets the load state of the file the asset with the given id was loaded from
+    pub fn set_load_state(&self, handle_id: HandleId, load_state: LoadState) {
+        set_load_state(&self.asset_sources, handle_id, load_state);
+    }
+
+    /// Gets the load state of the asset with the given id. An asset is only considered loaded once all of its
+    /// dependencies are loaded, and it is considered failed if any of its dependencies failed to load.
     pub fn get_load_state_untyped(&self, handle_id: HandleId) -> Option<LoadState> {
-        self.asset_info
-            .read()
-            .get(&handle_id)
-            .map(|asset_info| asset_info.load_state.clone())
+        get_recursive_load_state(
+            &self.asset_sources.read(),
+            AssetPathId::from(handle_id),
+            &mut HashSet::default(),
+        )
     }
 
     pub fn get_load_state<T>(&self, handle: Handle<T>) -> Option<LoadState> {
         self.get_load_state_untyped(handle.id)
     }
 
+    /// Gets the load state of the asset at the given path, which can contain a label
+    pub fn get_path_load_state<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Option<LoadState> {
+        self.get_load_state_untyped(HandleId::from(path.into().get_id()))
+    }
+
     pub fn get_group_load_state(&self, handle_ids: &[HandleId]) -> Option<LoadState> {
         let mut load_state = LoadState::Loaded(0);
         for handle_id in handle_ids.iter() {
@@ -333,26 +713,18 @@ impl AssetServer {
         &self,
         path: &Path,
     ) -> Result<Vec<HandleId>, AssetServerError> {
-        if !path.is_dir() {
+        if !self.asset_io.is_directory(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let root_path = self.get_root_path()?;
         let mut handle_ids = Vec::new();
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let child_path = entry.path();
-            if child_path.is_dir() {
+        for child_path in self.asset_io.read_directory(path)? {
+            if self.asset_io.is_directory(&child_path) {
                 handle_ids.extend(self.load_assets_in_folder_recursive(&child_path)?);
             } else {
-                let relative_child_path = child_path.strip_prefix(&root_path).unwrap();
-                let handle = match self.load_untyped(
-                    relative_child_path
-                        .to_str()
-                        .expect("Path should be a valid string"),
-                ) {
+                let handle = match self.load_untyped(child_path.as_path()) {
                     Ok(handle) => handle,
                     Err(AssetServerError::MissingAssetHandler) => continue,
                     Err(err) => return Err(err),
@@ -366,42 +738,375 @@ impl AssetServer {
     }
 }
 
-#[cfg(feature = "filesystem_watcher")]
-pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
+fn get_recursive_load_state(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    asset_path_id: AssetPathId,
+    visited: &mut HashSet<SourcePathId>,
+) -> Option<LoadState> {
+    let source_info = asset_sources.get(&asset_path_id.source_path_id())?;
+    let load_state = source_info.get_asset_load_state(asset_path_id.label_id());
+    // dependency cycles are only followed once
+    if !visited.insert(asset_path_id.source_path_id()) {
+        return Some(load_state);
+    }
 
-    loop {
-        let result = {
-            let rwlock_guard = asset_server.filesystem_watcher.read();
-            if let Some(filesystem_watcher) = rwlock_guard.as_ref() {
-                filesystem_watcher.receiver.try_recv()
-            } else {
-                break;
+    if let LoadState::Loaded(version) = load_state {
+        let mut dependencies_loaded = true;
+        for dependency in source_info.dependencies.iter() {
+            match get_recursive_load_state(asset_sources, dependency.get_id(), visited) {
+                Some(LoadState::Loaded(_)) => {}
+                Some(LoadState::Loading(_)) => dependencies_loaded = false,
+                // dependencies that couldn't be loaded at all are treated as failed
+                Some(LoadState::Failed(_)) | None => return Some(LoadState::Failed(version)),
             }
-        };
-        let event = match result {
-            Ok(result) => result.unwrap(),
-            Err(TryRecvError::Empty) => break,
-            Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected"),
-        };
-        if let notify::event::Event {
-            kind: notify::event::EventKind::Modify(_),
-            paths,
-            ..
-        } = event
-        {
-            for path in paths.iter() {
-                if !changed.contains(path) {
-                    let root_path = asset_server.get_root_path().unwrap();
-                    let relative_path = path.strip_prefix(root_path).unwrap();
-                    match asset_server.load_untyped(relative_path) {
-                        Ok(_) => {}
-                        Err(AssetServerError::AssetLoadError(error)) => panic!("{:?}", error),
-                        Err(_) => {}
-                    }
+        }
+
+        if !dependencies_loaded {
+            return Some(LoadState::Loading(version));
+        }
+    }
+
+    Some(load_state)
+}
+
+/// Sets the load state of the file the asset with the given id was loaded from
+fn set_load_state(asset_sources: &AssetSources, handle_id: HandleId, load_state: LoadState) {
+    let asset_path_id = AssetPathId::from(handle_id);
+    if let Some(source_info) = asset_sources
+        .write()
+        .get_mut(&asset_path_id.source_path_id())
+    {
+        if load_state.get_version() >= source_info.load_state.get_version() {
+            source_info.load_state = load_state;
+        }
+    }
+}
+
+/// Reads the meta file of the requested file into the request. If the meta file selects a loader, the request is
+/// handled by that loader's handler instead. Meta files are watched for changes along with their file.
+async fn read_request_meta(
+    asset_io: &dyn AssetIo,
+    importer: Option<&AssetImporter>,
+    asset_handlers: &RwLock<Vec<Arc<dyn AssetLoadRequestHandler>>>,
+    asset_sources: &AssetSources,
+    load_request: &mut LoadRequest,
+) -> Result<(), AssetServerError> {
+    let path = load_request.asset_path.path();
+    let meta = match load_asset_meta(asset_io, path).await? {
+        Some(meta) => meta,
+        None => return Ok(()),
+    };
+
+    if let Some(loader) = meta.loader() {
+        let asset_handlers = asset_handlers.read();
+        load_request.handler_index = asset_handlers
+            .iter()
+            .position(|handler| get_loader_name(handler.loader_name()) == loader)
+            .ok_or_else(|| AssetServerError::UnknownLoader(loader.to_string()))?;
+        check_processed_loader(
+            importer,
+            path,
+            loader,
+            asset_handlers[load_request.handler_index].extensions(),
+        )?;
+    }
+    asset_io.watch_path_for_changes(&get_meta_path(path))?;
+    if let Some(source_info) = asset_sources.write().get_mut(&SourcePathId::from(path)) {
+        source_info.has_meta = true;
+    }
+    load_request.meta = meta;
+    Ok(())
+}
+
+/// Processed files are loaded by the loader of the processed file's extension, so a loader selected in a meta file must
+/// be able to load the processed file instead of the source file
+fn check_processed_loader(
+    importer: Option<&AssetImporter>,
+    path: &Path,
+    loader: &str,
+    loader_extensions: &[&str],
+) -> Result<(), AssetServerError> {
+    match importer.and_then(|importer| importer.get_processed_extension(path)) {
+        Some(processed_extension) if !loader_extensions.contains(&processed_extension.as_str()) => {
+            Err(AssetServerError::LoaderCannotLoadProcessedAsset {
+                loader: loader.to_string(),
+                processed_extension,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Records which assets were loaded from the file at the given path and which assets they depend on
+pub(crate) fn set_asset_labels(
+    asset_sources: &AssetSources,
+    path: &Path,
+    load_context: &LoadContext,
+    version: AssetVersion,
+) {
+    let mut asset_sources = asset_sources.write();
+    if let Some(source_info) = asset_sources.get_mut(&SourcePathId::from(path)) {
+        if source_info.load_state.get_version() != version {
+            return;
+        }
+        source_info.asset_labels.clear();
+        source_info.asset_labels.insert(LabelId::from(None));
+        for (label, _type_id) in load_context.iter() {
+            source_info.asset_labels.insert(LabelId::from(Some(label)));
+        }
+        source_info.dependencies = load_context.dependencies().cloned().collect();
+    }
+}
+
+/// Reloads the assets whose files changed, if [AssetServer::watch_for_changes] was called
+pub fn reload_changed_assets_system(asset_server: Res<AssetServer>) {
+    for path in asset_server.asset_io.changed_paths() {
+        if asset_server.saved_paths.write().remove(&path) {
+            continue;
+        }
+        // changing a meta file reloads the file it belongs to
+        let path = get_meta_asset_path(&path).unwrap_or(path);
+        match asset_server.load_untyped(path.as_path()) {
+            Ok(_) => {}
+            Err(AssetServerError::AssetLoadError(error)) => panic!("{:?}", error),
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loaded_source(path: &str, dependencies: &[&str]) -> (SourcePathId, SourceInfo) {
+        let path = Path::new(path);
+        let mut labels = HashSet::default();
+        labels.insert(LabelId::from(None));
+        (
+            SourcePathId::from(path),
+            SourceInfo {
+                path: path.to_owned(),
+                load_state: LoadState::Loaded(0),
+                asset_labels: labels.clone(),
+                committed_assets: labels,
+                asset_types: Default::default(),
+                dependencies: dependencies
+                    .iter()
+                    .map(|dependency| AssetPath::from(*dependency).to_owned())
+                    .collect(),
+                has_meta: false,
+            },
+        )
+    }
+
+    fn load_state(asset_sources: &HashMap<SourcePathId, SourceInfo>, path: &str) -> LoadState {
+        get_recursive_load_state(
+            asset_sources,
+            AssetPath::from(path).get_id(),
+            &mut HashSet::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn dependency_load_state() {
+        let mut asset_sources = HashMap::default();
+        asset_sources.extend(vec![
+            loaded_source("scene.scn", &["mesh.gltf#Mesh0/Primitive0"]),
+            loaded_source("mesh.gltf", &["texture.png"]),
+            loaded_source("texture.png", &[]),
+            loaded_source("cycle.scn", &["cycle.scn"]),
+        ]);
+        asset_sources
+            .get_mut(&SourcePathId::from(Path::new("mesh.gltf")))
+            .unwrap()
+            .asset_labels
+            .insert(LabelId::from(Some("Mesh0/Primitive0")));
+        assert_eq!(
+            load_state(&asset_sources, "scene.scn"),
+            LoadState::Loaded(0)
+        );
+        assert_eq!(
+            load_state(&asset_sources, "cycle.scn"),
+            LoadState::Loaded(0)
+        );
+
+        let texture_id = SourcePathId::from(Path::new("texture.png"));
+        // reloading clears the committed assets
+        let texture_info = asset_sources.get_mut(&texture_id).unwrap();
+        texture_info.load_state = LoadState::Loading(1);
+        texture_info.committed_assets.clear();
+        assert_eq!(
+            load_state(&asset_sources, "scene.scn"),
+            LoadState::Loading(0)
+        );
+
+        asset_sources.get_mut(&texture_id).unwrap().load_state = LoadState::Failed(1);
+        assert_eq!(
+            load_state(&asset_sources, "scene.scn"),
+            LoadState::Failed(0)
+        );
+        assert_eq!(
+            load_state(&asset_sources, "mesh.gltf"),
+            LoadState::Failed(0)
+        );
+    }
+
+    struct TextLoader;
+
+    impl AssetLoader<String> for TextLoader {
+        fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<String> {
+            Ok(String::from_utf8(bytes)?)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Loads the whole text as the main asset, and each line and the number of lines as labeled assets
+    struct LinesLoader;
+
+    impl AssetLoader<String> for LinesLoader {
+        fn extensions(&self) -> &[&str] {
+            &["lines"]
+        }
+
+        fn load<'a>(
+            &'a self,
+            bytes: Vec<u8>,
+            load_context: &'a mut LoadContext,
+        ) -> bevy_utils::BoxedFuture<'a, Result<String>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes)?;
+                for (index, line) in text.lines().enumerate() {
+                    load_context.set(&format!("Line{}", index), line.to_string());
                 }
-            }
-            changed.extend(paths);
+                load_context.set("Count", text.lines().count());
+                Ok(text)
+            })
+        }
+    }
+
+    #[test]
+    fn load_labeled_asset_sync() {
+        let asset_io = crate::MemoryAssetIo::new();
+        asset_io.insert("a.lines", b"a\nb".to_vec());
+        let mut asset_server = AssetServer::new(Arc::new(asset_io), None, TaskPool::new());
+        asset_server.add_loader(LinesLoader);
+        let asset_channel = crate::AssetChannel::<String>::new();
+        asset_server.add_asset_sender(asset_channel.sender.clone());
+        let count_channel = crate::AssetChannel::<usize>::new();
+        asset_server.add_asset_sender(count_channel.sender.clone());
+
+        let mut assets = Assets::<String>::default();
+        assert!(matches!(
+            asset_server.load_sync(&mut assets, "a.lines#Line2"),
+            Err(AssetServerError::MissingLabeledAsset { label }) if label == "Line2"
+        ));
+        assert!(matches!(
+            asset_server.load_sync(&mut assets, "a.lines#Count"),
+            Err(AssetServerError::IncorrectAssetType { label }) if label == "Count"
+        ));
+
+        let handle = asset_server
+            .load_sync(&mut assets, "a.lines#Line1")
+            .unwrap();
+        let line = asset_channel
+            .receiver
+            .try_iter()
+            .find(|result| result.handle == handle)
+            .unwrap();
+        assert_eq!(line.result.unwrap(), "b");
+    }
+
+    struct TextSaver;
+
+    impl AssetSaver<String> for TextSaver {
+        fn to_bytes(&self, _asset_path: &Path, asset: &String) -> Result<Vec<u8>> {
+            Ok(asset.as_bytes().to_vec())
         }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn save_asset() {
+        let asset_io = crate::MemoryAssetIo::new();
+        asset_io.insert("a.txt", b"a".to_vec());
+        let mut asset_server = AssetServer::new(Arc::new(asset_io.clone()), None, TaskPool::new());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_saver(TextSaver);
+
+        let mut assets = Assets::<String>::default();
+        let handle = asset_server.load_sync(&mut assets, "a.txt").unwrap();
+        assets.set(&handle, "b".to_string());
+        // without watching, the change is never reported, so there's nothing to skip
+        asset_server.save(&assets, &handle, "a.txt").unwrap();
+        assert!(asset_server.saved_paths.read().is_empty());
+
+        asset_server.watch_for_changes().unwrap();
+        asset_server.save(&assets, &handle, "a.txt").unwrap();
+        asset_server.save(&assets, &handle, "copy.txt").unwrap();
+        assert!(matches!(
+            asset_server.save(&assets, &handle, "a.bin"),
+            Err(AssetServerError::MissingAssetSaver)
+        ));
+
+        // saving an asset to its own file doesn't reload it, saving it to another file does
+        assert!(asset_server.saved_paths.read().contains(Path::new("a.txt")));
+        assert!(!asset_server
+            .saved_paths
+            .read()
+            .contains(Path::new("copy.txt")));
+        let copy = asset_server.load_sync(&mut assets, "copy.txt").unwrap();
+        assert_eq!(assets.get(&copy).map(String::as_str), Some("b"));
+    }
+
+    struct ReverseProcessor;
+
+    impl crate::AssetProcessor for ReverseProcessor {
+        fn process(
+            &self,
+            _asset_path: &Path,
+            mut bytes: Vec<u8>,
+            _meta: &AssetMeta,
+        ) -> Result<Vec<u8>> {
+            bytes.reverse();
+            Ok(bytes)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn processed_extension(&self) -> &str {
+            "rev"
+        }
+    }
+
+    #[test]
+    fn reject_meta_loader_of_processed_asset() {
+        let asset_io = crate::MemoryAssetIo::new();
+        asset_io.insert("a.txt", b"ab".to_vec());
+        asset_io.insert("a.txt.meta", br#"(loader: "TextLoader")"#.to_vec());
+        let importer = crate::AssetImporter::new(std::env::temp_dir().join(format!(
+            "bevy_asset_meta_loader_test_{}",
+            std::process::id()
+        )));
+        let mut asset_server =
+            AssetServer::new(Arc::new(asset_io), Some(importer), TaskPool::new());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_processor(ReverseProcessor);
+
+        // TextLoader can't load the processed `.rev` file
+        let mut assets = Assets::<String>::default();
+        assert!(matches!(
+            asset_server.load_sync(&mut assets, "a.txt"),
+            Err(AssetServerError::LoaderCannotLoadProcessedAsset { processed_extension, .. })
+                if processed_extension == "rev"
+        ));
     }
 }
@@ -1,13 +1,14 @@
 use crate::{
-    update_asset_storage_system, AssetChannel, AssetLoader, AssetServer, ChannelAssetHandler,
-    Handle, HandleId,
+    update_asset_storage_system, AssetChannel, AssetLoader, AssetProcessor, AssetSaver,
+    AssetServer, ChannelAssetHandler, Handle, HandleId, RefChange,
 };
 use bevy_app::{prelude::Events, AppBuilder};
 use bevy_ecs::{FromResources, IntoQuerySystem, ResMut, Resource};
 use bevy_type_registry::RegisterType;
 use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender};
 
-/// Events that happen on assets of type `T`
+/// Events that happen on assets of type `T`. Event handles are weak, so they don't keep their asset alive.
 pub enum AssetEvent<T: Resource> {
     Created { handle: Handle<T> },
     Modified { handle: Handle<T> },
@@ -15,76 +16,118 @@ pub enum AssetEvent<T: Resource> {
 }
 
 /// Stores Assets of a given type and tracks changes to them.
+///
+/// Assets are reference counted by their strong [Handle]s. Assets whose last strong handle was dropped are removed at
+/// the end of the frame. Assets that never had a strong handle are kept until they are removed explicitly.
 pub struct Assets<T: Resource> {
-    assets: HashMap<Handle<T>, T>,
+    assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
+    ref_counts: HashMap<HandleId, usize>,
+    ref_change_sender: Sender<RefChange>,
+    ref_change_receiver: Receiver<RefChange>,
 }
 
 impl<T: Resource> Default for Assets<T> {
     fn default() -> Self {
+        let (ref_change_sender, ref_change_receiver) = crossbeam_channel::unbounded();
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
+            ref_counts: HashMap::default(),
+            ref_change_sender,
+            ref_change_receiver,
         }
     }
 }
 
 impl<T: Resource> Assets<T> {
+    /// Adds the given asset and returns a strong handle to it
     pub fn add(&mut self, asset: T) -> Handle<T> {
-        let handle = Handle::new();
-        self.assets.insert(handle, asset);
-        self.events.send(AssetEvent::Created { handle });
-        handle
+        let id = HandleId::new();
+        self.assets.insert(id, asset);
+        self.events.send(AssetEvent::Created {
+            handle: Handle::from_id(id),
+        });
+        self.get_handle(id)
     }
 
-    pub fn set(&mut self, handle: Handle<T>, asset: T) {
-        let exists = self.assets.contains_key(&handle);
-        self.assets.insert(handle, asset);
+    pub fn set<H: Into<HandleId>>(&mut self, handle: H, asset: T) {
+        let id = handle.into();
+        let exists = self.assets.contains_key(&id);
+        self.assets.insert(id, asset);
 
         if exists {
-            self.events.send(AssetEvent::Modified { handle });
+            self.events.send(AssetEvent::Modified {
+                handle: Handle::from_id(id),
+            });
         } else {
-            self.events.send(AssetEvent::Created { handle });
+            self.events.send(AssetEvent::Created {
+                handle: Handle::from_id(id),
+            });
         }
     }
 
+    /// Sets the "default" asset. The returned handle is weak, as the default asset is never removed automatically.
     pub fn add_default(&mut self, asset: T) -> Handle<T> {
         let handle = Handle::default();
-        let exists = self.assets.contains_key(&handle);
-        self.assets.insert(handle, asset);
-        if exists {
-            self.events.send(AssetEvent::Modified { handle });
-        } else {
-            self.events.send(AssetEvent::Created { handle });
-        }
+        self.set(&handle, asset);
         handle
     }
 
+    /// Returns a strong handle to the asset with the given id
+    pub fn get_handle<H: Into<HandleId>>(&self, handle: H) -> Handle<T> {
+        Handle::strong(handle.into(), self.ref_change_sender.clone())
+    }
+
+    /// The sender that the strong handles of this collection report their ref count changes to
+    pub(crate) fn ref_change_sender(&self) -> Sender<RefChange> {
+        self.ref_change_sender.clone()
+    }
+
+    /// Sends an [AssetEvent::Modified] event for the given asset, if it exists
+    pub fn mark_modified<H: Into<HandleId>>(&mut self, handle: H) {
+        let id = handle.into();
+        if self.assets.contains_key(&id) {
+            self.events.send(AssetEvent::Modified {
+                handle: Handle::from_id(id),
+            });
+        }
+    }
+
     pub fn get_with_id(&self, id: HandleId) -> Option<&T> {
-        self.get(&Handle::from_id(id))
+        self.assets.get(&id)
     }
 
     pub fn get_with_id_mut(&mut self, id: HandleId) -> Option<&mut T> {
-        self.get_mut(&Handle::from_id(id))
+        self.events.send(AssetEvent::Modified {
+            handle: Handle::from_id(id),
+        });
+        self.assets.get_mut(&id)
     }
 
     pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
-        self.assets.get(&handle)
+        self.get_with_id(handle.id)
     }
 
     pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
-        self.events.send(AssetEvent::Modified { handle: *handle });
-        self.assets.get_mut(&handle)
+        self.get_with_id_mut(handle.id)
     }
 
-    pub fn get_or_insert_with(
+    pub fn contains(&self, handle: &Handle<T>) -> bool {
+        self.assets.contains_key(&handle.id)
+    }
+
+    pub fn get_or_insert_with<H: Into<HandleId>>(
         &mut self,
-        handle: Handle<T>,
+        handle: H,
         insert_fn: impl FnOnce() -> T,
     ) -> &mut T {
+        let id = handle.into();
         let mut event = None;
-        let borrowed = self.assets.entry(handle).or_insert_with(|| {
-            event = Some(AssetEvent::Created { handle });
+        let borrowed = self.assets.entry(id).or_insert_with(|| {
+            event = Some(AssetEvent::Created {
+                handle: Handle::from_id(id),
+            });
             insert_fn()
         });
 
@@ -94,22 +137,79 @@ impl<T: Resource> Assets<T> {
         borrowed
     }
 
+    /// Iterates over the assets and weak handles to them
     pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
-        self.assets.iter().map(|(k, v)| (*k, v))
+        self.assets.iter().map(|(k, v)| (Handle::from_id(*k), v))
     }
 
     pub fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
-        self.assets.remove(&handle)
+        self.remove_with_id(handle.id)
+    }
+
+    pub fn remove_with_id(&mut self, id: HandleId) -> Option<T> {
+        let asset = self.assets.remove(&id);
+        if asset.is_some() {
+            self.events.send(AssetEvent::Removed {
+                handle: Handle::from_id(id),
+            });
+        }
+        asset
+    }
+
+    /// Applies the ref count changes of strong handles and removes assets that no longer have any strong handles
+    pub fn free_unused_assets(&mut self) {
+        let mut unused_assets = Vec::new();
+        for ref_change in self.ref_change_receiver.try_iter() {
+            match ref_change {
+                RefChange::Increment(id) => *self.ref_counts.entry(id).or_insert(0) += 1,
+                RefChange::Decrement(id) => {
+                    let ref_count = self
+                        .ref_counts
+                        .get_mut(&id)
+                        .expect("strong handles should be counted before they are dropped");
+                    *ref_count -= 1;
+                    if *ref_count == 0 {
+                        unused_assets.push(id);
+                    }
+                }
+            }
+        }
+
+        for id in unused_assets {
+            // the asset may have gotten a new strong handle after its count dropped to zero
+            if self.ref_counts.get(&id) == Some(&0) {
+                self.ref_counts.remove(&id);
+                self.remove_with_id(id);
+            }
+        }
     }
 
     pub fn asset_event_system(
         mut events: ResMut<Events<AssetEvent<T>>>,
         mut assets: ResMut<Assets<T>>,
     ) {
+        assets.free_unused_assets();
         events.extend(assets.events.drain())
     }
 }
 
+/// Adds the [AssetChannel] that loaded assets of type `T` are sent through, if it doesn't exist yet
+fn init_asset_channel<T: Resource>(app: &mut AppBuilder) {
+    if app.resources().contains::<AssetChannel<T>>() {
+        return;
+    }
+
+    let asset_channel = AssetChannel::<T>::new();
+    if let Some(asset_server) = app.resources().get::<AssetServer>() {
+        asset_server.add_asset_sender(asset_channel.sender.clone());
+    }
+    app.resources_mut().insert(asset_channel);
+    app.add_system_to_stage(
+        crate::stage::LOAD_ASSETS,
+        update_asset_storage_system::<T>.system(),
+    );
+}
+
 /// [AppBuilder] extension methods for adding new asset types
 pub trait AddAsset {
     fn add_asset<T>(&mut self) -> &mut Self
@@ -119,6 +219,13 @@ pub trait AddAsset {
     where
         TLoader: AssetLoader<TAsset> + FromResources,
         TAsset: Send + Sync + 'static;
+    fn add_asset_saver<TAsset, TSaver>(&mut self) -> &mut Self
+    where
+        TSaver: AssetSaver<TAsset> + FromResources,
+        TAsset: Send + Sync + 'static;
+    fn add_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromResources;
 }
 
 impl AddAsset for AppBuilder {
@@ -126,8 +233,17 @@ impl AddAsset for AppBuilder {
     where
         T: Resource,
     {
-        self.init_resource::<Assets<T>>()
-            .register_component::<Handle<T>>()
+        init_asset_channel::<T>(self);
+        self.init_resource::<Assets<T>>();
+        {
+            let resources = self.resources();
+            let assets = resources.get::<Assets<T>>().unwrap();
+            if let Some(asset_server) = resources.get::<AssetServer>() {
+                asset_server.add_ref_change_sender::<T>(assets.ref_change_sender());
+            }
+        }
+
+        self.register_component::<Handle<T>>()
             .add_system_to_stage(
                 super::stage::ASSET_EVENTS,
                 Assets::<T>::asset_event_system.system(),
@@ -141,13 +257,7 @@ impl AddAsset for AppBuilder {
         TAsset: Send + Sync + 'static,
     {
         {
-            if !self.resources().contains::<AssetChannel<TAsset>>() {
-                self.resources_mut().insert(AssetChannel::<TAsset>::new());
-                self.add_system_to_stage(
-                    crate::stage::LOAD_ASSETS,
-                    update_asset_storage_system::<TAsset>.system(),
-                );
-            }
+            init_asset_channel::<TAsset>(self);
             let asset_channel = self
                 .resources()
                 .get::<AssetChannel<TAsset>>()
@@ -160,9 +270,68 @@ impl AddAsset for AppBuilder {
             let handler = ChannelAssetHandler::new(
                 TLoader::from_resources(self.resources()),
                 asset_channel.sender.clone(),
+                asset_server.asset_io(),
+                asset_server.importer(),
+                asset_server.asset_sources(),
+                asset_server.asset_senders(),
+                asset_server.ref_change_senders(),
             );
             asset_server.add_handler(handler);
         }
         self
     }
+
+    fn add_asset_saver<TAsset, TSaver>(&mut self) -> &mut Self
+    where
+        TSaver: AssetSaver<TAsset> + FromResources,
+        TAsset: Send + Sync + 'static,
+    {
+        {
+            let mut asset_server = self
+                .resources()
+                .get_mut::<AssetServer>()
+                .expect("AssetServer does not exist. Consider adding it as a resource.");
+            asset_server.add_saver(TSaver::from_resources(self.resources()));
+        }
+        self
+    }
+
+    fn add_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromResources,
+    {
+        {
+            let asset_server = self
+                .resources()
+                .get::<AssetServer>()
+                .expect("AssetServer does not exist. Consider adding it as a resource.");
+            asset_server.add_processor(T::from_resources(self.resources()));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_unused_assets() {
+        let mut assets = Assets::<u32>::default();
+        let handle = assets.add(1);
+        let second_handle = handle.clone();
+        let weak_handle = handle.clone_weak();
+
+        drop(handle);
+        assets.free_unused_assets();
+        assert_eq!(assets.get(&weak_handle), Some(&1));
+
+        drop(second_handle);
+        assets.free_unused_assets();
+        assert!(assets.get(&weak_handle).is_none());
+        assert!(matches!(
+            assets.events.drain().last(),
+            Some(AssetEvent::Removed { handle }) if handle == weak_handle
+        ));
+    }
 }
@@ -4,6 +4,7 @@ use std::{
 };
 
 use bevy_property::{Properties, Property};
+use crossbeam_channel::Sender;
 use serde::{Deserialize, Serialize};
 use std::{any::TypeId, marker::PhantomData};
 use uuid::Uuid;
@@ -23,9 +24,38 @@ impl HandleId {
     }
 }
 
+/// A change to the number of strong [Handle]s that point to an asset
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RefChange {
+    Increment(HandleId),
+    Decrement(HandleId),
+}
+
+/// Whether a [Handle] keeps its asset alive
+#[derive(Debug)]
+pub enum HandleType {
+    /// Weak handles don't affect the lifetime of their asset
+    Weak,
+    /// Strong handles keep their asset alive. Once the last strong handle to an asset is dropped, the asset is removed
+    /// from its [Assets](crate::Assets) collection.
+    Strong(Sender<RefChange>),
+}
+
+impl Default for HandleType {
+    fn default() -> Self {
+        HandleType::Weak
+    }
+}
+
 /// A handle into a specific Asset of type `T`
 ///
 /// Handles contain a unique id that corresponds to a specific asset in the [Assets](crate::Assets) collection.
+/// Strong handles are reference counted: once every strong handle to an asset has been dropped, the asset is removed
+/// at the end of the frame. Weak handles (created with [Handle::clone_weak] or from an id) don't keep their asset
+/// alive.
+///
+/// Only the id of a handle is a property, so handles that are deserialized (for example as part of a scene) are weak.
+/// Use [AssetServer::get_handle](crate::AssetServer::get_handle) to get a strong handle to an asset loaded from a path.
 #[derive(Properties)]
 pub struct Handle<T>
 where
@@ -33,17 +63,42 @@ where
 {
     pub id: HandleId,
     #[property(ignore)]
+    handle_type: HandleType,
+    #[property(ignore)]
     marker: PhantomData<T>,
 }
 
 impl<T> Handle<T> {
     pub fn new() -> Self {
+        Handle::from_id(HandleId::new())
+    }
+
+    /// Creates a strong handle. If the [Assets](crate::Assets) collection has already been dropped there is no asset to
+    /// keep alive, so a weak handle is returned instead.
+    pub(crate) fn strong(id: HandleId, ref_change_sender: Sender<RefChange>) -> Self {
+        if ref_change_sender.send(RefChange::Increment(id)).is_err() {
+            return Handle::from_id(id);
+        }
         Handle {
-            id: HandleId::new(),
+            id,
+            handle_type: HandleType::Strong(ref_change_sender),
             marker: PhantomData,
         }
     }
 
+    /// Returns a weak handle to the same asset
+    pub fn clone_weak(&self) -> Self {
+        Handle::from_id(self.id)
+    }
+
+    pub fn is_strong(&self) -> bool {
+        matches!(self.handle_type, HandleType::Strong(_))
+    }
+
+    pub fn is_weak(&self) -> bool {
+        matches!(self.handle_type, HandleType::Weak)
+    }
+
     /// Gets a handle for the given type that has this handle's id. This is useful when an
     /// asset is derived from another asset. In this case, a common handle can be used to
     /// correlate them.
@@ -55,6 +110,7 @@ impl<T> Handle<T> {
     pub const fn from_id(id: HandleId) -> Self {
         Handle {
             id,
+            handle_type: HandleType::Weak,
             marker: PhantomData,
         }
     }
@@ -62,6 +118,7 @@ impl<T> Handle<T> {
     pub const fn from_u128(value: u128) -> Self {
         Handle {
             id: HandleId(Uuid::from_u128(value)),
+            handle_type: HandleType::Weak,
             marker: PhantomData,
         }
     }
@@ -69,6 +126,7 @@ impl<T> Handle<T> {
     pub const fn from_bytes(bytes: [u8; 16]) -> Self {
         Handle {
             id: HandleId(Uuid::from_bytes(bytes)),
+            handle_type: HandleType::Weak,
             marker: PhantomData,
         }
     }
@@ -109,10 +167,7 @@ where
 {
     fn from(handle: HandleUntyped) -> Self {
         if TypeId::of::<T>() == handle.type_id {
-            Handle {
-                id: handle.id,
-                marker: PhantomData::default(),
-            }
+            Handle::from_id(handle.id)
         } else {
             panic!("attempted to convert untyped handle to incorrect typed handle")
         }
@@ -142,24 +197,41 @@ impl<T> Debug for Handle<T> {
 
 impl<T> Default for Handle<T> {
     fn default() -> Self {
-        Handle {
-            id: DEFAULT_HANDLE_ID,
-            marker: PhantomData,
-        }
+        Handle::from_id(DEFAULT_HANDLE_ID)
     }
 }
 
 impl<T> Clone for Handle<T> {
     fn clone(&self) -> Self {
-        Handle {
-            id: self.id,
-            marker: PhantomData,
+        match self.handle_type {
+            HandleType::Strong(ref sender) => Handle::strong(self.id, sender.clone()),
+            HandleType::Weak => Handle::from_id(self.id),
         }
     }
 }
-impl<T> Copy for Handle<T> {}
 
-// SAFE: T is phantom data and Handle::id is an integer
+impl<T> Drop for Handle<T> {
+    fn drop(&mut self) {
+        if let HandleType::Strong(ref sender) = self.handle_type {
+            // the receiver is gone if the Assets collection was dropped first, in which case there is nothing to free
+            let _ = sender.send(RefChange::Decrement(self.id));
+        }
+    }
+}
+
+impl<T> From<&Handle<T>> for HandleId {
+    fn from(handle: &Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+impl<T> From<Handle<T>> for HandleId {
+    fn from(handle: Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+// SAFE: T is phantom data, Handle::id is an integer and the ref change sender is Send + Sync
 unsafe impl<T> Send for Handle<T> {}
 unsafe impl<T> Sync for Handle<T> {}
 
@@ -178,6 +250,18 @@ impl HandleUntyped {
     }
 }
 
+impl<T> From<&Handle<T>> for HandleUntyped
+where
+    T: 'static,
+{
+    fn from(handle: &Handle<T>) -> Self {
+        HandleUntyped {
+            id: handle.id,
+            type_id: TypeId::of::<T>(),
+        }
+    }
+}
+
 impl<T> From<Handle<T>> for HandleUntyped
 where
     T: 'static,
@@ -189,3 +273,19 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Assets;
+
+    #[test]
+    fn clone_after_assets_dropped() {
+        let mut assets = Assets::<u32>::default();
+        let handle = assets.add(1);
+        drop(assets);
+
+        let cloned = handle.clone();
+        assert!(cloned.is_weak());
+        assert_eq!(cloned, handle);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{AssetIo, AssetIoError, MemoryAssetIo};
+use bevy_utils::BoxedFuture;
+use std::{
+    fs::File,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+#[cfg(feature = "zip")]
+use std::io::Seek;
+
+/// Reads assets from a zip or tar archive, which makes it possible to ship packed game data. The archive's files are
+/// read into memory when it is opened. Archives can't be watched for changes.
+pub struct ArchiveAssetIo {
+    files: MemoryAssetIo,
+}
+
+impl ArchiveAssetIo {
+    #[cfg(feature = "zip")]
+    pub fn open_zip<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        Self::from_zip(File::open(path)?)
+    }
+
+    #[cfg(feature = "zip")]
+    pub fn from_zip<R: Read + Seek>(reader: R) -> Result<Self, AssetIoError> {
+        let mut archive = zip::ZipArchive::new(reader).map_err(anyhow::Error::from)?;
+        let files = MemoryAssetIo::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(anyhow::Error::from)?;
+            // entries with paths outside of the archive are skipped
+            let path = match file.enclosed_name() {
+                Some(path) if file.is_file() => normalize_path(path),
+                _ => continue,
+            };
+            let mut bytes = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut bytes)?;
+            files.insert(path, bytes);
+        }
+
+        Ok(ArchiveAssetIo { files })
+    }
+
+    #[cfg(feature = "tar")]
+    pub fn open_tar<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        Self::from_tar(File::open(path)?)
+    }
+
+    #[cfg(feature = "tar")]
+    pub fn from_tar<R: Read>(reader: R) -> Result<Self, AssetIoError> {
+        let mut archive = tar::Archive::new(reader);
+        let files = MemoryAssetIo::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = normalize_path(&entry.path()?);
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut bytes)?;
+            files.insert(path, bytes);
+        }
+
+        Ok(ArchiveAssetIo { files })
+    }
+}
+
+/// Removes `.` components, which archive tools commonly prefix paths with
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+impl AssetIo for ArchiveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        self.files.load_path(path)
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.files.read_directory(path)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.files.is_directory(path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+#[cfg(all(test, feature = "tar"))]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn load_from_tar() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, bytes) in &[("./textures/a.png", &[0u8][..]), ("models/b.gltf", &[1, 2])] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(bytes.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *bytes).unwrap();
+        }
+        let archive = builder.into_inner().unwrap();
+
+        let asset_io = ArchiveAssetIo::from_tar(&archive[..]).unwrap();
+        assert!(asset_io.is_directory(Path::new("textures")));
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("textures/a.png"))).unwrap(),
+            vec![0]
+        );
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("models/b.gltf"))).unwrap(),
+            vec![1, 2]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{AssetIo, AssetIoError};
+use bevy_utils::BoxedFuture;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "filesystem_watcher")]
+use super::filesystem_watcher::FilesystemWatcher;
+#[cfg(feature = "filesystem_watcher")]
+use bevy_utils::HashSet;
+#[cfg(feature = "filesystem_watcher")]
+use crossbeam_channel::TryRecvError;
+#[cfg(feature = "filesystem_watcher")]
+use parking_lot::RwLock;
+
+/// Reads assets from the filesystem, relative to a root path
+pub struct FileAssetIo {
+    root_path: PathBuf,
+    #[cfg(feature = "filesystem_watcher")]
+    filesystem_watcher: RwLock<Option<FilesystemWatcher>>,
+}
+
+impl FileAssetIo {
+    pub fn new<P: Into<PathBuf>>(root_path: P) -> Self {
+        FileAssetIo {
+            root_path: root_path.into(),
+            #[cfg(feature = "filesystem_watcher")]
+            filesystem_watcher: Default::default(),
+        }
+    }
+
+    /// The default root path: the directory of the crate's manifest when run through cargo, otherwise the directory
+    /// of the executable
+    pub fn get_root_path() -> PathBuf {
+        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+            PathBuf::from(manifest_dir)
+        } else {
+            env::current_exe()
+                .expect("Failed to get the path of the executable")
+                .parent()
+                .expect("The executable should be inside a directory")
+                .to_owned()
+        }
+    }
+
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+}
+
+impl AssetIo for FileAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            fs::read(&full_path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => AssetIoError::NotFound(full_path),
+                _ => AssetIoError::Io(e),
+            })
+        })
+    }
+
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let root_path = self.root_path.to_owned();
+        Ok(Box::new(fs::read_dir(root_path.join(path))?.map(
+            move |entry| {
+                let path = entry.unwrap().path();
+                path.strip_prefix(&root_path).unwrap().to_owned()
+            },
+        )))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.root_path.join(path).is_dir()
+    }
+
+    #[allow(unused_variables)]
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            let path = self.root_path.join(path);
+            if let Some(ref mut watcher) = *self.filesystem_watcher.write() {
+                watcher
+                    .watch(&path)
+                    .map_err(|_error| AssetIoError::PathWatchError(path))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            self.filesystem_watcher
+                .write()
+                .get_or_insert_with(FilesystemWatcher::default);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "filesystem_watcher")]
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = HashSet::default();
+        let mut changed_paths = Vec::new();
+        let filesystem_watcher = self.filesystem_watcher.read();
+        let filesystem_watcher = match filesystem_watcher.as_ref() {
+            Some(filesystem_watcher) => filesystem_watcher,
+            None => return changed_paths,
+        };
+
+        loop {
+            let event = match filesystem_watcher.receiver.try_recv() {
+                Ok(result) => result.unwrap(),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected"),
+            };
+            if let notify::event::Event {
+                kind: notify::event::EventKind::Modify(_),
+                paths,
+                ..
+            } = event
+            {
+                for path in paths {
+                    if let Ok(relative_path) = path.strip_prefix(&self.root_path) {
+                        if changed.insert(relative_path.to_owned()) {
+                            changed_paths.push(relative_path.to_owned());
+                        }
+                    }
+                }
+            }
+        }
+
+        changed_paths
+    }
+
+    #[cfg(not(feature = "filesystem_watcher"))]
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
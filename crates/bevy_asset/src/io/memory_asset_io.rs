@@ -0,0 +1,174 @@
+use crate::{AssetIo, AssetIoError};
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+#[derive(Default)]
+struct MemoryFiles {
+    files: HashMap<PathBuf, Vec<u8>>,
+    /// The watched paths. This is `None` until [AssetIo::watch_for_changes] is called.
+    watched_paths: Option<HashSet<PathBuf>>,
+    changed_paths: Vec<PathBuf>,
+}
+
+/// Reads assets from files that are stored in memory. Clones share the same files, so files can be added or changed
+/// after the [AssetServer](crate::AssetServer) was created. This is mostly useful for tests.
+#[derive(Clone, Default)]
+pub struct MemoryAssetIo {
+    files: Arc<RwLock<MemoryFiles>>,
+}
+
+impl MemoryAssetIo {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds or replaces the file at the given path. Replacing a watched file reports it as changed.
+    pub fn insert<P: Into<PathBuf>>(&self, path: P, bytes: Vec<u8>) {
+        let path = path.into();
+        let mut files = self.files.write();
+        let watched = files
+            .watched_paths
+            .as_ref()
+            .map_or(false, |watched_paths| watched_paths.contains(&path));
+        if watched {
+            files.changed_paths.push(path.clone());
+        }
+        files.files.insert(path, bytes);
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.write().files.remove(path)
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.read().files.contains_key(path)
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        let result = self
+            .files
+            .read()
+            .files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()));
+        Box::pin(async move { result })
+    }
+
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        self.insert(path, bytes);
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        if !self.is_directory(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        let files = self.files.read();
+        // directories only exist implicitly, as ancestors of files
+        let mut children = HashSet::default();
+        for file_path in files.files.keys() {
+            if let Ok(relative_path) = file_path.strip_prefix(path) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(path.join(child));
+                }
+            }
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.files
+            .read()
+            .files
+            .keys()
+            .any(|file_path| file_path != path && file_path.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        if let Some(watched_paths) = self.files.write().watched_paths.as_mut() {
+            watched_paths.insert(path.to_owned());
+        }
+
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.files
+            .write()
+            .watched_paths
+            .get_or_insert_with(HashSet::default);
+        Ok(())
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.files.write().changed_paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn read_directory() {
+        let asset_io = MemoryAssetIo::new();
+        asset_io.insert("textures/a.png", vec![0]);
+        asset_io.insert("textures/characters/b.png", vec![1]);
+        asset_io.insert("models/c.gltf", vec![2]);
+
+        assert!(asset_io.is_directory(Path::new("textures")));
+        assert!(!asset_io.is_directory(Path::new("textures/a.png")));
+        let mut children = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/a.png"),
+                PathBuf::from("textures/characters")
+            ]
+        );
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("models/c.gltf"))).unwrap(),
+            vec![2]
+        );
+        assert!(block_on(asset_io.load_path(Path::new("models/d.gltf"))).is_err());
+    }
+
+    #[test]
+    fn changed_paths() {
+        let asset_io = MemoryAssetIo::new();
+        asset_io.insert("a.png", vec![0]);
+        asset_io.insert("b.png", vec![0]);
+        asset_io.watch_path_for_changes(Path::new("a.png")).unwrap();
+        asset_io.insert("a.png", vec![1]);
+        // paths are only watched once watching is enabled
+        assert!(asset_io.changed_paths().is_empty());
+
+        asset_io.watch_for_changes().unwrap();
+        asset_io.watch_path_for_changes(Path::new("a.png")).unwrap();
+        asset_io.insert("a.png", vec![2]);
+        asset_io.insert("b.png", vec![2]);
+        assert_eq!(asset_io.changed_paths(), vec![PathBuf::from("a.png")]);
+        assert!(asset_io.changed_paths().is_empty());
+    }
+}
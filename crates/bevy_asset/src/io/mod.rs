@@ -0,0 +1,102 @@
+#[cfg(any(feature = "zip", feature = "tar"))]
+mod archive_asset_io;
+mod file_asset_io;
+#[cfg(feature = "filesystem_watcher")]
+mod filesystem_watcher;
+mod memory_asset_io;
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub use archive_asset_io::*;
+pub use file_asset_io::*;
+pub use memory_asset_io::*;
+
+use bevy_utils::BoxedFuture;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Errors that occur while reading assets with an [AssetIo]
+#[derive(Error, Debug)]
+pub enum AssetIoError {
+    #[error("Path not found: {0}")]
+    NotFound(PathBuf),
+    #[error("Encountered an io error while reading an asset.")]
+    Io(#[from] io::Error),
+    #[error("Failed to watch path: {0}")]
+    PathWatchError(PathBuf),
+    #[error("Failed to read asset archive.")]
+    ArchiveError(#[from] anyhow::Error),
+    #[error("Asset source is read only: {0}")]
+    ReadOnly(PathBuf),
+}
+
+/// The source that an [AssetServer](crate::AssetServer) reads asset files from. All paths are relative to the root
+/// of the source.
+pub trait AssetIo: Send + Sync + 'static {
+    /// Reads the contents of the file at the given path
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
+    /// Writes the file at the given path, replacing it if it exists. Sources are read only unless they implement this.
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+    }
+    /// Returns the paths of the files and directories directly inside the given directory
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError>;
+    fn is_directory(&self, path: &Path) -> bool;
+    /// Starts watching the given path for changes, if [AssetIo::watch_for_changes] was called
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
+    /// Enables change detection. Watched paths that changed are returned by [AssetIo::changed_paths].
+    fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+    /// Returns the watched paths that changed since the last call
+    fn changed_paths(&self) -> Vec<PathBuf>;
+}
+
+/// A resource for configuring which [AssetIo] the [AssetPlugin](crate::AssetPlugin) loads assets from. It must be
+/// added before the plugin. If it is missing, assets are loaded from the filesystem.
+pub enum AssetIoConfig {
+    /// Loads assets from the filesystem, relative to [FileAssetIo::get_root_path]
+    FileSystem,
+    /// Loads assets from memory
+    Memory(MemoryAssetIo),
+    /// Loads assets from the zip archive at the given path, relative to [FileAssetIo::get_root_path]
+    #[cfg(feature = "zip")]
+    Zip(PathBuf),
+    /// Loads assets from the tar archive at the given path, relative to [FileAssetIo::get_root_path]
+    #[cfg(feature = "tar")]
+    Tar(PathBuf),
+    /// Loads assets from a custom source
+    Custom(Arc<dyn AssetIo>),
+}
+
+impl Default for AssetIoConfig {
+    fn default() -> Self {
+        AssetIoConfig::FileSystem
+    }
+}
+
+impl AssetIoConfig {
+    pub fn create_asset_io(&self) -> Result<Arc<dyn AssetIo>, AssetIoError> {
+        Ok(match self {
+            AssetIoConfig::FileSystem => Arc::new(FileAssetIo::new(FileAssetIo::get_root_path())),
+            AssetIoConfig::Memory(memory_asset_io) => Arc::new(memory_asset_io.clone()),
+            #[cfg(feature = "zip")]
+            AssetIoConfig::Zip(path) => Arc::new(ArchiveAssetIo::open_zip(
+                FileAssetIo::get_root_path().join(path),
+            )?),
+            #[cfg(feature = "tar")]
+            AssetIoConfig::Tar(path) => Arc::new(ArchiveAssetIo::open_tar(
+                FileAssetIo::get_root_path().join(path),
+            )?),
+            AssetIoConfig::Custom(asset_io) => asset_io.clone(),
+        })
+    }
+}
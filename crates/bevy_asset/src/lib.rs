@@ -1,17 +1,25 @@
 mod asset_server;
 mod assets;
-#[cfg(feature = "filesystem_watcher")]
-mod filesystem_watcher;
 mod handle;
+mod io;
 mod load_request;
 mod loader;
+mod meta;
+mod path;
+mod processor;
+mod saver;
 
 pub use asset_server::*;
 pub use assets::*;
 use bevy_tasks::IoTaskPool;
 pub use handle::*;
+pub use io::*;
 pub use load_request::*;
 pub use loader::*;
+pub use meta::*;
+pub use path::*;
+pub use processor::*;
+pub use saver::*;
 
 /// The names of asset stages in an App Schedule
 pub mod stage {
@@ -20,7 +28,7 @@ pub mod stage {
 }
 
 pub mod prelude {
-    pub use crate::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
+    pub use crate::{AddAsset, AssetEvent, AssetPath, AssetServer, Assets, Handle};
 }
 
 use bevy_app::{prelude::Plugin, AppBuilder};
@@ -29,6 +37,9 @@ use bevy_type_registry::RegisterType;
 
 /// Adds support for Assets to an App. Assets are typed collections with change tracking, which are added as App Resources.
 /// Examples of assets: textures, sounds, 3d models, maps, scenes
+///
+/// Where assets are loaded from can be configured by adding an [AssetIoConfig] resource before the plugin. Adding an
+/// [AssetProcessingConfig] resource enables processing assets with [AssetProcessor]s.
 #[derive(Default)]
 pub struct AssetPlugin;
 
@@ -40,15 +51,24 @@ impl Plugin for AssetPlugin {
             .expect("IoTaskPool resource not found")
             .0
             .clone();
+        let asset_io = match app.resources().get::<AssetIoConfig>() {
+            Some(config) => config.create_asset_io(),
+            None => AssetIoConfig::default().create_asset_io(),
+        }
+        .expect("Failed to create the AssetIo");
+        let importer = app
+            .resources()
+            .get::<AssetProcessingConfig>()
+            .map(|config| {
+                AssetImporter::new(FileAssetIo::get_root_path().join(&config.imported_path))
+            });
         app.add_stage_before(bevy_app::stage::PRE_UPDATE, stage::LOAD_ASSETS)
             .add_stage_after(bevy_app::stage::POST_UPDATE, stage::ASSET_EVENTS)
-            .add_resource(AssetServer::new(task_pool))
-            .register_property::<HandleId>();
-
-        #[cfg(feature = "filesystem_watcher")]
-        app.add_system_to_stage(
-            stage::LOAD_ASSETS,
-            asset_server::filesystem_watcher_system.system(),
-        );
+            .add_resource(AssetServer::new(asset_io, importer, task_pool))
+            .register_property::<HandleId>()
+            .add_system_to_stage(
+                stage::LOAD_ASSETS,
+                asset_server::reload_changed_assets_system.system(),
+            );
     }
 }
@@ -1,23 +1,32 @@
-use crate::{AssetLoadError, AssetLoader, AssetResult, AssetVersion, Handle, HandleId};
+use crate::{
+    asset_server::{set_asset_labels, AssetSources},
+    processor::load_source_bytes,
+    AssetImporter, AssetIo, AssetLoadError, AssetLoader, AssetMeta, AssetPath, AssetResult,
+    AssetSenders, AssetVersion, Handle, HandleId, LoadContext, RefChangeSenders,
+};
 use anyhow::Result;
+use bevy_utils::BoxedFuture;
 use crossbeam_channel::Sender;
-use fs::File;
-use io::Read;
-use std::{fs, io, path::PathBuf};
+use std::{any::type_name, sync::Arc};
 
 /// A request from an [AssetServer](crate::AssetServer) to load an asset.
 #[derive(Debug)]
 pub struct LoadRequest {
-    pub path: PathBuf,
+    /// The asset path of the file to load, which identifies its assets
+    pub asset_path: AssetPath<'static>,
     pub handle_id: HandleId,
     pub handler_index: usize,
     pub version: AssetVersion,
+    /// The file's meta file, which contains the loader's settings
+    pub meta: AssetMeta,
 }
 
-/// Handles load requests from an AssetServer
+/// Handles load requests from an AssetServer. Requests are handled on the [IoTaskPool](bevy_tasks::IoTaskPool).
 pub trait AssetLoadRequestHandler: Send + Sync + 'static {
-    fn handle_request(&self, load_request: &LoadRequest);
+    fn handle_request<'a>(&'a self, load_request: &'a LoadRequest) -> BoxedFuture<'a, ()>;
     fn extensions(&self) -> &[&str];
+    /// The type name of the handler's loader, which is used to select it in meta files
+    fn loader_name(&self) -> &str;
 }
 
 pub(crate) struct ChannelAssetHandler<TLoader, TAsset>
@@ -27,29 +36,51 @@ where
 {
     sender: Sender<AssetResult<TAsset>>,
     loader: TLoader,
+    asset_io: Arc<dyn AssetIo>,
+    importer: Option<Arc<AssetImporter>>,
+    asset_sources: AssetSources,
+    asset_senders: AssetSenders,
+    ref_change_senders: RefChangeSenders,
 }
 
 impl<TLoader, TAsset> ChannelAssetHandler<TLoader, TAsset>
 where
     TLoader: AssetLoader<TAsset>,
 {
-    pub fn new(loader: TLoader, sender: Sender<AssetResult<TAsset>>) -> Self {
-        ChannelAssetHandler { sender, loader }
+    pub fn new(
+        loader: TLoader,
+        sender: Sender<AssetResult<TAsset>>,
+        asset_io: Arc<dyn AssetIo>,
+        importer: Option<Arc<AssetImporter>>,
+        asset_sources: AssetSources,
+        asset_senders: AssetSenders,
+        ref_change_senders: RefChangeSenders,
+    ) -> Self {
+        ChannelAssetHandler {
+            sender,
+            loader,
+            asset_io,
+            importer,
+            asset_sources,
+            asset_senders,
+            ref_change_senders,
+        }
     }
 
-    fn load_asset(&self, load_request: &LoadRequest) -> Result<TAsset, AssetLoadError> {
-        match File::open(&load_request.path) {
-            Ok(mut file) => {
-                let mut bytes = Vec::new();
-                file.read_to_end(&mut bytes)?;
-                let asset = self.loader.from_bytes(&load_request.path, bytes)?;
-                Ok(asset)
-            }
-            Err(e) => Err(AssetLoadError::Io(std::io::Error::new(
-                e.kind(),
-                format!("{}", load_request.path.display()),
-            ))),
-        }
+    async fn load_asset(
+        &self,
+        load_request: &LoadRequest,
+        load_context: &mut LoadContext,
+    ) -> Result<TAsset, AssetLoadError> {
+        let bytes = load_source_bytes(
+            &*self.asset_io,
+            self.importer.as_deref(),
+            load_request.asset_path.path(),
+            &load_request.meta,
+        )
+        .await?;
+        let asset = self.loader.load(bytes, load_context).await?;
+        Ok(asset)
     }
 }
 
@@ -58,20 +89,39 @@ where
     TLoader: AssetLoader<TAsset> + 'static,
     TAsset: Send + 'static,
 {
-    fn handle_request(&self, load_request: &LoadRequest) {
-        let result = self.load_asset(load_request);
-        let asset_result = AssetResult {
-            handle: Handle::from(load_request.handle_id),
-            result,
-            path: load_request.path.clone(),
-            version: load_request.version,
-        };
-        self.sender
-            .send(asset_result)
-            .expect("loaded asset should have been sent");
+    fn handle_request<'a>(&'a self, load_request: &'a LoadRequest) -> BoxedFuture<'a, ()> {
+        Box::pin(async move {
+            let mut load_context =
+                LoadContext::with_asset_io(load_request.asset_path.path(), self.asset_io.clone())
+                    .with_meta(load_request.meta.clone())
+                    .with_ref_change_senders(self.ref_change_senders.clone());
+            let result = self.load_asset(load_request, &mut load_context).await;
+            if result.is_ok() {
+                set_asset_labels(
+                    &self.asset_sources,
+                    load_request.asset_path.path(),
+                    &load_context,
+                    load_request.version,
+                );
+                load_context.send(&self.asset_senders, load_request.version);
+            }
+            let asset_result = AssetResult {
+                handle: Handle::from(load_request.handle_id),
+                result,
+                path: load_request.asset_path.clone(),
+                version: load_request.version,
+            };
+            self.sender
+                .send(asset_result)
+                .expect("loaded asset should have been sent");
+        })
     }
 
     fn extensions(&self) -> &[&str] {
         self.loader.extensions()
     }
+
+    fn loader_name(&self) -> &str {
+        type_name::<TLoader>()
+    }
 }
@@ -1,12 +1,18 @@
-use crate::{AssetServer, AssetVersion, Assets, Handle, LoadState};
+use crate::{
+    AssetIo, AssetIoError, AssetMeta, AssetMetaError, AssetPath, AssetServer, AssetVersion, Assets,
+    FileAssetIo, Handle, HandleId, LoadState, RefChange,
+};
 use anyhow::Result;
 use bevy_ecs::{Res, ResMut, Resource};
+use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender, TryRecvError};
-use fs::File;
-use io::Read;
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
 use std::{
-    fs, io,
+    any::{Any, TypeId},
+    io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use thiserror::Error;
 
@@ -15,20 +21,35 @@ use thiserror::Error;
 pub enum AssetLoadError {
     #[error("Encountered an io error while loading asset.")]
     Io(#[from] io::Error),
+    #[error("Encountered an error while reading asset.")]
+    AssetIoError(#[from] AssetIoError),
     #[error("This asset's loader encountered an error while loading.")]
     LoaderError(#[from] anyhow::Error),
+    #[error("This asset's processor encountered an error while processing.")]
+    ProcessorError(#[source] anyhow::Error),
+    #[error("Encountered an error while reading this asset's meta file.")]
+    AssetMetaError(#[from] AssetMetaError),
 }
 
-/// A loader for a given asset of type `T`
+/// A loader for a given asset of type `T`. Loaders implement [AssetLoader::from_bytes], [AssetLoader::load], or both.
 pub trait AssetLoader<T>: Send + Sync + 'static {
-    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<T, anyhow::Error>;
+    /// Loads the asset from the bytes of its file alone. Loaders that need a [LoadContext] only implement
+    /// [AssetLoader::load], in which case this returns an error.
+    fn from_bytes(&self, asset_path: &Path, _bytes: Vec<u8>) -> Result<T, anyhow::Error> {
+        Err(anyhow::anyhow!(
+            "{} can only be loaded with a LoadContext",
+            asset_path.display()
+        ))
+    }
     fn extensions(&self) -> &[&str];
-    fn load_from_file(&self, asset_path: &Path) -> Result<T, AssetLoadError> {
-        let mut file = File::open(asset_path)?;
-        let mut bytes = Vec::new();
-        file.read_to_end(&mut bytes)?;
-        let asset = self.from_bytes(asset_path, bytes)?;
-        Ok(asset)
+    /// Loads the asset from the bytes of its file. This runs on the [IoTaskPool](bevy_tasks::IoTaskPool). Loaders that
+    /// read other files, refer to other assets or add labeled assets should override this and use `load_context`.
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<T, anyhow::Error>> {
+        Box::pin(async move { self.from_bytes(load_context.path(), bytes) })
     }
 }
 
@@ -36,10 +57,201 @@ pub trait AssetLoader<T>: Send + Sync + 'static {
 pub struct AssetResult<T: 'static> {
     pub result: Result<T, AssetLoadError>,
     pub handle: Handle<T>,
-    pub path: PathBuf,
+    pub path: AssetPath<'static>,
     pub version: AssetVersion,
 }
 
+/// The [AssetChannel] senders of every asset type, keyed by the asset's [TypeId]
+pub(crate) type AssetSenders = Arc<RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>;
+
+/// The senders that strong handles report their ref count changes to, keyed by the asset's [TypeId]
+pub(crate) type RefChangeSenders = Arc<RwLock<HashMap<TypeId, Sender<RefChange>>>>;
+
+struct LabeledAsset {
+    label: String,
+    type_id: TypeId,
+    value: Box<dyn Any + Send + Sync>,
+    send: fn(&AssetSenders, AssetPath<'static>, Box<dyn Any + Send + Sync>, AssetVersion),
+}
+
+/// The context an [AssetLoader] loads a file in. It gives access to other files through the [AssetIo] the file is
+/// read from, and collects the file's labeled assets and dependencies.
+///
+/// Labeled assets are loaded from the same file as a "main" asset. Each labeled asset can be retrieved using a path of
+/// the form `path/to/file.ext#Label`.
+///
+/// The handles the context returns are strong, so an asset that stores them keeps its labeled assets and dependencies
+/// alive.
+pub struct LoadContext {
+    path: PathBuf,
+    asset_io: Arc<dyn AssetIo>,
+    meta: AssetMeta,
+    assets: Vec<LabeledAsset>,
+    dependencies: Vec<AssetPath<'static>>,
+    ref_change_senders: RefChangeSenders,
+}
+
+impl LoadContext {
+    /// Creates a context for loading the file at the given path, which reads additional files from the filesystem
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self::with_asset_io(
+            path,
+            Arc::new(FileAssetIo::new(FileAssetIo::get_root_path())),
+        )
+    }
+
+    /// Creates a context for loading the file at the given path, which reads additional files from `asset_io`
+    pub fn with_asset_io<P: Into<PathBuf>>(path: P, asset_io: Arc<dyn AssetIo>) -> Self {
+        LoadContext {
+            path: path.into(),
+            asset_io,
+            meta: AssetMeta::default(),
+            assets: Vec::new(),
+            dependencies: Vec::new(),
+            ref_change_senders: Default::default(),
+        }
+    }
+
+    /// Sets the senders used to create strong handles. Without them, the context returns weak handles.
+    pub(crate) fn with_ref_change_senders(mut self, ref_change_senders: RefChangeSenders) -> Self {
+        self.ref_change_senders = ref_change_senders;
+        self
+    }
+
+    /// Gets a strong handle to the asset with the given id, or a weak handle if `T` isn't a registered asset type
+    fn get_handle_with_id<T: 'static>(&self, id: HandleId) -> Handle<T> {
+        match self.ref_change_senders.read().get(&TypeId::of::<T>()) {
+            Some(sender) => Handle::strong(id, sender.clone()),
+            None => Handle::from_id(id),
+        }
+    }
+
+    /// Sets the contents of the loaded file's meta file
+    pub fn with_meta(mut self, meta: AssetMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    /// The asset path of the file that is loaded
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The loaded file's meta file. This is empty if the file has no meta file.
+    pub fn meta(&self) -> &AssetMeta {
+        &self.meta
+    }
+
+    /// Deserializes the loader settings in the loaded file's meta file, or returns the default settings if there are
+    /// none
+    pub fn settings<S: DeserializeOwned + Default>(&self) -> Result<S, AssetMetaError> {
+        self.meta.settings()
+    }
+
+    /// Reads the file at the given asset path from the source the file is loaded from. This can be used to load files
+    /// that the loaded file refers to, such as binary buffers.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.asset_io.load_path(path.as_ref()).await
+    }
+
+    /// Adds an asset with the given label and returns its handle
+    pub fn set<T: Send + Sync + 'static>(&mut self, label: &str, asset: T) -> Handle<T> {
+        self.assets.push(LabeledAsset {
+            label: label.to_string(),
+            type_id: TypeId::of::<T>(),
+            value: Box::new(asset),
+            send: send_labeled_asset::<T>,
+        });
+        self.get_handle(label)
+    }
+
+    /// Gets the handle of the labeled asset with the given label. The asset doesn't need to be added yet.
+    pub fn get_handle<T: 'static>(&self, label: &str) -> Handle<T> {
+        self.get_handle_with_id(HandleId::from(
+            AssetPath::new_ref(&self.path, Some(label)).get_id(),
+        ))
+    }
+
+    /// Gets the asset with the given label, if it was added with type `T`
+    pub fn get<T: 'static>(&self, label: &str) -> Option<&T> {
+        self.assets
+            .iter()
+            .find(|asset| asset.label == label)
+            .and_then(|asset| asset.value.downcast_ref::<T>())
+    }
+
+    /// Declares that the file's assets depend on the asset at the given path and returns its handle. This is how
+    /// handles to assets in other files are obtained, such as the textures a material refers to. The dependency is
+    /// loaded once the file is loaded, and the file's assets are only considered loaded once it is loaded too.
+    pub fn add_dependency<'a, T: 'static, P: Into<AssetPath<'a>>>(&mut self, path: P) -> Handle<T> {
+        let path = path.into().to_owned();
+        let handle = self.get_handle_with_id(HandleId::from(path.get_id()));
+        if !self.dependencies.contains(&path) {
+            self.dependencies.push(path);
+        }
+        handle
+    }
+
+    /// The paths of the assets the file's assets depend on
+    pub fn dependencies(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.dependencies.iter()
+    }
+
+    /// The labels and types of the added labeled assets
+    pub fn iter(&self) -> impl Iterator<Item = (&str, TypeId)> {
+        self.assets
+            .iter()
+            .map(|asset| (asset.label.as_str(), asset.type_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Sends the labeled assets to their [AssetChannel]s
+    pub(crate) fn send(self, asset_senders: &AssetSenders, version: AssetVersion) {
+        let path = self.path;
+        for asset in self.assets {
+            let asset_path = AssetPath::new(path.clone(), Some(asset.label));
+            (asset.send)(asset_senders, asset_path, asset.value, version);
+        }
+    }
+}
+
+fn send_labeled_asset<T: Send + Sync + 'static>(
+    asset_senders: &AssetSenders,
+    asset_path: AssetPath<'static>,
+    asset: Box<dyn Any + Send + Sync>,
+    version: AssetVersion,
+) {
+    let asset_senders = asset_senders.read();
+    let sender = asset_senders
+        .get(&TypeId::of::<T>())
+        .and_then(|sender| sender.downcast_ref::<Sender<AssetResult<T>>>())
+        .unwrap_or_else(|| {
+            panic!(
+                "Labeled asset {} has type {}, which is not a registered asset type. Consider adding it with AddAsset::add_asset.",
+                asset_path,
+                std::any::type_name::<T>()
+            )
+        });
+    let asset = *asset
+        .downcast::<T>()
+        .expect("labeled asset should have the type it was added with");
+    sender
+        .send(AssetResult {
+            result: Ok(asset),
+            handle: Handle::from(HandleId::from(asset_path.get_id())),
+            path: asset_path,
+            version,
+        })
+        .expect("loaded asset should have been sent");
+}
+
 /// A channel to send and receive [AssetResult]s
 pub struct AssetChannel<T: 'static> {
     pub sender: Sender<AssetResult<T>>,
@@ -54,7 +266,8 @@ impl<T> AssetChannel<T> {
     }
 }
 
-/// Reads [AssetResult]s from an [AssetChannel] and updates the [Assets] collection and [LoadState] accordingly
+/// Reads [AssetResult]s from an [AssetChannel] and updates the [Assets] collection and [LoadState] accordingly. Also
+/// sends [AssetEvent::Modified](crate::AssetEvent::Modified) events for assets whose dependencies were reloaded.
 pub fn update_asset_storage_system<T: Resource>(
     asset_channel: Res<AssetChannel<T>>,
     asset_server: Res<AssetServer>,
@@ -65,13 +278,12 @@ pub fn update_asset_storage_system<T: Resource>(
             Ok(result) => match result.result {
                 Ok(asset) => {
                     assets.set(result.handle, asset);
-                    asset_server
-                        .set_load_state(result.handle.id, LoadState::Loaded(result.version));
+                    asset_server.commit_asset(&result.path, TypeId::of::<T>(), result.version);
                 }
                 Err(err) => {
                     asset_server
                         .set_load_state(result.handle.id, LoadState::Failed(result.version));
-                    log::error!("Failed to load asset: {:?}", err);
+                    log::error!("Failed to load asset {}: {:?}", result.path, err);
                 }
             },
             Err(TryRecvError::Empty) => {
@@ -80,4 +292,39 @@ pub fn update_asset_storage_system<T: Resource>(
             Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected"),
         }
     }
+
+    for handle_id in asset_server.take_modified_dependents::<T>() {
+        assets.mark_modified(handle_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoadContext;
+    use crate::Assets;
+    use std::any::TypeId;
+
+    #[test]
+    fn labeled_asset_handles_keep_assets_alive() {
+        let mut assets = Assets::<u32>::default();
+        let mut load_context = LoadContext::new("file.txt");
+        load_context
+            .ref_change_senders
+            .write()
+            .insert(TypeId::of::<u32>(), assets.ref_change_sender());
+        // the loaded asset stores this handle
+        let labeled_handle = load_context.set("Label", 1u32);
+        assert!(labeled_handle.is_strong());
+        assets.set(&labeled_handle, 1);
+
+        let handle = assets.get_handle(&labeled_handle);
+        drop(handle);
+        assets.free_unused_assets();
+        assert_eq!(assets.get(&labeled_handle), Some(&1));
+
+        let weak_handle = labeled_handle.clone_weak();
+        drop(labeled_handle);
+        assets.free_unused_assets();
+        assert!(assets.get(&weak_handle).is_none());
+    }
 }
@@ -0,0 +1,192 @@
+use crate::{AssetIo, AssetIoError};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Errors that occur while reading an asset's `.meta` file
+#[derive(Error, Debug)]
+pub enum AssetMetaError {
+    #[error("Encountered an error while reading the meta file.")]
+    AssetIoError(#[from] AssetIoError),
+    #[error("The meta file is not valid UTF-8.")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+    #[error("Failed to parse the meta file.")]
+    Ron(#[from] ron::Error),
+}
+
+/// The settings in an asset's optional `.meta` sidecar file, which is stored next to the asset as
+/// `path/to/file.ext.meta`. Meta files are written in RON:
+///
+/// ```ron
+/// (
+///     loader: "ImageTextureLoader",
+///     settings: (
+///         srgb: false,
+///     ),
+/// )
+/// ```
+///
+/// Both fields are optional. `loader` selects the loader by its type name instead of by the asset's extension, and
+/// `settings` is deserialized into the settings type of the loader with [AssetMeta::settings].
+#[derive(Debug, Clone, Default)]
+pub struct AssetMeta {
+    loader: Option<String>,
+    source: String,
+}
+
+#[derive(Deserialize)]
+struct MetaLoader {
+    #[serde(default)]
+    loader: String,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "S: DeserializeOwned + Default"))]
+struct MetaSettings<S> {
+    #[serde(default)]
+    settings: S,
+}
+
+impl FromStr for AssetMeta {
+    type Err = AssetMetaError;
+
+    /// Parses the contents of a meta file
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let meta_loader = ron::de::from_str::<MetaLoader>(source)?;
+        Ok(AssetMeta {
+            loader: Some(meta_loader.loader).filter(|loader| !loader.is_empty()),
+            source: source.to_string(),
+        })
+    }
+}
+
+impl AssetMeta {
+    /// The name of the loader that was selected for the asset, if any
+    pub fn loader(&self) -> Option<&str> {
+        self.loader.as_deref()
+    }
+
+    /// The contents of the meta file. This is empty if the asset has no meta file.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Deserializes the meta file's settings. Returns the default settings if the asset has no meta file or the meta
+    /// file has no settings.
+    pub fn settings<S: DeserializeOwned + Default>(&self) -> Result<S, AssetMetaError> {
+        if self.source.is_empty() {
+            return Ok(S::default());
+        }
+
+        let meta_settings = ron::de::from_str::<MetaSettings<S>>(&self.source)?;
+        Ok(meta_settings.settings)
+    }
+}
+
+/// The path of the meta file of the asset file at the given path
+pub fn get_meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".meta");
+    PathBuf::from(meta_path)
+}
+
+/// If the given path is a meta file, returns the path of its asset file
+pub(crate) fn get_meta_asset_path(path: &Path) -> Option<PathBuf> {
+    if path.extension()? == "meta" {
+        Some(path.with_extension(""))
+    } else {
+        None
+    }
+}
+
+/// Reads the meta file of the asset file at the given path. Returns `None` if the asset has no meta file.
+pub(crate) async fn load_asset_meta(
+    asset_io: &dyn AssetIo,
+    path: &Path,
+) -> Result<Option<AssetMeta>, AssetMetaError> {
+    match asset_io.load_path(&get_meta_path(path)).await {
+        Ok(bytes) => Ok(Some(String::from_utf8(bytes)?.parse()?)),
+        Err(AssetIoError::NotFound(_)) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Gets the name a loader can be selected by in a meta file, which is its type name without the module path
+pub(crate) fn get_loader_name(type_name: &str) -> &str {
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(default)]
+    struct TestSettings {
+        srgb: bool,
+        filter: TestFilter,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum TestFilter {
+        Nearest,
+        Linear,
+    }
+
+    impl Default for TestSettings {
+        fn default() -> Self {
+            TestSettings {
+                srgb: true,
+                filter: TestFilter::Nearest,
+            }
+        }
+    }
+
+    #[test]
+    fn meta_settings() {
+        let meta = r#"(
+                loader: "TestLoader",
+                settings: (
+                    filter: Linear,
+                ),
+            )"#
+        .parse::<AssetMeta>()
+        .unwrap();
+        assert_eq!(meta.loader(), Some("TestLoader"));
+        assert_eq!(
+            meta.settings::<TestSettings>().unwrap(),
+            TestSettings {
+                srgb: true,
+                filter: TestFilter::Linear,
+            }
+        );
+
+        let meta = "()".parse::<AssetMeta>().unwrap();
+        assert_eq!(meta.loader(), None);
+        assert_eq!(
+            meta.settings::<TestSettings>().unwrap(),
+            TestSettings::default()
+        );
+        assert_eq!(
+            AssetMeta::default().settings::<TestSettings>().unwrap(),
+            TestSettings::default()
+        );
+
+        assert_eq!(
+            get_meta_path(Path::new("textures/a.png")),
+            PathBuf::from("textures/a.png.meta")
+        );
+        assert_eq!(
+            get_meta_asset_path(Path::new("textures/a.png.meta")),
+            Some(PathBuf::from("textures/a.png"))
+        );
+        assert_eq!(
+            get_loader_name("bevy_render::texture::ImageTextureLoader"),
+            "ImageTextureLoader"
+        );
+    }
+}
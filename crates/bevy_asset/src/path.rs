@@ -0,0 +1,309 @@
+use crate::HandleId;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::Hasher,
+    path::{Component, Path, PathBuf},
+};
+use twox_hash::XxHash64;
+use uuid::Uuid;
+
+/// The path to an asset. Files that contain multiple assets (such as GLTF files) use labels to address each
+/// asset individually: `path/to/file.gltf#Mesh0/Primitive1`. Paths without a label refer to the "main" asset of a file.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetPath<'a> {
+    path: Cow<'a, Path>,
+    label: Option<Cow<'a, str>>,
+}
+
+impl<'a> AssetPath<'a> {
+    #[inline]
+    pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
+        AssetPath {
+            path: Cow::Borrowed(path),
+            label: label.map(Cow::Borrowed),
+        }
+    }
+
+    #[inline]
+    pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
+        AssetPath {
+            path: Cow::Owned(path),
+            label: label.map(Cow::Owned),
+        }
+    }
+
+    /// The path of the file that contains the asset
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_ref().map(|label| label.as_ref())
+    }
+
+    /// Returns the path of the given labeled asset in the same file
+    pub fn with_label(&self, label: &str) -> AssetPath<'static> {
+        AssetPath::new(self.path.to_path_buf(), Some(label.to_string()))
+    }
+
+    /// Returns the path of the file's main asset
+    pub fn without_label(&self) -> AssetPath<'static> {
+        AssetPath::new(self.path.to_path_buf(), None)
+    }
+
+    #[inline]
+    pub fn get_id(&self) -> AssetPathId {
+        AssetPathId::from(self)
+    }
+
+    pub fn to_owned(&self) -> AssetPath<'static> {
+        AssetPath {
+            path: Cow::Owned(self.path.to_path_buf()),
+            label: self
+                .label
+                .as_ref()
+                .map(|label| Cow::Owned(label.to_string())),
+        }
+    }
+}
+
+impl<'a> fmt::Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = &self.label {
+            write!(f, "#{}", label)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a str> for AssetPath<'a> {
+    fn from(asset_path: &'a str) -> Self {
+        let mut parts = asset_path.splitn(2, '#');
+        let path = Path::new(parts.next().expect("path must be set"));
+        let label = parts.next();
+        AssetPath {
+            path: Cow::Borrowed(path),
+            label: label.map(Cow::Borrowed),
+        }
+    }
+}
+
+impl<'a> From<&'a String> for AssetPath<'a> {
+    fn from(asset_path: &'a String) -> Self {
+        asset_path.as_str().into()
+    }
+}
+
+impl<'a> From<String> for AssetPath<'a> {
+    fn from(asset_path: String) -> Self {
+        AssetPath::from(asset_path.as_str()).to_owned()
+    }
+}
+
+impl<'a> From<&'a Path> for AssetPath<'a> {
+    fn from(path: &'a Path) -> Self {
+        AssetPath {
+            path: Cow::Borrowed(path),
+            label: None,
+        }
+    }
+}
+
+impl<'a> From<&'a PathBuf> for AssetPath<'a> {
+    fn from(path: &'a PathBuf) -> Self {
+        path.as_path().into()
+    }
+}
+
+impl<'a> From<PathBuf> for AssetPath<'a> {
+    fn from(path: PathBuf) -> Self {
+        AssetPath {
+            path: Cow::Owned(path),
+            label: None,
+        }
+    }
+}
+
+impl<'a, 'b> From<&'b AssetPath<'a>> for AssetPath<'a> {
+    fn from(asset_path: &'b AssetPath<'a>) -> Self {
+        asset_path.clone()
+    }
+}
+
+/// A stable id for the file an asset is loaded from
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourcePathId(u64);
+
+/// A stable id for an asset label
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LabelId(u64);
+
+/// A stable id for an [AssetPath]. The [HandleId] of an asset that was loaded from a path is derived from this id,
+/// which makes it possible to get the handle of an asset before it has been loaded.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AssetPathId(SourcePathId, LabelId);
+
+impl AssetPathId {
+    pub fn new(source_path_id: SourcePathId, label_id: LabelId) -> Self {
+        AssetPathId(source_path_id, label_id)
+    }
+
+    pub fn source_path_id(&self) -> SourcePathId {
+        self.0
+    }
+
+    pub fn label_id(&self) -> LabelId {
+        self.1
+    }
+}
+
+/// Asset path ids are persisted (for example in scenes and processed asset metadata), so they are hashed with a
+/// fixed algorithm and seed instead of std's unspecified `DefaultHasher`
+fn get_hasher() -> XxHash64 {
+    XxHash64::with_seed(0)
+}
+
+/// Lexically resolves `.` and `..` components, so paths that point to the same file produce the same id.
+/// `..` components that would leave the root of the path are kept.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                _ => normalized.push(component),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+impl<'a> From<&'a Path> for SourcePathId {
+    fn from(path: &'a Path) -> Self {
+        let mut hasher = get_hasher();
+        // components are joined with '/' so the id doesn't depend on the platform's separator
+        for (i, component) in normalize_path(path).components().enumerate() {
+            if i > 0 {
+                hasher.write_u8(b'/');
+            }
+            hasher.write(component.as_os_str().to_string_lossy().as_bytes());
+        }
+        SourcePathId(hasher.finish())
+    }
+}
+
+impl<'a> From<Option<&'a str>> for LabelId {
+    fn from(label: Option<&'a str>) -> Self {
+        let mut hasher = get_hasher();
+        // a leading byte keeps the main asset's id distinct from an empty label
+        match label {
+            Some(label) => {
+                hasher.write_u8(1);
+                hasher.write(label.as_bytes());
+            }
+            None => hasher.write_u8(0),
+        }
+        LabelId(hasher.finish())
+    }
+}
+
+impl<'a, 'b> From<&'b AssetPath<'a>> for AssetPathId {
+    fn from(asset_path: &'b AssetPath<'a>) -> Self {
+        AssetPathId(
+            SourcePathId::from(asset_path.path()),
+            LabelId::from(asset_path.label()),
+        )
+    }
+}
+
+impl<'a> From<AssetPath<'a>> for AssetPathId {
+    fn from(asset_path: AssetPath<'a>) -> Self {
+        AssetPathId::from(&asset_path)
+    }
+}
+
+impl From<AssetPathId> for HandleId {
+    fn from(asset_path_id: AssetPathId) -> Self {
+        let AssetPathId(SourcePathId(source), LabelId(label)) = asset_path_id;
+        HandleId(Uuid::from_u128(((source as u128) << 64) | label as u128))
+    }
+}
+
+impl From<HandleId> for AssetPathId {
+    /// Recovers the [AssetPathId] a [HandleId] was derived from. This only produces a meaningful value for handles
+    /// of assets that were loaded from a path.
+    fn from(handle_id: HandleId) -> Self {
+        let value = handle_id.0.as_u128();
+        AssetPathId(SourcePathId((value >> 64) as u64), LabelId(value as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_labeled_path() {
+        let asset_path = AssetPath::from("models/scene.gltf#Mesh0/Primitive1");
+        assert_eq!(asset_path.path(), Path::new("models/scene.gltf"));
+        assert_eq!(asset_path.label(), Some("Mesh0/Primitive1"));
+        assert_eq!(asset_path.to_string(), "models/scene.gltf#Mesh0/Primitive1");
+
+        let asset_path = AssetPath::from("models/scene.gltf");
+        assert_eq!(asset_path.label(), None);
+    }
+
+    #[test]
+    fn handle_id_round_trip() {
+        let asset_path = AssetPath::from("models/scene.gltf#Mesh0/Primitive1");
+        let asset_path_id = asset_path.get_id();
+        let handle_id = HandleId::from(asset_path_id);
+        assert_eq!(AssetPathId::from(handle_id), asset_path_id);
+        assert_eq!(
+            asset_path_id.source_path_id(),
+            asset_path.without_label().get_id().source_path_id()
+        );
+        assert_ne!(
+            handle_id,
+            HandleId::from(asset_path.without_label().get_id())
+        );
+    }
+
+    #[test]
+    fn normalized_path_ids() {
+        assert_eq!(
+            AssetPath::from("textures/../models/./scene.gltf#Mesh0").get_id(),
+            AssetPath::from("models/scene.gltf#Mesh0").get_id()
+        );
+        assert_eq!(
+            normalize_path(Path::new("../a/../../b.png")),
+            Path::new("../../b.png")
+        );
+        assert_ne!(
+            AssetPath::from("a.png").get_id(),
+            AssetPath::from("../a.png").get_id()
+        );
+    }
+
+    #[test]
+    fn stable_path_ids() {
+        // ids are persisted, so changing how they are computed is a breaking change
+        let asset_path_id = AssetPath::from("models/scene.gltf#Mesh0").get_id();
+        assert_eq!(
+            asset_path_id,
+            AssetPathId::new(
+                SourcePathId(6936747643345641004),
+                LabelId(8530822486949353606)
+            )
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{AssetIo, AssetLoadError, AssetMeta};
+use bevy_utils::HashMap;
+use parking_lot::RwLock;
+use std::{
+    any::type_name,
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use twox_hash::XxHash64;
+
+/// Transforms source files into a form that is faster to load, such as decoded images or compiled shaders. Processed
+/// files are cached by the [AssetImporter], so a file is only processed again once it or its processor changes.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the bytes of a source file. `meta` is the file's meta file, which can contain the processor's
+    /// settings.
+    fn process(
+        &self,
+        asset_path: &Path,
+        bytes: Vec<u8>,
+        meta: &AssetMeta,
+    ) -> Result<Vec<u8>, anyhow::Error>;
+    /// The extensions of the source files this processor processes
+    fn extensions(&self) -> &[&str];
+    /// The extension of the processed files, which selects the [AssetLoader](crate::AssetLoader) that loads them
+    fn processed_extension(&self) -> &str;
+    /// The version of the processor's output. This should be increased whenever the output or the processor's
+    /// settings change, which invalidates the files it processed before.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// A resource that enables asset processing. It must be added before the [AssetPlugin](crate::AssetPlugin).
+pub struct AssetProcessingConfig {
+    /// The directory processed files are cached in, relative to [FileAssetIo::get_root_path](crate::FileAssetIo)
+    pub imported_path: PathBuf,
+}
+
+impl Default for AssetProcessingConfig {
+    fn default() -> Self {
+        AssetProcessingConfig {
+            imported_path: PathBuf::from("imported"),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ProcessorInfo {
+    processor: Arc<dyn AssetProcessor>,
+    type_name: &'static str,
+}
+
+/// Processes source files with the registered [AssetProcessor]s and caches the processed files in the "imported"
+/// directory. Cached files are keyed by a hash of the source file's contents, its meta file, the processor and its
+/// version, so they are loaded instead of processing the file again for as long as they are up to date.
+pub struct AssetImporter {
+    imported_path: PathBuf,
+    processors: RwLock<Vec<ProcessorInfo>>,
+    extension_to_processor_index: RwLock<HashMap<String, usize>>,
+}
+
+impl AssetImporter {
+    pub fn new<P: Into<PathBuf>>(imported_path: P) -> Self {
+        AssetImporter {
+            imported_path: imported_path.into(),
+            processors: Default::default(),
+            extension_to_processor_index: Default::default(),
+        }
+    }
+
+    /// The directory processed files are cached in
+    pub fn imported_path(&self) -> &Path {
+        &self.imported_path
+    }
+
+    pub fn add_processor<P: AssetProcessor>(&self, processor: P) {
+        let mut processors = self.processors.write();
+        let processor_index = processors.len();
+        let mut extension_to_processor_index = self.extension_to_processor_index.write();
+        for extension in processor.extensions().iter() {
+            extension_to_processor_index.insert(extension.to_string(), processor_index);
+        }
+
+        processors.push(ProcessorInfo {
+            processor: Arc::new(processor),
+            type_name: type_name::<P>(),
+        });
+    }
+
+    fn get_processor(&self, path: &Path) -> Option<ProcessorInfo> {
+        let extension = path.extension()?.to_str()?;
+        let index = *self.extension_to_processor_index.read().get(extension)?;
+        Some(self.processors.read()[index].clone())
+    }
+
+    /// Gets the extension of the processed file if the file at the given path is processed
+    pub fn get_processed_extension(&self, path: &Path) -> Option<String> {
+        self.get_processor(path)
+            .map(|info| info.processor.processed_extension().to_string())
+    }
+
+    /// Reads the file at the given path from `asset_io` and processes it, if a processor was added for its extension.
+    /// An up to date processed file is read from the cache instead.
+    pub async fn load_path(
+        &self,
+        asset_io: &dyn AssetIo,
+        path: &Path,
+        meta: &AssetMeta,
+    ) -> Result<Vec<u8>, AssetLoadError> {
+        let bytes = asset_io.load_path(path).await?;
+        let info = match self.get_processor(path) {
+            Some(info) => info,
+            None => return Ok(bytes),
+        };
+
+        let imported_path = self.get_imported_path(path, &bytes, meta, &info);
+        if let Ok(processed_bytes) = fs::read(&imported_path) {
+            return Ok(processed_bytes);
+        }
+
+        let processed_bytes = info
+            .processor
+            .process(path, bytes, meta)
+            .map_err(AssetLoadError::ProcessorError)?;
+        // the cache only speeds up loading, so failing to write to it doesn't fail the load
+        if let Err(err) = write_imported_file(&imported_path, &processed_bytes) {
+            log::warn!(
+                "Failed to cache processed asset {}: {:?}",
+                imported_path.display(),
+                err
+            );
+        }
+        remove_stale_imported_files(&imported_path, path, &info);
+        Ok(processed_bytes)
+    }
+
+    /// The path of the processed file for the given source file, like `imported/textures/a.png.{hash}.texture`
+    fn get_imported_path(
+        &self,
+        path: &Path,
+        bytes: &[u8],
+        meta: &AssetMeta,
+        info: &ProcessorInfo,
+    ) -> PathBuf {
+        // the hash names files that outlive the process, so it must not change between runs or compiler versions
+        let mut hasher = XxHash64::with_seed(0);
+        for part in [bytes, meta.source().as_bytes(), info.type_name.as_bytes()].iter() {
+            hasher.write_u64(part.len() as u64);
+            hasher.write(part);
+        }
+        hasher.write_u32(info.processor.version());
+        let file_name = format!(
+            "{}.{:016x}.{}",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            hasher.finish(),
+            info.processor.processed_extension()
+        );
+        match path.parent() {
+            Some(parent) => self.imported_path.join(parent).join(file_name),
+            None => self.imported_path.join(file_name),
+        }
+    }
+}
+
+/// Removes the files that were processed from older versions of the source file at `path`, which are never loaded
+/// again. Only the file at `imported_path` is kept.
+fn remove_stale_imported_files(imported_path: &Path, path: &Path, info: &ProcessorInfo) {
+    let (directory, current_file_name) = match (imported_path.parent(), imported_path.file_name()) {
+        (Some(directory), Some(file_name)) => (directory, file_name),
+        _ => return,
+    };
+    let prefix = format!(
+        "{}.",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    let suffix = format!(".{}", info.processor.processed_extension());
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let file_name = entry.file_name();
+        if file_name == current_file_name {
+            continue;
+        }
+        let file_name = file_name.to_string_lossy();
+        // the part between the prefix and suffix is the hash, which keeps `a.png` from matching `a.png.b.png`
+        let is_stale = file_name.starts_with(&prefix)
+            && file_name.ends_with(&suffix)
+            && file_name.len() == prefix.len() + 16 + suffix.len();
+        if is_stale {
+            if let Err(err) = fs::remove_file(entry.path()) {
+                log::warn!(
+                    "Failed to remove stale processed asset {}: {:?}",
+                    entry.path().display(),
+                    err
+                );
+            }
+        }
+    }
+}
+
+fn write_imported_file(path: &Path, bytes: &[u8]) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)
+}
+
+/// Reads the file at the given path, processing it with `importer` if there is one
+pub(crate) async fn load_source_bytes(
+    asset_io: &dyn AssetIo,
+    importer: Option<&AssetImporter>,
+    path: &Path,
+    meta: &AssetMeta,
+) -> Result<Vec<u8>, AssetLoadError> {
+    match importer {
+        Some(importer) => importer.load_path(asset_io, path, meta).await,
+        None => Ok(asset_io.load_path(path).await?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryAssetIo;
+    use futures_lite::future::block_on;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct ReverseProcessor {
+        processed: Arc<AtomicUsize>,
+    }
+
+    impl AssetProcessor for ReverseProcessor {
+        fn process(
+            &self,
+            _asset_path: &Path,
+            mut bytes: Vec<u8>,
+            _meta: &AssetMeta,
+        ) -> Result<Vec<u8>, anyhow::Error> {
+            self.processed.fetch_add(1, Ordering::Relaxed);
+            bytes.reverse();
+            Ok(bytes)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["src"]
+        }
+
+        fn processed_extension(&self) -> &str {
+            "rev"
+        }
+    }
+
+    #[test]
+    fn load_cached_processed_files() {
+        let imported_path =
+            std::env::temp_dir().join(format!("bevy_asset_importer_test_{}", std::process::id()));
+        let importer = AssetImporter::new(&imported_path);
+        let processor = ReverseProcessor::default();
+        let processed = processor.processed.clone();
+        importer.add_processor(processor);
+        let asset_io = MemoryAssetIo::new();
+        asset_io.insert("data/a.src", vec![1, 2, 3]);
+        asset_io.insert("data/b.txt", vec![1, 2, 3]);
+        let load = |path: &str, meta: &AssetMeta| {
+            block_on(importer.load_path(&asset_io, Path::new(path), meta)).unwrap()
+        };
+        let no_meta = AssetMeta::default();
+
+        assert_eq!(
+            importer.get_processed_extension(Path::new("data/a.src")),
+            Some("rev".to_string())
+        );
+        assert_eq!(load("data/b.txt", &no_meta), vec![1, 2, 3]);
+        assert_eq!(load("data/a.src", &no_meta), vec![3, 2, 1]);
+        assert_eq!(load("data/a.src", &no_meta), vec![3, 2, 1]);
+        assert_eq!(processed.load(Ordering::Relaxed), 1);
+
+        // changing the source file or its meta file invalidates the processed file
+        asset_io.insert("data/a.src", vec![4, 5]);
+        assert_eq!(load("data/a.src", &no_meta), vec![5, 4]);
+        assert_eq!(processed.load(Ordering::Relaxed), 2);
+        let meta = "(settings: ())".parse().unwrap();
+        assert_eq!(load("data/a.src", &meta), vec![5, 4]);
+        assert_eq!(processed.load(Ordering::Relaxed), 3);
+
+        // only the processed file of the current version of the source file is kept
+        let imported_files = fs::read_dir(imported_path.join("data")).unwrap().count();
+        assert_eq!(imported_files, 1);
+
+        fs::remove_dir_all(imported_path).unwrap();
+    }
+}
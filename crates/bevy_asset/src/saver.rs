@@ -0,0 +1,10 @@
+use anyhow::Result;
+use std::path::Path;
+
+/// A saver for a given asset of type `T`. Savers are the counterpart of [AssetLoader](crate::AssetLoader)s: the
+/// bytes a saver writes for an extension can be read by the loader for the same extension.
+pub trait AssetSaver<T>: Send + Sync + 'static {
+    /// Encodes the asset as the contents of the file at `asset_path`
+    fn to_bytes(&self, asset_path: &Path, asset: &T) -> Result<Vec<u8>, anyhow::Error>;
+    fn extensions(&self) -> &[&str];
+}
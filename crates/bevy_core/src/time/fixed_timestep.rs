@@ -0,0 +1,176 @@
+use crate::time::Time;
+use bevy_ecs::{Resources, RunCriteria, ShouldRun, World};
+use bevy_utils::HashMap;
+
+/// The state of a [FixedTimestep]
+#[derive(Debug, Clone)]
+pub struct FixedTimestepState {
+    step: f64,
+    accumulator: f64,
+}
+
+impl FixedTimestepState {
+    /// The time between steps in seconds
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// The number of steps that run per second
+    pub fn steps_per_second(&self) -> f64 {
+        1.0 / self.step
+    }
+
+    /// The time that has accumulated since the last step in seconds
+    pub fn accumulator(&self) -> f64 {
+        self.accumulator
+    }
+
+    /// How far the accumulated time is into the next step, from 0.0 to 1.0. This can be used to interpolate between
+    /// the last two steps when rendering.
+    pub fn alpha(&self) -> f64 {
+        self.accumulator / self.step
+    }
+}
+
+/// A resource that stores the state of each labeled [FixedTimestep]
+#[derive(Debug, Default)]
+pub struct FixedTimesteps {
+    fixed_timesteps: HashMap<String, FixedTimestepState>,
+}
+
+impl FixedTimesteps {
+    pub fn get(&self, label: &str) -> Option<&FixedTimestepState> {
+        self.fixed_timesteps.get(label)
+    }
+}
+
+/// A [RunCriteria] that runs its stage at a fixed rate, independent of the frame rate. Each update, the [Time] delta is
+/// added to an accumulator and the stage runs once for every full step that has accumulated, which can be zero or
+/// multiple times per update. The stage runs at most [FixedTimestep::with_max_steps] times per update, so a long stall
+/// doesn't make every following update slower as it tries to catch up. The time that can't be caught up on is dropped.
+///
+/// ```
+/// # use bevy_core::FixedTimestep;
+/// # use bevy_ecs::Schedule;
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage("physics");
+/// schedule.set_run_criteria("physics", FixedTimestep::steps_per_second(60.0).with_label("physics"));
+/// ```
+#[derive(Debug)]
+pub struct FixedTimestep {
+    state: FixedTimestepState,
+    label: Option<String>,
+    max_steps: u32,
+    steps: u32,
+    looping: bool,
+}
+
+impl FixedTimestep {
+    /// The default maximum number of times the stage runs per update
+    pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+    /// Runs the stage every `step` seconds
+    pub fn step(step: f64) -> Self {
+        FixedTimestep {
+            state: FixedTimestepState {
+                step,
+                accumulator: 0.0,
+            },
+            label: None,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            steps: 0,
+            looping: false,
+        }
+    }
+
+    /// Runs the stage `rate` times per second
+    pub fn steps_per_second(rate: f64) -> Self {
+        Self::step(1.0 / rate)
+    }
+
+    /// Stores the timestep's [FixedTimestepState] in the [FixedTimesteps] resource under the given label
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Sets the maximum number of times the stage runs per update
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    fn update(&mut self, delta_seconds: f64) -> ShouldRun {
+        // the delta is only accumulated once per update, not every time the stage loops
+        if !self.looping {
+            self.state.accumulator += delta_seconds;
+            self.steps = 0;
+        }
+
+        if self.state.accumulator < self.state.step {
+            self.looping = false;
+            ShouldRun::No
+        } else if self.steps >= self.max_steps {
+            self.state.accumulator %= self.state.step;
+            self.looping = false;
+            ShouldRun::No
+        } else {
+            self.state.accumulator -= self.state.step;
+            self.steps += 1;
+            self.looping = true;
+            ShouldRun::YesAndLoop
+        }
+    }
+}
+
+impl RunCriteria for FixedTimestep {
+    fn should_run(&mut self, _world: &mut World, resources: &mut Resources) -> ShouldRun {
+        let delta_seconds = resources
+            .get::<Time>()
+            .expect("FixedTimestep requires the Time resource")
+            .delta_seconds_f64;
+        let should_run = self.update(delta_seconds);
+
+        if let Some(ref label) = self.label {
+            let mut fixed_timesteps = resources
+                .get_mut::<FixedTimesteps>()
+                .expect("labeled FixedTimesteps require the FixedTimesteps resource");
+            fixed_timesteps
+                .fixed_timesteps
+                .insert(label.clone(), self.state.clone());
+        }
+
+        should_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_timestep() {
+        let mut fixed_timestep = FixedTimestep::step(0.5);
+        let mut steps = |delta_seconds| {
+            let mut steps = 0;
+            while fixed_timestep.update(delta_seconds) == ShouldRun::YesAndLoop {
+                steps += 1;
+            }
+            steps
+        };
+
+        assert_eq!(steps(0.25), 0);
+        assert_eq!(steps(0.5), 1);
+        assert_eq!(steps(1.3), 3);
+        assert_eq!(steps(0.0), 0);
+        assert!((fixed_timestep.state.alpha() - 0.1).abs() < 1e-9);
+
+        let mut fixed_timestep = FixedTimestep::step(0.5).with_max_steps(2);
+        let mut steps = 0;
+        while fixed_timestep.update(10.2) == ShouldRun::YesAndLoop {
+            steps += 1;
+        }
+        assert_eq!(steps, 2);
+        assert!((fixed_timestep.state.alpha() - 0.4).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,8 @@
+mod fixed_timestep;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
+pub use fixed_timestep::*;
 pub use time::*;
 pub use timer::*;
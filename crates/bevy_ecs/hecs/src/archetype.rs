@@ -19,6 +19,8 @@ use crate::{
         alloc::{alloc, dealloc, Layout},
         vec::Vec,
     },
+    change_detection::{check_tick, MAX_CHANGE_AGE},
+    sparse_set::SparseSet,
     Entity,
 };
 use bevy_utils::{HashMap, HashMapExt};
@@ -29,7 +31,11 @@ use core::{
     ptr::{self, NonNull},
 };
 
-use crate::{borrow::AtomicBorrow, query::Fetch, Access, Component, Query};
+use crate::{
+    borrow::{AtomicBorrow, PartitionBorrow},
+    query::{Fetch, Partition},
+    Access, Component, Query,
+};
 
 /// A collection of entities having the same component types
 ///
@@ -45,6 +51,9 @@ pub struct Archetype {
     data: UnsafeCell<NonNull<u8>>,
     data_size: usize,
     grow_size: usize,
+    sparse_sets: HashMap<TypeId, SparseSet>,
+    /// The borrows of each component column split by each sparse set type, keyed by `(column, sparse set)`
+    partition_borrows: HashMap<(TypeId, TypeId), PartitionBorrow>,
 }
 
 impl Archetype {
@@ -71,6 +80,8 @@ impl Archetype {
             data: UnsafeCell::new(NonNull::dangling()),
             data_size: 0,
             grow_size,
+            sparse_sets: HashMap::default(),
+            partition_borrows: HashMap::default(),
         }
     }
 
@@ -86,6 +97,9 @@ impl Archetype {
                 }
             }
         }
+        for sparse_set in self.sparse_sets.values_mut() {
+            sparse_set.clear();
+        }
         self.len = 0;
     }
 
@@ -102,7 +116,21 @@ impl Archetype {
     }
 
     pub(crate) fn has_dynamic(&self, id: TypeId) -> bool {
-        self.state.contains_key(&id)
+        self.state.contains_key(&id) || self.sparse_sets.contains_key(&id)
+    }
+
+    /// Returns true if the entity at `index` has a component of type `ty`. Components in the archetype's table are
+    /// present for every entity, while sparse set components are only present for some.
+    pub fn has_component(&self, ty: TypeId, index: usize) -> bool {
+        self.state.contains_key(&ty)
+            || self
+                .sparse_sets
+                .get(&ty)
+                .map_or(false, |sparse_set| sparse_set.contains(index))
+    }
+
+    pub(crate) fn has_table_type(&self, ty: TypeId) -> bool {
+        self.state.contains_key(&ty)
     }
 
     #[allow(missing_docs)]
@@ -140,13 +168,22 @@ impl Archetype {
         self.state.get_mut(&ty)
     }
 
+    fn get_borrow(&self, ty: TypeId) -> Option<&AtomicBorrow> {
+        match self.state.get(&ty) {
+            Some(state) => Some(&state.borrow),
+            None => self
+                .sparse_sets
+                .get(&ty)
+                .map(|sparse_set| &sparse_set.borrow),
+        }
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn borrow<T: Component>(&self) {
         if self
-            .state
-            .get(&TypeId::of::<T>())
-            .map_or(false, |x| !x.borrow.borrow())
+            .get_borrow(TypeId::of::<T>())
+            .map_or(false, |x| !x.borrow())
         {
             panic!("{} already borrowed uniquely", type_name::<T>());
         }
@@ -156,9 +193,8 @@ impl Archetype {
     #[inline]
     pub fn borrow_mut<T: Component>(&self) {
         if self
-            .state
-            .get(&TypeId::of::<T>())
-            .map_or(false, |x| !x.borrow.borrow_mut())
+            .get_borrow(TypeId::of::<T>())
+            .map_or(false, |x| !x.borrow_mut())
         {
             panic!("{} already borrowed", type_name::<T>());
         }
@@ -167,16 +203,69 @@ impl Archetype {
     #[allow(missing_docs)]
     #[inline]
     pub fn release<T: Component>(&self) {
-        if let Some(x) = self.state.get(&TypeId::of::<T>()) {
-            x.borrow.release();
+        if let Some(x) = self.get_borrow(TypeId::of::<T>()) {
+            x.release();
         }
     }
 
     #[allow(missing_docs)]
     #[inline]
     pub fn release_mut<T: Component>(&self) {
-        if let Some(x) = self.state.get(&TypeId::of::<T>()) {
-            x.borrow.release_mut();
+        if let Some(x) = self.get_borrow(TypeId::of::<T>()) {
+            x.release_mut();
+        }
+    }
+
+    fn get_partition_borrow(
+        &self,
+        ty: TypeId,
+        partition: Partition,
+    ) -> Option<(&AtomicBorrow, &PartitionBorrow)> {
+        Some((
+            self.get_borrow(ty)?,
+            self.partition_borrows.get(&(ty, partition.ty))?,
+        ))
+    }
+
+    /// Borrows the `T` components of the entities on one side of `partition`. Borrows of the other side don't
+    /// conflict, but borrows of the whole column do.
+    #[inline]
+    pub fn borrow_partition<T: Component>(&self, partition: Partition) {
+        if self
+            .get_partition_borrow(TypeId::of::<T>(), partition)
+            .map_or(false, |(column, x)| !x.borrow(column, partition.contains))
+        {
+            panic!("{} already borrowed uniquely", type_name::<T>());
+        }
+    }
+
+    /// Uniquely borrows the `T` components of the entities on one side of `partition`. Borrows of the other side don't
+    /// conflict, but borrows of the whole column do.
+    #[inline]
+    pub fn borrow_partition_mut<T: Component>(&self, partition: Partition) {
+        if self
+            .get_partition_borrow(TypeId::of::<T>(), partition)
+            .map_or(false, |(column, x)| {
+                !x.borrow_mut(column, partition.contains)
+            })
+        {
+            panic!("{} already borrowed", type_name::<T>());
+        }
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn release_partition<T: Component>(&self, partition: Partition) {
+        if let Some((column, x)) = self.get_partition_borrow(TypeId::of::<T>(), partition) {
+            x.release(column, partition.contains);
+        }
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn release_partition_mut<T: Component>(&self, partition: Partition) {
+        if let Some((column, x)) = self.get_partition_borrow(TypeId::of::<T>(), partition) {
+            x.release_mut(column, partition.contains);
         }
     }
 
@@ -206,11 +295,79 @@ impl Archetype {
         self.entities[index]
     }
 
-    #[allow(missing_docs)]
+    /// The types of the components in the archetype's table, which every entity in the archetype has
     pub fn types(&self) -> &[TypeInfo] {
         &self.types
     }
 
+    /// The types of the sparse set components entities in the archetype can have. These aren't part of the
+    /// archetype's identity, so use [Archetype::has_component] to check which entities have them.
+    pub fn sparse_set_types(&self) -> impl Iterator<Item = &TypeInfo> {
+        self.sparse_sets
+            .values()
+            .map(|sparse_set| sparse_set.type_info())
+    }
+
+    pub(crate) fn sparse_set(&self, ty: TypeId) -> Option<&SparseSet> {
+        self.sparse_sets.get(&ty)
+    }
+
+    /// Adds a sparse set for the given type. Returns false if the archetype already has one.
+    pub(crate) fn add_sparse_set(&mut self, info: TypeInfo) -> bool {
+        if self.sparse_sets.contains_key(&info.id) {
+            return false;
+        }
+        for &column in self.state.keys().chain(self.sparse_sets.keys()) {
+            self.partition_borrows
+                .insert((column, info.id), PartitionBorrow::new());
+            self.partition_borrows
+                .insert((info.id, column), PartitionBorrow::new());
+        }
+        self.partition_borrows
+            .insert((info.id, info.id), PartitionBorrow::new());
+        self.sparse_sets.insert(info.id, SparseSet::new(info));
+        true
+    }
+
+    /// Returns a pointer to the component of type `T` of the entity at `index`, which can be stored in the
+    /// archetype's table or in a sparse set
+    ///
+    /// # Safety
+    /// `index` must be in-bounds
+    #[inline]
+    pub unsafe fn get_component<T: Component>(&self, index: usize) -> Option<NonNull<T>> {
+        match self.get::<T>() {
+            Some(components) => Some(NonNull::new_unchecked(components.as_ptr().add(index))),
+            None => self
+                .sparse_sets
+                .get(&TypeId::of::<T>())?
+                .get(index)
+                .map(|ptr| ptr.cast::<T>()),
+        }
+    }
+
+    /// Like [Archetype::get_component], but also returns a pointer to the tick the component was last mutated at
+    ///
+    /// # Safety
+    /// `index` must be in-bounds
+    #[inline]
+    pub(crate) unsafe fn get_component_with_mutated_tick<T: Component>(
+        &self,
+        index: usize,
+    ) -> Option<(NonNull<T>, NonNull<u32>)> {
+        match self.get_with_type_state::<T>() {
+            Some((components, type_state)) => Some((
+                NonNull::new_unchecked(components.as_ptr().add(index)),
+                NonNull::new_unchecked(type_state.mutated().as_ptr().add(index)),
+            )),
+            None => self
+                .sparse_sets
+                .get(&TypeId::of::<T>())?
+                .get_with_ticks(index)
+                .map(|(ptr, _, mutated)| (ptr.cast::<T>(), mutated)),
+        }
+    }
+
     /// # Safety
     /// `index` must be in-bounds
     pub(crate) unsafe fn get_dynamic(
@@ -228,6 +385,11 @@ impl Archetype {
         ))
     }
 
+    /// Returns a pointer to the sparse set component of type `ty` of the entity at `index`, if it has one
+    pub(crate) fn get_sparse_dynamic(&self, ty: TypeId, index: usize) -> Option<NonNull<u8>> {
+        self.sparse_sets.get(&ty)?.get(index)
+    }
+
     /// # Safety
     /// Every type must be written immediately after this call
     pub unsafe fn allocate(&mut self, id: Entity) -> usize {
@@ -250,10 +412,14 @@ impl Archetype {
         self.entities.len()
     }
 
-    #[allow(missing_docs)]
-    pub fn clear_trackers(&mut self) {
+    /// Clamps the added and mutated ticks of all components so they are never more than
+    /// [MAX_CHANGE_AGE](crate::MAX_CHANGE_AGE) ticks older than `change_tick`
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
         for type_state in self.state.values_mut() {
-            type_state.clear_trackers();
+            type_state.check_change_ticks(change_tick);
+        }
+        for sparse_set in self.sparse_sets.values_mut() {
+            sparse_set.check_change_ticks(change_tick);
         }
     }
 
@@ -270,8 +436,8 @@ impl Archetype {
             );
 
             for type_state in self.state.values_mut() {
-                type_state.mutated_entities.resize_with(count, || false);
-                type_state.added_entities.resize_with(count, || false);
+                type_state.mutated_ticks.resize(count, 0);
+                type_state.added_ticks.resize(count, 0);
             }
 
             let old_data_size = mem::replace(&mut self.data_size, 0);
@@ -328,7 +494,6 @@ impl Archetype {
                 .as_ptr();
             (ty.drop)(removed);
             if index != last {
-                // TODO: copy component tracker state here
                 ptr::copy_nonoverlapping(
                     self.get_dynamic(ty.id, ty.layout.size(), last)
                         .unwrap()
@@ -338,8 +503,14 @@ impl Archetype {
                 );
 
                 let type_state = self.state.get_mut(&ty.id).unwrap();
-                type_state.mutated_entities[index] = type_state.mutated_entities[last];
-                type_state.added_entities[index] = type_state.added_entities[last];
+                type_state.mutated_ticks[index] = type_state.mutated_ticks[last];
+                type_state.added_ticks[index] = type_state.added_ticks[last];
+            }
+        }
+        for sparse_set in self.sparse_sets.values_mut() {
+            sparse_set.remove(index);
+            if index != last {
+                sparse_set.move_row(last, index);
             }
         }
         self.len = last;
@@ -355,7 +526,7 @@ impl Archetype {
     pub(crate) unsafe fn move_to(
         &mut self,
         index: usize,
-        mut f: impl FnMut(*mut u8, TypeId, usize, bool, bool),
+        mut f: impl FnMut(*mut u8, TypeId, usize, u32, u32),
     ) -> Option<Entity> {
         let last = self.len - 1;
         for ty in &self.types {
@@ -364,9 +535,9 @@ impl Archetype {
                 .unwrap()
                 .as_ptr();
             let type_state = self.state.get(&ty.id).unwrap();
-            let is_added = type_state.added_entities[index];
-            let is_mutated = type_state.mutated_entities[index];
-            f(moved, ty.id(), ty.layout().size(), is_added, is_mutated);
+            let added_tick = type_state.added_ticks[index];
+            let mutated_tick = type_state.mutated_ticks[index];
+            f(moved, ty.id(), ty.layout().size(), added_tick, mutated_tick);
             if index != last {
                 ptr::copy_nonoverlapping(
                     self.get_dynamic(ty.id, ty.layout.size(), last)
@@ -376,8 +547,17 @@ impl Archetype {
                     ty.layout.size(),
                 );
                 let type_state = self.state.get_mut(&ty.id).unwrap();
-                type_state.added_entities[index] = type_state.added_entities[last];
-                type_state.mutated_entities[index] = type_state.mutated_entities[last];
+                type_state.added_ticks[index] = type_state.added_ticks[last];
+                type_state.mutated_ticks[index] = type_state.mutated_ticks[last];
+            }
+        }
+        for sparse_set in self.sparse_sets.values_mut() {
+            let ty = *sparse_set.type_info();
+            sparse_set.take(index, |moved, added_tick, mutated_tick| {
+                f(moved, ty.id, ty.layout.size(), added_tick, mutated_tick)
+            });
+            if index != last {
+                sparse_set.move_row(last, index);
             }
         }
         self.len -= 1;
@@ -396,6 +576,9 @@ impl Archetype {
     ///  - `index` must be in-bound
     ///  - `size` must be the size of the component
     ///  - the storage array must be big enough
+    ///
+    /// If `added` is true, the component is marked as added at `change_tick`. Otherwise it replaces an existing
+    /// component and is marked as mutated at `change_tick`.
     pub unsafe fn put_dynamic(
         &mut self,
         component: *mut u8,
@@ -403,10 +586,15 @@ impl Archetype {
         size: usize,
         index: usize,
         added: bool,
+        change_tick: u32,
     ) {
         let state = self.state.get_mut(&ty).unwrap();
         if added {
-            state.added_entities[index] = true;
+            state.added_ticks[index] = change_tick;
+            // added components don't count as mutated
+            state.mutated_ticks[index] = change_tick.wrapping_sub(MAX_CHANGE_AGE);
+        } else {
+            state.mutated_ticks[index] = change_tick;
         }
         let ptr = (*self.data.get())
             .as_ptr()
@@ -415,6 +603,77 @@ impl Archetype {
         ptr::copy_nonoverlapping(component, ptr, size);
     }
 
+    /// Moves a component into the sparse set of type `ty`. If the entity at `index` already has a component of this
+    /// type, it is dropped and replaced, which counts as a mutation.
+    ///
+    /// # Safety
+    ///
+    ///  - `component` must point to a valid component of type `ty`, which must not be used afterwards
+    ///  - the archetype must have a sparse set for `ty`
+    pub(crate) unsafe fn put_sparse(
+        &mut self,
+        component: *mut u8,
+        ty: TypeId,
+        index: usize,
+        change_tick: u32,
+    ) {
+        self.sparse_sets
+            .get_mut(&ty)
+            .unwrap()
+            .insert(index, component, change_tick);
+    }
+
+    /// Writes a component of a newly allocated entity into the archetype's table or into a sparse set, and marks it
+    /// as added at `change_tick`
+    ///
+    /// # Safety
+    /// See [Archetype::put_dynamic] and [Archetype::put_sparse]
+    pub(crate) unsafe fn put_new(
+        &mut self,
+        component: *mut u8,
+        ty: TypeId,
+        size: usize,
+        index: usize,
+        change_tick: u32,
+    ) {
+        if self.state.contains_key(&ty) {
+            self.put_dynamic(component, ty, size, index, true, change_tick);
+        } else {
+            self.put_sparse(component, ty, index, change_tick);
+        }
+    }
+
+    /// Like [Archetype::put_sparse], but keeps the ticks of a component moved from another archetype
+    ///
+    /// # Safety
+    ///
+    /// See [Archetype::put_sparse]. The entity at `index` must not have a component of type `ty` yet.
+    pub(crate) unsafe fn put_sparse_with_ticks(
+        &mut self,
+        component: *mut u8,
+        ty: TypeId,
+        index: usize,
+        added_tick: u32,
+        mutated_tick: u32,
+    ) {
+        self.sparse_sets
+            .get_mut(&ty)
+            .unwrap()
+            .push(index, component, added_tick, mutated_tick);
+    }
+
+    /// Removes the sparse set component of type `ty` of the entity at `index` without dropping it. Returns false if
+    /// the entity doesn't have one.
+    ///
+    /// # Safety
+    /// The component must have been moved out of the archetype
+    pub(crate) unsafe fn forget_sparse(&mut self, ty: TypeId, index: usize) -> bool {
+        match self.sparse_sets.get_mut(&ty) {
+            Some(sparse_set) => sparse_set.take(index, |_, _, _| {}),
+            None => false,
+        }
+    }
+
     /// How, if at all, `Q` will access entities in this archetype
     pub fn access<Q: Query>(&self) -> Option<Access> {
         Q::Fetch::access(self)
@@ -442,8 +701,8 @@ impl Drop for Archetype {
 pub struct TypeState {
     offset: usize,
     borrow: AtomicBorrow,
-    mutated_entities: Vec<bool>,
-    added_entities: Vec<bool>,
+    mutated_ticks: Vec<u32>,
+    added_ticks: Vec<u32>,
 }
 
 impl TypeState {
@@ -451,31 +710,31 @@ impl TypeState {
         Self {
             offset: 0,
             borrow: AtomicBorrow::new(),
-            mutated_entities: Vec::new(),
-            added_entities: Vec::new(),
+            mutated_ticks: Vec::new(),
+            added_ticks: Vec::new(),
         }
     }
 
-    fn clear_trackers(&mut self) {
-        for mutated in self.mutated_entities.iter_mut() {
-            *mutated = false;
+    fn check_change_ticks(&mut self, change_tick: u32) {
+        for tick in self.mutated_ticks.iter_mut() {
+            check_tick(tick, change_tick);
         }
 
-        for added in self.added_entities.iter_mut() {
-            *added = false;
+        for tick in self.added_ticks.iter_mut() {
+            check_tick(tick, change_tick);
         }
     }
 
-    #[allow(missing_docs)]
+    /// The change tick each component was last mutated at
     #[inline]
-    pub fn mutated(&self) -> NonNull<bool> {
-        unsafe { NonNull::new_unchecked(self.mutated_entities.as_ptr() as *mut bool) }
+    pub fn mutated(&self) -> NonNull<u32> {
+        unsafe { NonNull::new_unchecked(self.mutated_ticks.as_ptr() as *mut u32) }
     }
 
-    #[allow(missing_docs)]
+    /// The change tick each component was added at
     #[inline]
-    pub fn added(&self) -> NonNull<bool> {
-        unsafe { NonNull::new_unchecked(self.added_entities.as_ptr() as *mut bool) }
+    pub fn added(&self) -> NonNull<u32> {
+        unsafe { NonNull::new_unchecked(self.added_ticks.as_ptr() as *mut u32) }
     }
 }
 
@@ -485,6 +744,7 @@ pub struct TypeInfo {
     id: TypeId,
     layout: Layout,
     drop: unsafe fn(*mut u8),
+    type_name: &'static str,
 }
 
 impl TypeInfo {
@@ -498,6 +758,7 @@ impl TypeInfo {
             id: TypeId::of::<T>(),
             layout: Layout::new::<T>(),
             drop: drop_ptr::<T>,
+            type_name: core::any::type_name::<T>(),
         }
     }
 
@@ -513,6 +774,12 @@ impl TypeInfo {
         self.layout
     }
 
+    /// The name of the type, for diagnostics
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
     pub(crate) unsafe fn drop(&self, data: *mut u8) {
         (self.drop)(data)
     }
@@ -63,6 +63,112 @@ impl AtomicBorrow {
 
 const UNIQUE_BIT: usize = !(usize::max_value() >> 1);
 
+/// Tracks the borrows of a component column that are split by whether entities have a sparse set component, so that
+/// queries filtered on opposite sides of the split can access the column at once. Together, the holders of either
+/// side keep the whole column borrowed uniquely.
+pub struct PartitionBorrow {
+    holders: AtomicUsize,
+    sides: [AtomicBorrow; 2],
+}
+
+/// Marks `PartitionBorrow::holders` while the first or last holder borrows or releases the column
+const LOCKED: usize = usize::MAX;
+
+impl PartitionBorrow {
+    pub const fn new() -> Self {
+        Self {
+            holders: AtomicUsize::new(0),
+            sides: [AtomicBorrow::new(), AtomicBorrow::new()],
+        }
+    }
+
+    pub fn borrow(&self, column: &AtomicBorrow, contains: bool) -> bool {
+        if !self.hold(column) {
+            return false;
+        }
+        if self.sides[contains as usize].borrow() {
+            true
+        } else {
+            self.unhold(column);
+            false
+        }
+    }
+
+    pub fn borrow_mut(&self, column: &AtomicBorrow, contains: bool) -> bool {
+        if !self.hold(column) {
+            return false;
+        }
+        if self.sides[contains as usize].borrow_mut() {
+            true
+        } else {
+            self.unhold(column);
+            false
+        }
+    }
+
+    pub fn release(&self, column: &AtomicBorrow, contains: bool) {
+        self.sides[contains as usize].release();
+        self.unhold(column);
+    }
+
+    pub fn release_mut(&self, column: &AtomicBorrow, contains: bool) {
+        self.sides[contains as usize].release_mut();
+        self.unhold(column);
+    }
+
+    fn hold(&self, column: &AtomicBorrow) -> bool {
+        loop {
+            let holders = self.holders.load(Ordering::Acquire);
+            if holders == LOCKED {
+                core::hint::spin_loop();
+            } else if holders == 0 {
+                if self
+                    .holders
+                    .compare_exchange_weak(0, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    let borrowed = column.borrow_mut();
+                    self.holders
+                        .store(if borrowed { 1 } else { 0 }, Ordering::Release);
+                    return borrowed;
+                }
+            } else if self
+                .holders
+                .compare_exchange_weak(holders, holders + 1, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    fn unhold(&self, column: &AtomicBorrow) {
+        loop {
+            let holders = self.holders.load(Ordering::Acquire);
+            debug_assert!(holders != 0, "unbalanced release");
+            if holders == LOCKED {
+                core::hint::spin_loop();
+            } else if holders == 1 {
+                if self
+                    .holders
+                    .compare_exchange_weak(1, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    column.release_mut();
+                    self.holders.store(0, Ordering::Release);
+                    return;
+                }
+            } else if self
+                .holders
+                .compare_exchange_weak(holders, holders - 1, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+}
+
 /// Shared borrow of an entity's component
 #[derive(Clone)]
 pub struct Ref<'a, T: Component> {
@@ -78,12 +184,12 @@ impl<'a, T: Component> Ref<'a, T> {
     /// - the index of the component must be valid
     pub unsafe fn new(archetype: &'a Archetype, index: usize) -> Result<Self, MissingComponent> {
         let target = archetype
-            .get::<T>()
+            .get_component::<T>(index)
             .ok_or_else(MissingComponent::new::<T>)?;
         archetype.borrow::<T>();
         Ok(Self {
             archetype,
-            target: &*target.as_ptr().add(index as usize),
+            target: &*target.as_ptr(),
         })
     }
 }
@@ -118,24 +224,31 @@ where
 pub struct RefMut<'a, T: Component> {
     archetype: &'a Archetype,
     target: &'a mut T,
-    modified: &'a mut bool,
+    mutated_tick: &'a mut u32,
+    change_tick: u32,
 }
 
 impl<'a, T: Component> RefMut<'a, T> {
-    /// Creates a new entity component mutable borrow
+    /// Creates a new entity component mutable borrow, which marks the component as mutated at `change_tick` when
+    /// it is mutably dereferenced
     ///
     /// # Safety
     ///
     /// - the index of the component must be valid
-    pub unsafe fn new(archetype: &'a Archetype, index: usize) -> Result<Self, MissingComponent> {
-        let (target, type_state) = archetype
-            .get_with_type_state::<T>()
+    pub unsafe fn new(
+        archetype: &'a Archetype,
+        index: usize,
+        change_tick: u32,
+    ) -> Result<Self, MissingComponent> {
+        let (target, mutated_tick) = archetype
+            .get_component_with_mutated_tick::<T>(index)
             .ok_or_else(MissingComponent::new::<T>)?;
         archetype.borrow_mut::<T>();
         Ok(Self {
             archetype,
-            target: &mut *target.as_ptr().add(index),
-            modified: &mut *type_state.mutated().as_ptr().add(index),
+            target: &mut *target.as_ptr(),
+            mutated_tick: &mut *mutated_tick.as_ptr(),
+            change_tick,
         })
     }
 }
@@ -159,7 +272,7 @@ impl<'a, T: Component> Deref for RefMut<'a, T> {
 
 impl<'a, T: Component> DerefMut for RefMut<'a, T> {
     fn deref_mut(&mut self) -> &mut T {
-        *self.modified = true;
+        *self.mutated_tick = self.change_tick;
         self.target
     }
 }
@@ -178,6 +291,7 @@ where
 pub struct EntityRef<'a> {
     archetype: Option<&'a Archetype>,
     index: usize,
+    change_tick: u32,
 }
 
 impl<'a> EntityRef<'a> {
@@ -186,13 +300,15 @@ impl<'a> EntityRef<'a> {
         Self {
             archetype: None,
             index: 0,
+            change_tick: 0,
         }
     }
 
-    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize) -> Self {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize, change_tick: u32) -> Self {
         Self {
             archetype: Some(archetype),
             index,
+            change_tick,
         }
     }
 
@@ -208,7 +324,7 @@ impl<'a> EntityRef<'a> {
     ///
     /// Panics if the component is already borrowed from another entity with the same components.
     pub fn get_mut<T: Component>(&self) -> Option<RefMut<'a, T>> {
-        Some(unsafe { RefMut::new(self.archetype?, self.index).ok()? })
+        Some(unsafe { RefMut::new(self.archetype?, self.index, self.change_tick).ok()? })
     }
 }
 
@@ -0,0 +1,66 @@
+// modified by Bevy contributors
+
+/// The number of change ticks that can pass before stored ticks are clamped by [World::check_change_ticks].
+/// This must be low enough that the clamped ticks of a component and a system can't wrap around past each other.
+///
+/// [World::check_change_ticks]: crate::World::check_change_ticks
+pub const CHECK_TICK_THRESHOLD: u32 = 518_400_000;
+
+/// The maximum age a stored change tick can have before it is clamped. Anything older than this is treated as
+/// "changed a very long time ago".
+pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
+
+/// The change ticks a query compares component ticks against. A component counts as added or mutated if its tick is
+/// newer than `last_change_tick`, which is usually the tick the querying system last ran at.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ChangeTicks {
+    /// The tick changes are detected since
+    pub last_change_tick: u32,
+    /// The current tick, which is written to components that are mutated through the query
+    pub change_tick: u32,
+}
+
+impl ChangeTicks {
+    #[allow(missing_docs)]
+    pub fn new(last_change_tick: u32, change_tick: u32) -> Self {
+        Self {
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    /// Returns true if `tick` is newer than `last_change_tick`. Ticks are compared relative to `change_tick` so the
+    /// comparison keeps working when the world's tick counter wraps around.
+    #[inline]
+    pub fn is_changed(&self, tick: u32) -> bool {
+        let ticks_since_change = self.change_tick.wrapping_sub(tick);
+        let ticks_since_last_run = self.change_tick.wrapping_sub(self.last_change_tick);
+        ticks_since_change < ticks_since_last_run
+    }
+}
+
+/// Clamps `tick` so it is never more than [MAX_CHANGE_AGE] ticks older than `change_tick`
+#[inline]
+pub fn check_tick(tick: &mut u32, change_tick: u32) {
+    if change_tick.wrapping_sub(*tick) > MAX_CHANGE_AGE {
+        *tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_tick_wraparound() {
+        let ticks = ChangeTicks::new(u32::MAX - 1, 2);
+        assert!(ticks.is_changed(u32::MAX));
+        assert!(ticks.is_changed(1));
+        assert!(!ticks.is_changed(u32::MAX - 2));
+
+        let mut tick = 3;
+        let change_tick = tick + MAX_CHANGE_AGE + 10;
+        check_tick(&mut tick, change_tick);
+        assert_eq!(change_tick.wrapping_sub(tick), MAX_CHANGE_AGE);
+    }
+}
@@ -0,0 +1,420 @@
+// modified by Bevy contributors
+
+use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    archetype::Archetype,
+    query::{
+        Access, Added, Changed, Fetch, Mutated, Or, Partition, Query, ReadOnlyFetch, With, Without,
+    },
+    sparse_set::SparseSet,
+    ChangeTicks, Component,
+};
+
+/// A set of conditions entities must meet to be yielded by a query, without fetching any components.
+///
+/// Filters only read the components' change ticks, so unlike queries like [Changed] they never borrow the components
+/// they test.
+pub trait QueryFilter {
+    #[doc(hidden)]
+    type EntityFilter: EntityFilter;
+
+    /// How this filter will access `archetype`, if at all. `None` means no entity of `archetype` can match.
+    fn access(archetype: &Archetype) -> Option<Access>;
+
+    /// The entities of `archetype` this filter is restricted to by a sparse set component, if any. Filtered queries
+    /// only borrow the components of these entities.
+    fn partition(_archetype: &Archetype) -> Option<Partition> {
+        None
+    }
+
+    /// Construct an `EntityFilter` for `archetype`, or `None` if no entity of `archetype` can match. Change detection
+    /// compares component ticks against `ticks`.
+    ///
+    /// # Safety
+    /// The filter must not outlive `archetype`
+    unsafe fn get_entity_filter(
+        archetype: &Archetype,
+        ticks: ChangeTicks,
+    ) -> Option<Self::EntityFilter>;
+}
+
+/// Tests the entities of a single archetype against a [QueryFilter]
+pub trait EntityFilter: Sized {
+    /// Returns true if the entity at `offset` matches the filter
+    ///
+    /// # Safety
+    /// `offset` must be in bounds of the archetype the filter was constructed for
+    unsafe fn matches_entity(&self, offset: usize) -> bool;
+}
+
+impl<T: Component> QueryFilter for With<T, ()> {
+    type EntityFilter = FilterWith;
+
+    fn access(archetype: &Archetype) -> Option<Access> {
+        if archetype.has::<T>() {
+            Some(Access::Iterate)
+        } else {
+            None
+        }
+    }
+
+    fn partition(archetype: &Archetype) -> Option<Partition> {
+        Partition::of::<T>(archetype, true)
+    }
+
+    unsafe fn get_entity_filter(
+        archetype: &Archetype,
+        _ticks: ChangeTicks,
+    ) -> Option<Self::EntityFilter> {
+        if archetype.has_table_type(TypeId::of::<T>()) {
+            Some(FilterWith(None))
+        } else {
+            archetype
+                .sparse_set(TypeId::of::<T>())
+                .map(|sparse_set| FilterWith(Some(NonNull::from(sparse_set))))
+        }
+    }
+}
+
+/// Holds the sparse set of the filtered type, or `None` if every entity of the archetype has the component
+#[doc(hidden)]
+pub struct FilterWith(Option<NonNull<SparseSet>>);
+
+impl EntityFilter for FilterWith {
+    #[inline]
+    unsafe fn matches_entity(&self, offset: usize) -> bool {
+        self.0
+            .map_or(true, |sparse_set| sparse_set.as_ref().contains(offset))
+    }
+}
+
+impl<T: Component> QueryFilter for Without<T, ()> {
+    type EntityFilter = FilterWithout;
+
+    fn access(archetype: &Archetype) -> Option<Access> {
+        // entities in archetypes with a sparse set for `T` are checked one by one
+        if archetype.has_table_type(TypeId::of::<T>()) {
+            None
+        } else {
+            Some(Access::Iterate)
+        }
+    }
+
+    fn partition(archetype: &Archetype) -> Option<Partition> {
+        Partition::of::<T>(archetype, false)
+    }
+
+    unsafe fn get_entity_filter(
+        archetype: &Archetype,
+        _ticks: ChangeTicks,
+    ) -> Option<Self::EntityFilter> {
+        if archetype.has_table_type(TypeId::of::<T>()) {
+            None
+        } else {
+            Some(FilterWithout(
+                archetype.sparse_set(TypeId::of::<T>()).map(NonNull::from),
+            ))
+        }
+    }
+}
+
+/// Holds the sparse set of the filtered type, or `None` if no entity of the archetype has the component
+#[doc(hidden)]
+pub struct FilterWithout(Option<NonNull<SparseSet>>);
+
+impl EntityFilter for FilterWithout {
+    #[inline]
+    unsafe fn matches_entity(&self, offset: usize) -> bool {
+        self.0
+            .map_or(true, |sparse_set| !sparse_set.as_ref().contains(offset))
+    }
+}
+
+/// The added and mutated ticks of the `T` components of an archetype
+#[doc(hidden)]
+pub struct ComponentTicks<T> {
+    added: NonNull<u32>,
+    mutated: NonNull<u32>,
+    sparse_set: Option<NonNull<SparseSet>>,
+    ticks: ChangeTicks,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T: Component> ComponentTicks<T> {
+    unsafe fn new(archetype: &Archetype, ticks: ChangeTicks) -> Option<Self> {
+        if let Some((_, type_state)) = archetype.get_with_type_state::<T>() {
+            Some(Self {
+                added: type_state.added(),
+                mutated: type_state.mutated(),
+                sparse_set: None,
+                ticks,
+                _marker: PhantomData,
+            })
+        } else {
+            let sparse_set = archetype.sparse_set(TypeId::of::<T>())?;
+            Some(Self {
+                added: NonNull::dangling(),
+                mutated: NonNull::dangling(),
+                sparse_set: Some(NonNull::from(sparse_set)),
+                ticks,
+                _marker: PhantomData,
+            })
+        }
+    }
+
+    /// Returns the added and mutated ticks of the component at `offset`, or `None` if it is missing
+    #[inline]
+    unsafe fn get(&self, offset: usize) -> Option<(u32, u32)> {
+        match self.sparse_set {
+            None => Some((
+                *self.added.as_ptr().add(offset),
+                *self.mutated.as_ptr().add(offset),
+            )),
+            Some(sparse_set) => sparse_set
+                .as_ref()
+                .get_with_ticks(offset)
+                .map(|(_, added, mutated)| (*added.as_ptr(), *mutated.as_ptr())),
+        }
+    }
+}
+
+macro_rules! impl_change_filter {
+    ($filter: ident, $entity_filter: ident, |$ticks: ident, $added: ident, $mutated: ident| $matches: expr) => {
+        impl<'a, T: Component> QueryFilter for $filter<'a, T> {
+            type EntityFilter = $entity_filter<T>;
+
+            fn access(archetype: &Archetype) -> Option<Access> {
+                // the component's ticks are read, so the filter can't run alongside writes to the component
+                if archetype.has::<T>() {
+                    Some(Access::Read)
+                } else {
+                    None
+                }
+            }
+
+            unsafe fn get_entity_filter(
+                archetype: &Archetype,
+                ticks: ChangeTicks,
+            ) -> Option<Self::EntityFilter> {
+                ComponentTicks::new(archetype, ticks).map($entity_filter)
+            }
+        }
+
+        #[doc(hidden)]
+        pub struct $entity_filter<T>(ComponentTicks<T>);
+
+        impl<T: Component> EntityFilter for $entity_filter<T> {
+            #[inline]
+            unsafe fn matches_entity(&self, offset: usize) -> bool {
+                let $ticks = &self.0.ticks;
+                match self.0.get(offset) {
+                    Some(($added, $mutated)) => $matches,
+                    None => false,
+                }
+            }
+        }
+    };
+}
+
+impl_change_filter!(Added, FilterAdded, |ticks, added, _mutated| ticks
+    .is_changed(added));
+impl_change_filter!(Mutated, FilterMutated, |ticks, _added, mutated| ticks
+    .is_changed(mutated));
+impl_change_filter!(Changed, FilterChanged, |ticks, added, mutated| ticks
+    .is_changed(added)
+    || ticks.is_changed(mutated));
+
+macro_rules! impl_or_filter {
+    ( $( $F:ident ),+ ) => {
+        impl<$( $F: QueryFilter ),+> QueryFilter for Or<($( $F ),+)> {
+            type EntityFilter = FilterOr<($( Option<$F::EntityFilter> ),+)>;
+
+            fn access(archetype: &Archetype) -> Option<Access> {
+                let mut max_access = None;
+                $(
+                max_access = max_access.max($F::access(archetype));
+                )+
+                max_access
+            }
+
+            unsafe fn get_entity_filter(
+                archetype: &Archetype,
+                ticks: ChangeTicks,
+            ) -> Option<Self::EntityFilter> {
+                let filters = ($( $F::get_entity_filter(archetype, ticks) ),+);
+                #[allow(non_snake_case)]
+                let ($( $F ),+) = &filters;
+                if false $( || $F.is_some() )+ {
+                    Some(FilterOr(filters))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<$( $F: EntityFilter ),+> EntityFilter for FilterOr<($( Option<$F> ),+)> {
+            #[allow(non_snake_case)]
+            #[inline]
+            unsafe fn matches_entity(&self, offset: usize) -> bool {
+                let ($( $F ),+) = &self.0;
+                false $( || $F.as_ref().map_or(false, |filter| filter.matches_entity(offset)) )+
+            }
+        }
+    };
+}
+
+impl_or_filter!(F1, F2);
+impl_or_filter!(F1, F2, F3);
+impl_or_filter!(F1, F2, F3, F4);
+impl_or_filter!(F1, F2, F3, F4, F5);
+impl_or_filter!(F1, F2, F3, F4, F5, F6);
+impl_or_filter!(F1, F2, F3, F4, F5, F6, F7);
+impl_or_filter!(F1, F2, F3, F4, F5, F6, F7, F8);
+impl_or_filter!(F1, F2, F3, F4, F5, F6, F7, F8, F9);
+impl_or_filter!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10);
+
+#[doc(hidden)]
+pub struct FilterOr<T>(T);
+
+macro_rules! tuple_filter_impl {
+    ($($name: ident),*) => {
+        impl<$($name: QueryFilter),*> QueryFilter for ($($name,)*) {
+            type EntityFilter = ($($name::EntityFilter,)*);
+
+            #[allow(unused_variables, unused_mut)]
+            fn access(archetype: &Archetype) -> Option<Access> {
+                let mut access = Access::Iterate;
+                $(
+                    access = access.max($name::access(archetype)?);
+                )*
+                Some(access)
+            }
+
+            #[allow(unused_variables)]
+            fn partition(archetype: &Archetype) -> Option<Partition> {
+                None $(.or_else(|| $name::partition(archetype)))*
+            }
+
+            #[allow(unused_variables)]
+            unsafe fn get_entity_filter(
+                archetype: &Archetype,
+                ticks: ChangeTicks,
+            ) -> Option<Self::EntityFilter> {
+                Some(($($name::get_entity_filter(archetype, ticks)?,)*))
+            }
+        }
+
+        impl<$($name: EntityFilter),*> EntityFilter for ($($name,)*) {
+            #[allow(unused_variables)]
+            #[inline]
+            unsafe fn matches_entity(&self, offset: usize) -> bool {
+                #[allow(non_snake_case)]
+                let ($($name,)*) = self;
+                true $(&& $name.matches_entity(offset))*
+            }
+        }
+    };
+}
+
+smaller_tuples_too!(
+    tuple_filter_impl,
+    O,
+    N,
+    M,
+    L,
+    K,
+    J,
+    I,
+    H,
+    G,
+    F,
+    E,
+    D,
+    C,
+    B,
+    A
+);
+
+/// Query transformer yielding the results of the query `Q` for the entities that match the filter `F`
+///
+/// # Example
+/// ```
+/// # use bevy_hecs::*;
+/// let mut world = World::new();
+/// let a = world.spawn((123, true, "abc"));
+/// let b = world.spawn((456, false));
+/// let c = world.spawn((42, "def"));
+/// let entities = world.query::<Filtered<(Entity, &i32), (With<bool>, Without<&str>)>>()
+///     .iter()
+///     .map(|(e, &i)| (e, i))
+///     .collect::<Vec<_>>();
+/// assert_eq!(entities, &[(b, 456)]);
+/// ```
+pub struct Filtered<Q, F>(PhantomData<(Q, fn(F))>);
+
+impl<Q: Query, F: QueryFilter> Query for Filtered<Q, F> {
+    type Fetch = FetchFiltered<Q::Fetch, F>;
+}
+
+#[doc(hidden)]
+pub struct FetchFiltered<Q, F: QueryFilter> {
+    fetch: Q,
+    filter: F::EntityFilter,
+    offset: usize,
+}
+
+unsafe impl<Q: ReadOnlyFetch, F: QueryFilter> ReadOnlyFetch for FetchFiltered<Q, F> {}
+
+impl<'a, Q: Fetch<'a>, F: QueryFilter> Fetch<'a> for FetchFiltered<Q, F> {
+    type Item = Q::Item;
+
+    fn access(archetype: &Archetype) -> Option<Access> {
+        let filter_access = F::access(archetype)?;
+        Q::access(archetype).map(|access| access.max(filter_access))
+    }
+
+    fn borrow(archetype: &Archetype) {
+        // filters don't borrow the components they test, and filters on a sparse set component only borrow the
+        // components of the entities they can match
+        match F::partition(archetype) {
+            Some(partition) => Q::borrow_partition(archetype, partition),
+            None => Q::borrow(archetype),
+        }
+    }
+
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        let filter = F::get_entity_filter(archetype, ticks)?;
+        Some(Self {
+            fetch: Q::get(archetype, offset, ticks)?,
+            filter,
+            offset,
+        })
+    }
+
+    fn release(archetype: &Archetype) {
+        match F::partition(archetype) {
+            Some(partition) => Q::release_partition(archetype, partition),
+            None => Q::release(archetype),
+        }
+    }
+
+    unsafe fn should_skip(&self) -> bool {
+        self.fetch.should_skip() || !self.filter.matches_entity(self.offset)
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.fetch.is_missing() || !self.filter.matches_entity(self.offset)
+    }
+
+    unsafe fn skip(&mut self) {
+        self.offset += 1;
+        self.fetch.skip();
+    }
+
+    #[inline]
+    unsafe fn next(&mut self) -> Q::Item {
+        self.offset += 1;
+        self.fetch.next()
+    }
+}
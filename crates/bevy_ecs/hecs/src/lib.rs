@@ -67,24 +67,30 @@ macro_rules! smaller_tuples_too {
 mod archetype;
 mod borrow;
 mod bundle;
+mod change_detection;
 mod entities;
 mod entity_builder;
+mod filter;
 mod query;
 mod query_one;
 #[cfg(feature = "serde")]
 mod serde;
+mod sparse_set;
 mod world;
 
 pub use archetype::{Archetype, TypeState};
 pub use borrow::{Ref, RefMut};
 pub use bundle::{Bundle, DynamicBundle, MissingComponent};
+pub use change_detection::{ChangeTicks, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE};
 pub use entities::{Entity, EntityReserver, Location, NoSuchEntity};
 pub use entity_builder::{BuiltEntity, EntityBuilder};
+pub use filter::{EntityFilter, Filtered, QueryFilter};
 pub use query::{
-    Access, Added, BatchedIter, Changed, Mut, Mutated, Or, Query, QueryBorrow, QueryIter,
-    ReadOnlyFetch, With, Without,
+    Access, Added, BatchedIter, Changed, Mut, Mutated, Or, Partition, Query, QueryBorrow,
+    QueryIter, ReadOnlyFetch, With, Without,
 };
 pub use query_one::QueryOne;
+pub use sparse_set::{StorageType, StorageTypeError};
 pub use world::{ArchetypesGeneration, Component, ComponentError, SpawnBatchIter, World};
 
 // Unstable implementation details needed by the macros
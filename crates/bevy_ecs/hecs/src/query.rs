@@ -15,12 +15,15 @@
 // modified by Bevy contributors
 
 use core::{
+    any::TypeId,
     marker::PhantomData,
     ops::{Deref, DerefMut},
     ptr::NonNull,
 };
 
-use crate::{archetype::Archetype, Component, Entity, MissingComponent};
+use crate::{
+    archetype::Archetype, sparse_set::SparseSet, ChangeTicks, Component, Entity, MissingComponent,
+};
 
 /// A collection of component types to fetch from a `World`
 pub trait Query {
@@ -41,14 +44,25 @@ pub trait Fetch<'a>: Sized {
 
     /// Acquire dynamic borrows from `archetype`
     fn borrow(archetype: &Archetype);
-    /// Construct a `Fetch` for `archetype` if it should be traversed
+    /// Construct a `Fetch` for `archetype` if it should be traversed. Change detection compares component ticks
+    /// against `ticks`.
     ///
     /// # Safety
     /// `offset` must be in bounds of `archetype`
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self>;
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self>;
     /// Release dynamic borrows acquired by `borrow`
     fn release(archetype: &Archetype);
 
+    /// Acquire dynamic borrows from `archetype` for only the entities in `partition`. Fetches that can't split their
+    /// borrows borrow the whole archetype instead.
+    fn borrow_partition(archetype: &Archetype, _partition: Partition) {
+        Self::borrow(archetype)
+    }
+    /// Release dynamic borrows acquired by `borrow_partition`
+    fn release_partition(archetype: &Archetype, _partition: Partition) {
+        Self::release(archetype)
+    }
+
     /// if this returns true, the current item will be skipped during iteration
     ///
     /// # Safety
@@ -57,6 +71,23 @@ pub trait Fetch<'a>: Sized {
         false
     }
 
+    /// Returns true if the current item doesn't have a component this fetch requires. Table components are present
+    /// for every item, but sparse set components can be missing.
+    ///
+    /// # Safety
+    /// shouldn't be called if there is no current item
+    unsafe fn is_missing(&self) -> bool {
+        false
+    }
+
+    /// Move past the current item without accessing it
+    ///
+    /// # Safety
+    /// See `next`
+    unsafe fn skip(&mut self) {
+        self.next();
+    }
+
     /// Access the next item in this archetype without bounds checking
     ///
     /// # Safety
@@ -78,6 +109,88 @@ pub enum Access {
     Write,
 }
 
+/// The entities of an archetype that have a sparse set component, or those that don't. Queries filtered on opposite
+/// sides of a partition can borrow the same components at once.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Partition {
+    pub(crate) ty: TypeId,
+    pub(crate) contains: bool,
+}
+
+impl Partition {
+    /// The entities of `archetype` that have a `T` component if `contains` is true, or those that don't. Returns `None`
+    /// if `archetype` doesn't store `T` components in a sparse set.
+    pub fn of<T: Component>(archetype: &Archetype, contains: bool) -> Option<Self> {
+        let ty = TypeId::of::<T>();
+        archetype.sparse_set(ty).map(|_| Self { ty, contains })
+    }
+}
+
+/// Walks over the components of type `T` in an archetype, which are either stored in the archetype's table or in a
+/// sparse set
+#[doc(hidden)]
+pub struct ComponentCursor<T> {
+    value: NonNull<T>,
+    added: NonNull<u32>,
+    mutated: NonNull<u32>,
+    sparse_set: Option<NonNull<SparseSet>>,
+    row: usize,
+}
+
+impl<T: Component> ComponentCursor<T> {
+    unsafe fn new(archetype: &Archetype, offset: usize) -> Option<Self> {
+        if let Some((components, type_state)) = archetype.get_with_type_state::<T>() {
+            Some(Self {
+                value: NonNull::new_unchecked(components.as_ptr().add(offset)),
+                added: NonNull::new_unchecked(type_state.added().as_ptr().add(offset)),
+                mutated: NonNull::new_unchecked(type_state.mutated().as_ptr().add(offset)),
+                sparse_set: None,
+                row: offset,
+            })
+        } else {
+            let sparse_set = archetype.sparse_set(TypeId::of::<T>())?;
+            Some(Self {
+                value: NonNull::dangling(),
+                added: NonNull::dangling(),
+                mutated: NonNull::dangling(),
+                sparse_set: Some(NonNull::from(sparse_set)),
+                row: offset,
+            })
+        }
+    }
+
+    #[inline]
+    unsafe fn is_missing(&self) -> bool {
+        self.sparse_set
+            .map_or(false, |sparse_set| !sparse_set.as_ref().contains(self.row))
+    }
+
+    /// Pointers to the current component and its added and mutated ticks. The current item must not be missing.
+    #[inline]
+    unsafe fn current(&self) -> (NonNull<T>, NonNull<u32>, NonNull<u32>) {
+        match self.sparse_set {
+            None => (self.value, self.added, self.mutated),
+            Some(sparse_set) => {
+                let (value, added, mutated) = sparse_set
+                    .as_ref()
+                    .get_with_ticks(self.row)
+                    .expect("sparse set component is missing");
+                (value.cast::<T>(), added, mutated)
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn advance(&mut self) {
+        if self.sparse_set.is_none() {
+            self.value = NonNull::new_unchecked(self.value.as_ptr().add(1));
+            self.added = NonNull::new_unchecked(self.added.as_ptr().add(1));
+            self.mutated = NonNull::new_unchecked(self.mutated.as_ptr().add(1));
+        }
+        self.row += 1;
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct EntityFetch(NonNull<Entity>);
 unsafe impl ReadOnlyFetch for EntityFetch {}
@@ -98,7 +211,7 @@ impl<'a> Fetch<'a> for EntityFetch {
     fn borrow(_archetype: &Archetype) {}
 
     #[inline]
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
+    unsafe fn get(archetype: &'a Archetype, offset: usize, _ticks: ChangeTicks) -> Option<Self> {
         Some(EntityFetch(NonNull::new_unchecked(
             archetype.entities().as_ptr().add(offset),
         )))
@@ -120,7 +233,7 @@ impl<'a, T: Component> Query for &'a T {
 }
 
 #[doc(hidden)]
-pub struct FetchRead<T>(NonNull<T>);
+pub struct FetchRead<T>(ComponentCursor<T>);
 
 unsafe impl<T> ReadOnlyFetch for FetchRead<T> {}
 
@@ -139,21 +252,39 @@ impl<'a, T: Component> Fetch<'a> for FetchRead<T> {
         archetype.borrow::<T>();
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        archetype
-            .get::<T>()
-            .map(|x| Self(NonNull::new_unchecked(x.as_ptr().add(offset))))
+    unsafe fn get(archetype: &'a Archetype, offset: usize, _ticks: ChangeTicks) -> Option<Self> {
+        ComponentCursor::new(archetype, offset).map(Self)
     }
 
     fn release(archetype: &Archetype) {
         archetype.release::<T>();
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        archetype.borrow_partition::<T>(partition);
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        archetype.release_partition::<T>(partition);
+    }
+
+    unsafe fn should_skip(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.0.advance();
+    }
+
     #[inline]
     unsafe fn next(&mut self) -> &'a T {
-        let x = self.0.as_ptr();
-        self.0 = NonNull::new_unchecked(x.add(1));
-        &*x
+        let (value, _, _) = self.0.current();
+        self.0.advance();
+        &*value.as_ptr()
     }
 }
 
@@ -168,21 +299,28 @@ impl<T: Query> Query for Option<T> {
 /// Unique borrow of an entity's component
 pub struct Mut<'a, T: Component> {
     pub(crate) value: &'a mut T,
-    pub(crate) mutated: &'a mut bool,
+    pub(crate) mutated_tick: &'a mut u32,
+    pub(crate) change_tick: u32,
 }
 
 impl<'a, T: Component> Mut<'a, T> {
-    /// Creates a new mutable reference to a component. This is unsafe because the index bounds are not checked.
+    /// Creates a new mutable reference to a component, which marks the component as mutated at `change_tick` when
+    /// it is mutably dereferenced. This is unsafe because the index bounds are not checked.
     ///
     /// # Safety
     /// This doesn't check the bounds of index in archetype
-    pub unsafe fn new(archetype: &'a Archetype, index: usize) -> Result<Self, MissingComponent> {
-        let (target, type_state) = archetype
-            .get_with_type_state::<T>()
+    pub unsafe fn new(
+        archetype: &'a Archetype,
+        index: usize,
+        change_tick: u32,
+    ) -> Result<Self, MissingComponent> {
+        let (target, mutated_tick) = archetype
+            .get_component_with_mutated_tick::<T>(index)
             .ok_or_else(MissingComponent::new::<T>)?;
         Ok(Self {
-            value: &mut *target.as_ptr().add(index),
-            mutated: &mut *type_state.mutated().as_ptr().add(index),
+            value: &mut *target.as_ptr(),
+            mutated_tick: &mut *mutated_tick.as_ptr(),
+            change_tick,
         })
     }
 }
@@ -202,7 +340,7 @@ impl<'a, T: Component> Deref for Mut<'a, T> {
 impl<'a, T: Component> DerefMut for Mut<'a, T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut T {
-        *self.mutated = true;
+        *self.mutated_tick = self.change_tick;
         self.value
     }
 }
@@ -217,7 +355,7 @@ impl<'a, T: Component> Query for Mut<'a, T> {
     type Fetch = FetchMut<T>;
 }
 #[doc(hidden)]
-pub struct FetchMut<T>(NonNull<T>, NonNull<bool>);
+pub struct FetchMut<T>(ComponentCursor<T>, u32);
 
 impl<'a, T: Component> Fetch<'a> for FetchMut<T> {
     type Item = Mut<'a, T>;
@@ -234,30 +372,42 @@ impl<'a, T: Component> Fetch<'a> for FetchMut<T> {
         archetype.borrow_mut::<T>();
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        archetype
-            .get_with_type_state::<T>()
-            .map(|(components, type_state)| {
-                Self(
-                    NonNull::new_unchecked(components.as_ptr().add(offset)),
-                    NonNull::new_unchecked(type_state.mutated().as_ptr().add(offset)),
-                )
-            })
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        ComponentCursor::new(archetype, offset).map(|cursor| Self(cursor, ticks.change_tick))
     }
 
     fn release(archetype: &Archetype) {
         archetype.release_mut::<T>();
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        archetype.borrow_partition_mut::<T>(partition);
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        archetype.release_partition_mut::<T>(partition);
+    }
+
+    unsafe fn should_skip(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.0.advance();
+    }
+
     #[inline]
     unsafe fn next(&mut self) -> Mut<'a, T> {
-        let component = self.0.as_ptr();
-        let mutated = self.1.as_ptr();
-        self.0 = NonNull::new_unchecked(component.add(1));
-        self.1 = NonNull::new_unchecked(mutated.add(1));
+        let (value, _, mutated) = self.0.current();
+        self.0.advance();
         Mut {
-            value: &mut *component,
-            mutated: &mut *mutated,
+            value: &mut *value.as_ptr(),
+            mutated_tick: &mut *mutated.as_ptr(),
+            change_tick: self.1,
         }
     }
 }
@@ -285,8 +435,8 @@ macro_rules! impl_or_query {
                  )+
             }
 
-            unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-                Some(Self(( $( $T::get(archetype, offset)?),+ )))
+            unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+                Some(Self(( $( $T::get(archetype, offset, ticks)?),+ )))
             }
 
             fn release(archetype: &Archetype) {
@@ -295,16 +445,40 @@ macro_rules! impl_or_query {
                  )+
             }
 
+            fn borrow_partition(archetype: &Archetype, partition: Partition) {
+                $(
+                    $T::borrow_partition(archetype, partition);
+                 )+
+            }
+
+            fn release_partition(archetype: &Archetype, partition: Partition) {
+                $(
+                    $T::release_partition(archetype, partition);
+                 )+
+            }
+
             #[allow(non_snake_case)]
             unsafe fn next(&mut self) -> Self::Item {
                 let ($( $T ),+) = &mut self.0;
                 ($( $T.next() ),+)
             }
 
-             #[allow(non_snake_case)]
+            #[allow(non_snake_case)]
             unsafe fn should_skip(&self) -> bool {
                 let ($( $T ),+) = &self.0;
-                true $( && $T.should_skip() )+
+                (true $( && $T.should_skip() )+) || self.is_missing()
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn is_missing(&self) -> bool {
+                let ($( $T ),+) = &self.0;
+                false $( || $T.is_missing() )+
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn skip(&mut self) {
+                let ($( $T ),+) = &mut self.0;
+                $( $T.skip(); )+
             }
         }
     };
@@ -344,7 +518,7 @@ pub struct Or<T>(PhantomData<T>);
 #[doc(hidden)]
 pub struct FetchOr<T>(T);
 
-/// Query transformer that retrieves components of type `T` that have been mutated since the start of the frame.
+/// Query transformer that retrieves components of type `T` that have been mutated since the querying system last ran.
 /// Added components do not count as mutated.
 pub struct Mutated<'a, T> {
     value: &'a T,
@@ -364,7 +538,7 @@ impl<'a, T: Component> Query for Mutated<'a, T> {
 }
 
 #[doc(hidden)]
-pub struct FetchMutated<T>(NonNull<T>, NonNull<bool>);
+pub struct FetchMutated<T>(ComponentCursor<T>, ChangeTicks);
 
 impl<'a, T: Component> Fetch<'a> for FetchMutated<T> {
     type Item = Mutated<'a, T>;
@@ -381,36 +555,46 @@ impl<'a, T: Component> Fetch<'a> for FetchMutated<T> {
         archetype.borrow::<T>();
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        archetype
-            .get_with_type_state::<T>()
-            .map(|(components, type_state)| {
-                Self(
-                    NonNull::new_unchecked(components.as_ptr().add(offset)),
-                    NonNull::new_unchecked(type_state.mutated().as_ptr().add(offset)),
-                )
-            })
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        ComponentCursor::new(archetype, offset).map(|cursor| Self(cursor, ticks))
     }
 
     fn release(archetype: &Archetype) {
         archetype.release::<T>();
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        archetype.borrow_partition::<T>(partition);
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        archetype.release_partition::<T>(partition);
+    }
+
     unsafe fn should_skip(&self) -> bool {
-        // skip if the current item wasn't mutated
-        !*self.1.as_ref()
+        // skip if the current item is missing or wasn't mutated
+        self.0.is_missing() || !self.1.is_changed(*self.0.current().2.as_ref())
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.0.advance();
     }
 
     #[inline]
     unsafe fn next(&mut self) -> Self::Item {
-        self.1 = NonNull::new_unchecked(self.1.as_ptr().add(1));
-        let value = self.0.as_ptr();
-        self.0 = NonNull::new_unchecked(value.add(1));
-        Mutated { value: &*value }
+        let (value, _, _) = self.0.current();
+        self.0.advance();
+        Mutated {
+            value: &*value.as_ptr(),
+        }
     }
 }
 
-/// Query transformer that retrieves components of type `T` that have been added since the start of the frame.
+/// Query transformer that retrieves components of type `T` that have been added since the querying system last ran.
 pub struct Added<'a, T> {
     value: &'a T,
 }
@@ -429,7 +613,7 @@ impl<'a, T: Component> Query for Added<'a, T> {
 }
 
 #[doc(hidden)]
-pub struct FetchAdded<T>(NonNull<T>, NonNull<bool>);
+pub struct FetchAdded<T>(ComponentCursor<T>, ChangeTicks);
 unsafe impl<T> ReadOnlyFetch for FetchAdded<T> {}
 
 impl<'a, T: Component> Fetch<'a> for FetchAdded<T> {
@@ -447,36 +631,47 @@ impl<'a, T: Component> Fetch<'a> for FetchAdded<T> {
         archetype.borrow::<T>();
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        archetype
-            .get_with_type_state::<T>()
-            .map(|(components, type_state)| {
-                Self(
-                    NonNull::new_unchecked(components.as_ptr().add(offset)),
-                    NonNull::new_unchecked(type_state.added().as_ptr().add(offset)),
-                )
-            })
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        ComponentCursor::new(archetype, offset).map(|cursor| Self(cursor, ticks))
     }
 
     fn release(archetype: &Archetype) {
         archetype.release::<T>();
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        archetype.borrow_partition::<T>(partition);
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        archetype.release_partition::<T>(partition);
+    }
+
     unsafe fn should_skip(&self) -> bool {
-        // skip if the current item wasn't added
-        !*self.1.as_ref()
+        // skip if the current item is missing or wasn't added
+        self.0.is_missing() || !self.1.is_changed(*self.0.current().1.as_ref())
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.0.advance();
     }
 
     #[inline]
     unsafe fn next(&mut self) -> Self::Item {
-        self.1 = NonNull::new_unchecked(self.1.as_ptr().add(1));
-        let value = self.0.as_ptr();
-        self.0 = NonNull::new_unchecked(value.add(1));
-        Added { value: &*value }
+        let (value, _, _) = self.0.current();
+        self.0.advance();
+        Added {
+            value: &*value.as_ptr(),
+        }
     }
 }
 
-/// Query transformer that retrieves components of type `T` that have either been mutated or added since the start of the frame.
+/// Query transformer that retrieves components of type `T` that have either been mutated or added since the querying system last
+/// ran.
 pub struct Changed<'a, T> {
     value: &'a T,
 }
@@ -495,7 +690,7 @@ impl<'a, T: Component> Query for Changed<'a, T> {
 }
 
 #[doc(hidden)]
-pub struct FetchChanged<T>(NonNull<T>, NonNull<bool>, NonNull<bool>);
+pub struct FetchChanged<T>(ComponentCursor<T>, ChangeTicks);
 unsafe impl<T> ReadOnlyFetch for FetchChanged<T> {}
 
 impl<'a, T: Component> Fetch<'a> for FetchChanged<T> {
@@ -513,34 +708,46 @@ impl<'a, T: Component> Fetch<'a> for FetchChanged<T> {
         archetype.borrow::<T>();
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        archetype
-            .get_with_type_state::<T>()
-            .map(|(components, type_state)| {
-                Self(
-                    NonNull::new_unchecked(components.as_ptr().add(offset)),
-                    NonNull::new_unchecked(type_state.added().as_ptr().add(offset)),
-                    NonNull::new_unchecked(type_state.mutated().as_ptr().add(offset)),
-                )
-            })
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        ComponentCursor::new(archetype, offset).map(|cursor| Self(cursor, ticks))
     }
 
     fn release(archetype: &Archetype) {
         archetype.release::<T>();
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        archetype.borrow_partition::<T>(partition);
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        archetype.release_partition::<T>(partition);
+    }
+
     unsafe fn should_skip(&self) -> bool {
+        if self.0.is_missing() {
+            return true;
+        }
         // skip if the current item wasn't added or mutated
-        !*self.1.as_ref() && !self.2.as_ref()
+        let (_, added, mutated) = self.0.current();
+        !self.1.is_changed(*added.as_ref()) && !self.1.is_changed(*mutated.as_ref())
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.0.advance();
     }
 
     #[inline]
     unsafe fn next(&mut self) -> Self::Item {
-        self.1 = NonNull::new_unchecked(self.1.as_ptr().add(1));
-        self.2 = NonNull::new_unchecked(self.2.as_ptr().add(1));
-        let value = self.0.as_ptr();
-        self.0 = NonNull::new_unchecked(value.add(1));
-        Changed { value: &*value }
+        let (value, _, _) = self.0.current();
+        self.0.advance();
+        Changed {
+            value: &*value.as_ptr(),
+        }
     }
 }
 
@@ -559,26 +766,48 @@ impl<'a, T: Fetch<'a>> Fetch<'a> for TryFetch<T> {
         T::borrow(archetype)
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        Some(Self(T::get(archetype, offset)))
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        Some(Self(T::get(archetype, offset, ticks)))
     }
 
     fn release(archetype: &Archetype) {
         T::release(archetype)
     }
 
+    fn borrow_partition(archetype: &Archetype, partition: Partition) {
+        T::borrow_partition(archetype, partition)
+    }
+
+    fn release_partition(archetype: &Archetype, partition: Partition) {
+        T::release_partition(archetype, partition)
+    }
+
     unsafe fn next(&mut self) -> Option<T::Item> {
-        Some(self.0.as_mut()?.next())
+        let fetch = self.0.as_mut()?;
+        if fetch.is_missing() {
+            fetch.skip();
+            None
+        } else {
+            Some(fetch.next())
+        }
     }
 
     unsafe fn should_skip(&self) -> bool {
-        self.0.as_ref().map_or(false, |fetch| fetch.should_skip())
+        self.0
+            .as_ref()
+            .map_or(false, |fetch| !fetch.is_missing() && fetch.should_skip())
+    }
+
+    unsafe fn skip(&mut self) {
+        if let Some(fetch) = self.0.as_mut() {
+            fetch.skip();
+        }
     }
 }
 
 /// Query transformer skipping entities that have a `T` component
 ///
-/// See also `QueryBorrow::without`.
+/// See also `QueryBorrow::without`. `Without<T>` on its own is a [QueryFilter](crate::QueryFilter).
 ///
 /// # Example
 /// ```
@@ -593,14 +822,14 @@ impl<'a, T: Fetch<'a>> Fetch<'a> for TryFetch<T> {
 ///     .collect::<Vec<_>>();
 /// assert_eq!(entities, &[(c, 42)]);
 /// ```
-pub struct Without<T, Q>(PhantomData<(Q, fn(T))>);
+pub struct Without<T, Q = ()>(PhantomData<(Q, fn(T))>);
 
 impl<T: Component, Q: Query> Query for Without<T, Q> {
     type Fetch = FetchWithout<T, Q::Fetch>;
 }
 
 #[doc(hidden)]
-pub struct FetchWithout<T, F>(F, PhantomData<fn(T)>);
+pub struct FetchWithout<T, F>(F, Option<ComponentCursor<T>>);
 unsafe impl<'a, T: Component, F: Fetch<'a>> ReadOnlyFetch for FetchWithout<T, F> where
     F: ReadOnlyFetch
 {
@@ -610,7 +839,8 @@ impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWithout<T, F> {
     type Item = F::Item;
 
     fn access(archetype: &Archetype) -> Option<Access> {
-        if archetype.has::<T>() {
+        // entities in archetypes with a sparse set for `T` are checked one by one
+        if archetype.has_table_type(TypeId::of::<T>()) {
             None
         } else {
             F::access(archetype)
@@ -618,32 +848,56 @@ impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWithout<T, F> {
     }
 
     fn borrow(archetype: &Archetype) {
-        F::borrow(archetype)
+        match Partition::of::<T>(archetype, false) {
+            Some(partition) => F::borrow_partition(archetype, partition),
+            None => F::borrow(archetype),
+        }
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        if archetype.has::<T>() {
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        if archetype.has_table_type(TypeId::of::<T>()) {
             return None;
         }
-        Some(Self(F::get(archetype, offset)?, PhantomData))
+        Some(Self(
+            F::get(archetype, offset, ticks)?,
+            ComponentCursor::new(archetype, offset),
+        ))
     }
 
     fn release(archetype: &Archetype) {
-        F::release(archetype)
+        match Partition::of::<T>(archetype, false) {
+            Some(partition) => F::release_partition(archetype, partition),
+            None => F::release(archetype),
+        }
     }
 
     unsafe fn next(&mut self) -> F::Item {
+        if let Some(cursor) = self.1.as_mut() {
+            cursor.advance();
+        }
         self.0.next()
     }
 
     unsafe fn should_skip(&self) -> bool {
-        self.0.should_skip()
+        self.is_missing() || self.0.should_skip()
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        let has_component = self.1.as_ref().map_or(false, |cursor| !cursor.is_missing());
+        has_component || self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        if let Some(cursor) = self.1.as_mut() {
+            cursor.advance();
+        }
+        self.0.skip()
     }
 }
 
 /// Query transformer skipping entities that do not have a `T` component
 ///
-/// See also `QueryBorrow::with`.
+/// See also `QueryBorrow::with`. `With<T>` on its own is a [QueryFilter](crate::QueryFilter).
 ///
 /// # Example
 /// ```
@@ -660,14 +914,14 @@ impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWithout<T, F> {
 /// assert!(entities.contains(&(a, 123)));
 /// assert!(entities.contains(&(b, 456)));
 /// ```
-pub struct With<T, Q>(PhantomData<(Q, fn(T))>);
+pub struct With<T, Q = ()>(PhantomData<(Q, fn(T))>);
 
 impl<T: Component, Q: Query> Query for With<T, Q> {
     type Fetch = FetchWith<T, Q::Fetch>;
 }
 
 #[doc(hidden)]
-pub struct FetchWith<T, F>(F, PhantomData<fn(T)>);
+pub struct FetchWith<T, F>(F, ComponentCursor<T>);
 unsafe impl<'a, T: Component, F: Fetch<'a>> ReadOnlyFetch for FetchWith<T, F> where F: ReadOnlyFetch {}
 
 impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWith<T, F> {
@@ -682,26 +936,40 @@ impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWith<T, F> {
     }
 
     fn borrow(archetype: &Archetype) {
-        F::borrow(archetype)
+        match Partition::of::<T>(archetype, true) {
+            Some(partition) => F::borrow_partition(archetype, partition),
+            None => F::borrow(archetype),
+        }
     }
 
-    unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-        if !archetype.has::<T>() {
-            return None;
-        }
-        Some(Self(F::get(archetype, offset)?, PhantomData))
+    unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+        let cursor = ComponentCursor::new(archetype, offset)?;
+        Some(Self(F::get(archetype, offset, ticks)?, cursor))
     }
 
     fn release(archetype: &Archetype) {
-        F::release(archetype)
+        match Partition::of::<T>(archetype, true) {
+            Some(partition) => F::release_partition(archetype, partition),
+            None => F::release(archetype),
+        }
     }
 
     unsafe fn next(&mut self) -> F::Item {
+        self.1.advance();
         self.0.next()
     }
 
     unsafe fn should_skip(&self) -> bool {
-        self.0.should_skip()
+        self.1.is_missing() || self.0.should_skip()
+    }
+
+    unsafe fn is_missing(&self) -> bool {
+        self.1.is_missing() || self.0.is_missing()
+    }
+
+    unsafe fn skip(&mut self) {
+        self.1.advance();
+        self.0.skip()
     }
 }
 
@@ -710,14 +978,16 @@ impl<'a, T: Component, F: Fetch<'a>> Fetch<'a> for FetchWith<T, F> {
 /// Note that borrows are not released until this object is dropped.
 pub struct QueryBorrow<'w, Q: Query> {
     archetypes: &'w [Archetype],
+    ticks: ChangeTicks,
     borrowed: bool,
     _marker: PhantomData<Q>,
 }
 
 impl<'w, Q: Query> QueryBorrow<'w, Q> {
-    pub(crate) fn new(archetypes: &'w [Archetype]) -> Self {
+    pub(crate) fn new(archetypes: &'w [Archetype], ticks: ChangeTicks) -> Self {
         Self {
             archetypes,
+            ticks,
             borrowed: false,
             _marker: PhantomData,
         }
@@ -810,6 +1080,7 @@ impl<'w, Q: Query> QueryBorrow<'w, Q> {
     fn transform<R: Query>(mut self) -> QueryBorrow<'w, R> {
         let borrow = QueryBorrow {
             archetypes: self.archetypes,
+            ticks: self.ticks,
             borrowed: self.borrowed,
             _marker: PhantomData,
         };
@@ -852,10 +1123,11 @@ impl<'q, 'w, Q: Query> Iterator for QueryIter<'q, 'w, Q> {
                     let archetype = self.borrow.archetypes.get(self.archetype_index)?;
                     self.archetype_index += 1;
                     unsafe {
-                        self.iter = Q::Fetch::get(archetype, 0).map(|fetch| ChunkIter {
-                            fetch,
-                            len: archetype.len(),
-                        });
+                        self.iter =
+                            Q::Fetch::get(archetype, 0, self.borrow.ticks).map(|fetch| ChunkIter {
+                                fetch,
+                                len: archetype.len(),
+                            });
                     }
                 }
                 Some(ref mut iter) => match unsafe { iter.next() } {
@@ -903,7 +1175,7 @@ impl<Q: Query> ChunkIter<Q> {
             self.len -= 1;
             if self.fetch.should_skip() {
                 // we still need to progress the iterator
-                let _ = self.fetch.next();
+                self.fetch.skip();
                 continue;
             }
 
@@ -935,7 +1207,7 @@ impl<'q, 'w, Q: Query> Iterator for BatchedIter<'q, 'w, Q> {
                 self.batch = 0;
                 continue;
             }
-            if let Some(fetch) = unsafe { Q::Fetch::get(archetype, offset) } {
+            if let Some(fetch) = unsafe { Q::Fetch::get(archetype, offset, self.borrow.ticks) } {
                 self.batch += 1;
                 return Some(Batch {
                     _marker: PhantomData,
@@ -993,13 +1265,21 @@ macro_rules! tuple_impl {
                 $($name::borrow(archetype);)*
             }
             #[allow(unused_variables)]
-            unsafe fn get(archetype: &'a Archetype, offset: usize) -> Option<Self> {
-                Some(($($name::get(archetype, offset)?,)*))
+            unsafe fn get(archetype: &'a Archetype, offset: usize, ticks: ChangeTicks) -> Option<Self> {
+                Some(($($name::get(archetype, offset, ticks)?,)*))
             }
             #[allow(unused_variables)]
             fn release(archetype: &Archetype) {
                 $($name::release(archetype);)*
             }
+            #[allow(unused_variables)]
+            fn borrow_partition(archetype: &Archetype, partition: Partition) {
+                $($name::borrow_partition(archetype, partition);)*
+            }
+            #[allow(unused_variables)]
+            fn release_partition(archetype: &Archetype, partition: Partition) {
+                $($name::release_partition(archetype, partition);)*
+            }
 
             #[allow(unused_variables)]
             unsafe fn next(&mut self) -> Self::Item {
@@ -1013,6 +1293,19 @@ macro_rules! tuple_impl {
                 let ($($name,)*) = self;
                 $($name.should_skip()||)* false
             }
+
+            unsafe fn is_missing(&self) -> bool {
+                #[allow(non_snake_case)]
+                let ($($name,)*) = self;
+                $($name.is_missing()||)* false
+            }
+
+            #[allow(unused_variables)]
+            unsafe fn skip(&mut self) {
+                #[allow(non_snake_case)]
+                let ($($name,)*) = self;
+                $($name.skip();)*
+            }
         }
 
         impl<$($name: Query),*> Query for ($($name,)*) {
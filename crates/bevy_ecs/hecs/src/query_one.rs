@@ -4,13 +4,14 @@ use core::marker::PhantomData;
 
 use crate::{
     query::{Fetch, ReadOnlyFetch, With, Without},
-    Archetype, Component, Query,
+    Archetype, ChangeTicks, Component, Query,
 };
 
 /// A borrow of a `World` sufficient to execute the query `Q` on a single entity
 pub struct QueryOne<'a, Q: Query> {
     archetype: &'a Archetype,
     index: usize,
+    ticks: ChangeTicks,
     _marker: PhantomData<Q>,
 }
 
@@ -20,10 +21,11 @@ impl<'a, Q: Query> QueryOne<'a, Q> {
     /// # Safety
     ///
     /// `index` must be in-bounds for `archetype`
-    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize) -> Self {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize, ticks: ChangeTicks) -> Self {
         Self {
             archetype,
             index,
+            ticks,
             _marker: PhantomData,
         }
     }
@@ -36,7 +38,10 @@ impl<'a, Q: Query> QueryOne<'a, Q> {
     /// pre-existing borrow.
     pub fn get(&mut self) -> Option<<Q::Fetch as Fetch<'_>>::Item> {
         unsafe {
-            let mut fetch = Q::Fetch::get(self.archetype, self.index)?;
+            let mut fetch = Q::Fetch::get(self.archetype, self.index, self.ticks)?;
+            if fetch.is_missing() {
+                return None;
+            }
             Some(fetch.next())
         }
     }
@@ -60,6 +65,7 @@ impl<'a, Q: Query> QueryOne<'a, Q> {
         QueryOne {
             archetype: self.archetype,
             index: self.index,
+            ticks: self.ticks,
             _marker: PhantomData,
         }
     }
@@ -72,6 +78,7 @@ unsafe impl<Q: Query> Sync for QueryOne<'_, Q> {}
 pub struct ReadOnlyQueryOne<'a, Q: Query> {
     archetype: &'a Archetype,
     index: usize,
+    ticks: ChangeTicks,
     _marker: PhantomData<Q>,
 }
 
@@ -84,10 +91,11 @@ where
     /// # Safety
     ///
     /// `index` must be in-bounds for `archetype`
-    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize) -> Self {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize, ticks: ChangeTicks) -> Self {
         Self {
             archetype,
             index,
+            ticks,
             _marker: PhantomData,
         }
     }
@@ -103,7 +111,10 @@ where
         Q::Fetch: ReadOnlyFetch,
     {
         unsafe {
-            let mut fetch = Q::Fetch::get(self.archetype, self.index)?;
+            let mut fetch = Q::Fetch::get(self.archetype, self.index, self.ticks)?;
+            if fetch.is_missing() {
+                return None;
+            }
             Some(fetch.next())
         }
     }
@@ -127,6 +138,7 @@ where
         QueryOne {
             archetype: self.archetype,
             index: self.index,
+            ticks: self.ticks,
             _marker: PhantomData,
         }
     }
@@ -0,0 +1,313 @@
+// modified by Bevy contributors
+
+use crate::{
+    alloc::{
+        alloc::{alloc, dealloc, Layout},
+        vec::Vec,
+    },
+    archetype::TypeInfo,
+    borrow::AtomicBorrow,
+    change_detection::{check_tick, MAX_CHANGE_AGE},
+};
+use core::{
+    fmt,
+    ptr::{self, NonNull},
+};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+const EMPTY: u32 = u32::MAX;
+
+/// How the components of a type are stored
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StorageType {
+    /// Components are stored in the tables of the archetypes their entities belong to. This is the default, and
+    /// makes iterating over components fast.
+    Table,
+    /// Components are stored in a sparse set next to the archetype's table. They aren't part of the archetype of
+    /// their entity, so adding and removing them doesn't move the entity to another archetype, at the cost of
+    /// slower iteration. This suits small components that are frequently added and removed, like markers.
+    SparseSet,
+}
+
+impl Default for StorageType {
+    fn default() -> Self {
+        StorageType::Table
+    }
+}
+
+/// Error indicating that the storage type of a component type can't change because its components were already added
+/// to the world
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StorageTypeError {
+    /// The name of the component type
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for StorageTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "can't change the storage type of {} after it was added to the world",
+            self.type_name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for StorageTypeError {}
+
+/// The sparse set components of one type in an archetype. The set maps archetype rows to densely packed components.
+pub(crate) struct SparseSet {
+    info: TypeInfo,
+    /// The dense index of the component of each archetype row, or `EMPTY`
+    sparse: Vec<u32>,
+    /// The archetype row of each component
+    rows: Vec<usize>,
+    data: NonNull<u8>,
+    capacity: usize,
+    added_ticks: Vec<u32>,
+    mutated_ticks: Vec<u32>,
+    pub(crate) borrow: AtomicBorrow,
+}
+
+impl SparseSet {
+    pub(crate) fn new(info: TypeInfo) -> Self {
+        Self {
+            info,
+            sparse: Vec::new(),
+            rows: Vec::new(),
+            // zero sized components are never allocated, but their pointers still need to be aligned
+            data: NonNull::new(info.layout().align() as *mut u8).unwrap(),
+            capacity: 0,
+            added_ticks: Vec::new(),
+            mutated_ticks: Vec::new(),
+            borrow: AtomicBorrow::new(),
+        }
+    }
+
+    pub(crate) fn type_info(&self) -> &TypeInfo {
+        &self.info
+    }
+
+    #[inline]
+    fn dense_index(&self, row: usize) -> Option<usize> {
+        match self.sparse.get(row) {
+            Some(&dense) if dense != EMPTY => Some(dense as usize),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    unsafe fn ptr(&self, dense: usize) -> NonNull<u8> {
+        NonNull::new_unchecked(self.data.as_ptr().add(dense * self.info.layout().size()))
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, row: usize) -> bool {
+        self.dense_index(row).is_some()
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, row: usize) -> Option<NonNull<u8>> {
+        self.dense_index(row)
+            .map(|dense| unsafe { self.ptr(dense) })
+    }
+
+    /// Returns the component of `row` along with pointers to its added and mutated ticks
+    #[inline]
+    pub(crate) fn get_with_ticks(
+        &self,
+        row: usize,
+    ) -> Option<(NonNull<u8>, NonNull<u32>, NonNull<u32>)> {
+        let dense = self.dense_index(row)?;
+        unsafe {
+            Some((
+                self.ptr(dense),
+                NonNull::new_unchecked(self.added_ticks.as_ptr().add(dense) as *mut u32),
+                NonNull::new_unchecked(self.mutated_ticks.as_ptr().add(dense) as *mut u32),
+            ))
+        }
+    }
+
+    /// Moves the component `component` points to into the set. An existing component of `row` is dropped and
+    /// replaced, which counts as a mutation.
+    ///
+    /// # Safety
+    /// `component` must point to a valid component of the set's type, which must not be used afterwards
+    pub(crate) unsafe fn insert(&mut self, row: usize, component: *mut u8, change_tick: u32) {
+        if let Some(dense) = self.dense_index(row) {
+            let ptr = self.ptr(dense).as_ptr();
+            self.info.drop(ptr);
+            ptr::copy_nonoverlapping(component, ptr, self.info.layout().size());
+            self.mutated_ticks[dense] = change_tick;
+        } else {
+            // added components don't count as mutated
+            self.push(
+                row,
+                component,
+                change_tick,
+                change_tick.wrapping_sub(MAX_CHANGE_AGE),
+            );
+        }
+    }
+
+    /// Moves the component `component` points to into the set, keeping the given ticks
+    ///
+    /// # Safety
+    /// `component` must point to a valid component of the set's type, which must not be used afterwards, and `row`
+    /// must not have a component yet
+    pub(crate) unsafe fn push(
+        &mut self,
+        row: usize,
+        component: *mut u8,
+        added_tick: u32,
+        mutated_tick: u32,
+    ) {
+        debug_assert!(!self.contains(row));
+        let dense = self.rows.len();
+        if dense == self.capacity {
+            self.grow();
+        }
+        ptr::copy_nonoverlapping(
+            component,
+            self.ptr(dense).as_ptr(),
+            self.info.layout().size(),
+        );
+        self.rows.push(row);
+        self.added_ticks.push(added_tick);
+        self.mutated_ticks.push(mutated_tick);
+        if self.sparse.len() <= row {
+            self.sparse.resize(row + 1, EMPTY);
+        }
+        self.sparse[row] = dense as u32;
+    }
+
+    /// Removes the component of `row` from the set without dropping it. `f` receives the component and its added
+    /// and mutated ticks, and takes ownership of the component. Returns false if `row` has no component.
+    ///
+    /// # Safety
+    /// The set doesn't drop the component, so `f` must move it out of the pointer it receives or drop it, unless the
+    /// component was already moved out. The pointer must not be used after `f` returns, as the set reuses its memory.
+    pub(crate) unsafe fn take(&mut self, row: usize, f: impl FnOnce(*mut u8, u32, u32)) -> bool {
+        let dense = match self.dense_index(row) {
+            Some(dense) => dense,
+            None => return false,
+        };
+        f(
+            self.ptr(dense).as_ptr(),
+            self.added_ticks[dense],
+            self.mutated_ticks[dense],
+        );
+        self.swap_remove(dense);
+        true
+    }
+
+    /// Removes and drops the component of `row`. Returns false if `row` has no component.
+    pub(crate) fn remove(&mut self, row: usize) -> bool {
+        let info = self.info;
+        unsafe { self.take(row, |ptr, _, _| info.drop(ptr)) }
+    }
+
+    unsafe fn swap_remove(&mut self, dense: usize) {
+        let last = self.rows.len() - 1;
+        self.sparse[self.rows[dense]] = EMPTY;
+        if dense != last {
+            ptr::copy_nonoverlapping(
+                self.ptr(last).as_ptr(),
+                self.ptr(dense).as_ptr(),
+                self.info.layout().size(),
+            );
+            self.sparse[self.rows[last]] = dense as u32;
+        }
+        self.rows.swap_remove(dense);
+        self.added_ticks.swap_remove(dense);
+        self.mutated_ticks.swap_remove(dense);
+    }
+
+    /// Updates the set after the archetype moved the entity in row `from` to row `to`, which must not have a
+    /// component
+    pub(crate) fn move_row(&mut self, from: usize, to: usize) {
+        if let Some(dense) = self.dense_index(from) {
+            debug_assert!(!self.contains(to));
+            self.sparse[from] = EMPTY;
+            if self.sparse.len() <= to {
+                self.sparse.resize(to + 1, EMPTY);
+            }
+            self.sparse[to] = dense as u32;
+            self.rows[dense] = to;
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for dense in 0..self.rows.len() {
+            unsafe {
+                self.info.drop(self.ptr(dense).as_ptr());
+            }
+        }
+        self.sparse.clear();
+        self.rows.clear();
+        self.added_ticks.clear();
+        self.mutated_ticks.clear();
+    }
+
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
+        for tick in self.mutated_ticks.iter_mut() {
+            check_tick(tick, change_tick);
+        }
+
+        for tick in self.added_ticks.iter_mut() {
+            check_tick(tick, change_tick);
+        }
+    }
+
+    fn grow(&mut self) {
+        let new_capacity = (self.capacity * 2).max(4);
+        let layout = self.info.layout();
+        if layout.size() != 0 {
+            unsafe {
+                let new_data = NonNull::new(alloc(
+                    Layout::from_size_align(layout.size() * new_capacity, layout.align()).unwrap(),
+                ))
+                .unwrap();
+                if self.capacity != 0 {
+                    ptr::copy_nonoverlapping(
+                        self.data.as_ptr(),
+                        new_data.as_ptr(),
+                        layout.size() * self.rows.len(),
+                    );
+                    dealloc(
+                        self.data.as_ptr(),
+                        Layout::from_size_align_unchecked(
+                            layout.size() * self.capacity,
+                            layout.align(),
+                        ),
+                    );
+                }
+                self.data = new_data;
+            }
+        }
+        self.capacity = new_capacity;
+    }
+}
+
+impl Drop for SparseSet {
+    fn drop(&mut self) {
+        self.clear();
+        let layout = self.info.layout();
+        if layout.size() != 0 && self.capacity != 0 {
+            unsafe {
+                dealloc(
+                    self.data.as_ptr(),
+                    Layout::from_size_align_unchecked(
+                        layout.size() * self.capacity,
+                        layout.align(),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+unsafe impl Send for SparseSet {}
+unsafe impl Sync for SparseSet {}
@@ -16,10 +16,15 @@
 
 use crate::{
     alloc::vec::Vec, borrow::EntityRef, query::ReadOnlyFetch, query_one::ReadOnlyQueryOne,
-    EntityReserver, Mut, RefMut,
+    ChangeTicks, EntityReserver, Mut, RefMut, StorageType, StorageTypeError, TypeInfo,
+    CHECK_TICK_THRESHOLD,
 };
 use bevy_utils::{HashMap, HashSet};
-use core::{any::TypeId, fmt, mem, ptr};
+use core::{
+    any::TypeId,
+    fmt, ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 #[cfg(feature = "std")]
 use std::error::Error;
@@ -38,13 +43,23 @@ use crate::{
 ///
 /// The components of entities who have the same set of component types are stored in contiguous
 /// runs, allowing for extremely fast, cache-friendly iteration.
+///
+/// Changes to components are tracked with a change tick, which is incremented every time a system runs. Components
+/// store the tick they were added and last mutated at, so a system can detect the changes made since its last run.
+///
+/// Component types can be stored in sparse sets instead with [World::set_storage_type]. Sparse set components aren't
+/// part of their entity's archetype, so adding and removing them doesn't move the entity's other components.
 pub struct World {
     entities: Entities,
     index: HashMap<Vec<TypeId>, u32>,
-    removed_components: HashMap<TypeId, Vec<Entity>>,
+    sparse_types: HashSet<TypeId>,
+    removed_components: HashMap<TypeId, Removals>,
     #[allow(missing_docs)]
     pub archetypes: Vec<Archetype>,
     archetype_generation: u64,
+    change_tick: AtomicU32,
+    last_change_tick: u32,
+    last_check_tick: u32,
 }
 
 impl World {
@@ -58,9 +73,13 @@ impl World {
         Self {
             entities: Entities::default(),
             index,
+            sparse_types: HashSet::default(),
             archetypes,
             archetype_generation: 0,
             removed_components: HashMap::default(),
+            change_tick: AtomicU32::new(1),
+            last_change_tick: 0,
+            last_check_tick: 0,
         }
     }
 
@@ -88,21 +107,26 @@ impl World {
         self.flush();
 
         let entity = self.entities.alloc();
-        let archetype_id = components.with_ids(|ids| {
-            self.index.get(ids).copied().unwrap_or_else(|| {
-                let x = self.archetypes.len() as u32;
-                self.archetypes.push(Archetype::new(components.type_info()));
-                self.index.insert(ids.to_vec(), x);
-                self.archetype_generation += 1;
-                x
+        let archetype_id = if components.with_ids(|ids| self.has_sparse_type(ids)) {
+            self.get_or_insert_archetype(components.type_info(), &[])
+        } else {
+            components.with_ids(|ids| {
+                self.index.get(ids).copied().unwrap_or_else(|| {
+                    let x = self.archetypes.len() as u32;
+                    self.archetypes.push(Archetype::new(components.type_info()));
+                    self.index.insert(ids.to_vec(), x);
+                    self.archetype_generation += 1;
+                    x
+                })
             })
-        });
+        };
 
+        let change_tick = self.change_tick();
         let archetype = &mut self.archetypes[archetype_id as usize];
         unsafe {
             let index = archetype.allocate(entity);
             components.put(|ptr, ty, size| {
-                archetype.put_dynamic(ptr, ty, size, index, true);
+                archetype.put_new(ptr, ty, size, index, change_tick);
                 true
             });
             self.entities.meta[entity.id as usize].location = Location {
@@ -145,6 +169,7 @@ impl World {
             entities: &mut self.entities,
             archetype_id,
             archetype: &mut self.archetypes[archetype_id as usize],
+            change_tick: *self.change_tick.get_mut(),
         }
     }
 
@@ -152,18 +177,21 @@ impl World {
     pub fn despawn(&mut self, entity: Entity) -> Result<(), NoSuchEntity> {
         self.flush();
 
+        let change_tick = self.change_tick();
         let loc = self.entities.free(entity)?;
         let archetype = &mut self.archetypes[loc.archetype as usize];
+        for ty in archetype.types().iter().chain(archetype.sparse_set_types()) {
+            if !archetype.has_component(ty.id(), loc.index) {
+                continue;
+            }
+            self.removed_components
+                .entry(ty.id())
+                .or_default()
+                .push(entity, change_tick);
+        }
         if let Some(moved) = unsafe { archetype.remove(loc.index) } {
             self.entities.get_mut(moved).unwrap().index = loc.index;
         }
-        for ty in archetype.types() {
-            let removed_entities = self
-                .removed_components
-                .entry(ty.id())
-                .or_insert_with(Vec::new);
-            removed_entities.push(entity);
-        }
         Ok(())
     }
 
@@ -181,15 +209,19 @@ impl World {
         self.flush();
         self.entities.reserve(additional);
 
-        let archetype_id = T::with_static_ids(|ids| {
-            self.index.get(ids).copied().unwrap_or_else(|| {
-                let x = self.archetypes.len() as u32;
-                self.archetypes.push(Archetype::new(T::static_type_info()));
-                self.index.insert(ids.to_vec(), x);
-                self.archetype_generation += 1;
-                x
+        let archetype_id = if T::with_static_ids(|ids| self.has_sparse_type(ids)) {
+            self.get_or_insert_archetype(T::static_type_info(), &[])
+        } else {
+            T::with_static_ids(|ids| {
+                self.index.get(ids).copied().unwrap_or_else(|| {
+                    let x = self.archetypes.len() as u32;
+                    self.archetypes.push(Archetype::new(T::static_type_info()));
+                    self.index.insert(ids.to_vec(), x);
+                    self.archetype_generation += 1;
+                    x
+                })
             })
-        });
+        };
 
         self.archetypes[archetype_id as usize].reserve(additional as usize);
         archetype_id
@@ -199,13 +231,15 @@ impl World {
     ///
     /// Preserves allocated storage for reuse.
     pub fn clear(&mut self) {
+        let change_tick = self.change_tick();
         for archetype in &mut self.archetypes {
-            for ty in archetype.types() {
-                let removed_entities = self
-                    .removed_components
-                    .entry(ty.id())
-                    .or_insert_with(Vec::new);
-                removed_entities.extend(archetype.iter_entities().copied());
+            for ty in archetype.types().iter().chain(archetype.sparse_set_types()) {
+                let removals = self.removed_components.entry(ty.id()).or_default();
+                for (index, entity) in archetype.iter_entities().enumerate() {
+                    if archetype.has_component(ty.id(), index) {
+                        removals.push(*entity, change_tick);
+                    }
+                }
             }
             archetype.clear();
         }
@@ -220,11 +254,98 @@ impl World {
     /// Returns true if the given entity has a component with the given type id.
     pub fn has_component_type(&self, entity: Entity, ty: TypeId) -> bool {
         self.get_entity_location(entity)
-            .map(|location| &self.archetypes[location.archetype as usize])
-            .map(|archetype| archetype.has_type(ty))
+            .map(|location| (&self.archetypes[location.archetype as usize], location))
+            .map(|(archetype, location)| archetype.has_component(ty, location.index))
             .unwrap_or(false)
     }
 
+    /// Sets how components of type `T` are stored. See [StorageType]. Fails if `T` components were already added to
+    /// the world with another storage type.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_hecs::*;
+    /// struct Selected;
+    ///
+    /// let mut world = World::new();
+    /// world.set_storage_type::<Selected>(StorageType::SparseSet).unwrap();
+    /// let e = world.spawn((123,));
+    /// let archetypes = world.archetypes().len();
+    /// world.insert_one(e, Selected).unwrap();
+    /// world.remove_one::<Selected>(e).unwrap();
+    /// assert_eq!(world.archetypes().len(), archetypes);
+    /// assert!(world.set_storage_type::<Selected>(StorageType::Table).is_err());
+    /// ```
+    pub fn set_storage_type<T: Component>(
+        &mut self,
+        storage_type: StorageType,
+    ) -> Result<(), StorageTypeError> {
+        let ty = TypeId::of::<T>();
+        if self.storage_type::<T>() == storage_type {
+            return Ok(());
+        }
+        if self
+            .archetypes
+            .iter()
+            .any(|archetype| archetype.has_type(ty))
+        {
+            return Err(StorageTypeError {
+                type_name: core::any::type_name::<T>(),
+            });
+        }
+        match storage_type {
+            StorageType::Table => self.sparse_types.remove(&ty),
+            StorageType::SparseSet => self.sparse_types.insert(ty),
+        };
+        Ok(())
+    }
+
+    /// How components of type `T` are stored
+    pub fn storage_type<T: Component>(&self) -> StorageType {
+        if self.sparse_types.contains(&TypeId::of::<T>()) {
+            StorageType::SparseSet
+        } else {
+            StorageType::Table
+        }
+    }
+
+    fn has_sparse_type(&self, ids: &[TypeId]) -> bool {
+        !self.sparse_types.is_empty() && ids.iter().any(|id| self.sparse_types.contains(id))
+    }
+
+    /// Finds or creates the archetype for the table components in `info`, and makes sure it has a sparse set for
+    /// every sparse set component in `info` and `sparse_info`
+    fn get_or_insert_archetype(&mut self, info: Vec<TypeInfo>, sparse_info: &[TypeInfo]) -> u32 {
+        use std::collections::hash_map::Entry;
+
+        let (mut table_info, mut sparse_info_all): (Vec<_>, Vec<_>) = info
+            .into_iter()
+            .partition(|ty| !self.sparse_types.contains(&ty.id()));
+        sparse_info_all.extend_from_slice(sparse_info);
+        table_info.sort();
+
+        let elements = table_info.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let archetype_id = match self.index.entry(elements) {
+            Entry::Occupied(x) => *x.get(),
+            Entry::Vacant(x) => {
+                let index = self.archetypes.len() as u32;
+                self.archetypes.push(Archetype::new(table_info));
+                x.insert(index);
+                self.archetype_generation += 1;
+                index
+            }
+        };
+
+        let archetype = &mut self.archetypes[archetype_id as usize];
+        for ty in sparse_info_all {
+            if archetype.add_sparse_set(ty) {
+                // systems need to recompute their archetype access
+                self.archetype_generation += 1;
+            }
+        }
+        archetype_id
+    }
+
     /// Efficiently iterate over all entities that have certain components
     ///
     /// Calling `iter` on the returned value yields `(Entity, Q)` tuples, where `Q` is some query
@@ -327,7 +448,20 @@ impl World {
     /// assert!(entities.contains(&(b, 456, false)));
     /// ```
     pub unsafe fn query_unchecked<Q: Query>(&self) -> QueryBorrow<'_, Q> {
-        QueryBorrow::new(&self.archetypes)
+        self.query_unchecked_with_ticks(self.change_ticks())
+    }
+
+    /// Like `query_unchecked`, but detects changes relative to the given `ticks` instead of the ticks of the last
+    /// `clear_trackers` call. Systems use this to detect the changes made since they last ran.
+    ///
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    pub unsafe fn query_unchecked_with_ticks<Q: Query>(
+        &self,
+        ticks: ChangeTicks,
+    ) -> QueryBorrow<'_, Q> {
+        QueryBorrow::new(&self.archetypes, ticks)
     }
 
     /// Prepare a read only query against a single entity
@@ -354,7 +488,13 @@ impl World {
         Q::Fetch: ReadOnlyFetch,
     {
         let loc = self.entities.get(entity)?;
-        Ok(unsafe { ReadOnlyQueryOne::new(&self.archetypes[loc.archetype as usize], loc.index) })
+        Ok(unsafe {
+            ReadOnlyQueryOne::new(
+                &self.archetypes[loc.archetype as usize],
+                loc.index,
+                self.change_ticks(),
+            )
+        })
     }
 
     /// Prepare a query against a single entity
@@ -411,6 +551,7 @@ impl World {
         Ok(QueryOne::new(
             &self.archetypes[loc.archetype as usize],
             loc.index,
+            self.change_ticks(),
         ))
     }
 
@@ -422,10 +563,9 @@ impl World {
                 return Err(MissingComponent::new::<T>().into());
             }
             Ok(&*self.archetypes[loc.archetype as usize]
-                .get::<T>()
+                .get_component::<T>(loc.index)
                 .ok_or_else(MissingComponent::new::<T>)?
-                .as_ptr()
-                .add(loc.index as usize))
+                .as_ptr())
         }
     }
 
@@ -441,7 +581,13 @@ impl World {
     pub fn entity(&mut self, entity: Entity) -> Result<EntityRef<'_>, NoSuchEntity> {
         Ok(match self.entities.get(entity)? {
             Location { archetype: 0, .. } => EntityRef::empty(),
-            loc => unsafe { EntityRef::new(&self.archetypes[loc.archetype as usize], loc.index) },
+            loc => unsafe {
+                EntityRef::new(
+                    &self.archetypes[loc.archetype as usize],
+                    loc.index,
+                    self.change_tick(),
+                )
+            },
         })
     }
 
@@ -461,6 +607,7 @@ impl World {
         Ok(Mut::new(
             &self.archetypes[loc.archetype as usize],
             loc.index,
+            self.change_tick(),
         )?)
     }
 
@@ -481,14 +628,23 @@ impl World {
     /// assert!(ids.contains(&b));
     /// ```
     pub fn iter(&mut self) -> Iter<'_> {
-        Iter::new(&self.archetypes, &self.entities)
+        Iter::new(&self.archetypes, &self.entities, self.change_tick())
     }
 
-    #[allow(missing_docs)]
+    /// The entities whose `C` component was removed since the last `clear_trackers` call, including despawned
+    /// entities that had a `C` component
     pub fn removed<C: Component>(&self) -> &[Entity] {
         self.removed_components
             .get(&TypeId::of::<C>())
-            .map_or(&[], |entities| entities.as_slice())
+            .map_or(&[], |removals| &removals.entities[removals.current..])
+    }
+
+    /// The entities whose `C` component was removed since the second to last `clear_trackers` call, along with the
+    /// change tick of each removal. Systems use the ticks to find the removals made since they last ran.
+    pub fn removed_with_ticks<C: Component>(&self) -> (&[Entity], &[u32]) {
+        self.removed_components
+            .get(&TypeId::of::<C>())
+            .map_or((&[], &[]), |removals| (&removals.entities, &removals.ticks))
     }
 
     /// Add `components` to `entity`
@@ -512,14 +668,19 @@ impl World {
         entity: Entity,
         components: impl DynamicBundle,
     ) -> Result<(), NoSuchEntity> {
-        use std::collections::hash_map::Entry;
-
         self.flush();
-        let loc = self.entities.get_mut(entity)?;
+        let change_tick = self.change_tick();
+        let loc = *self.entities.get_mut(entity)?;
         unsafe {
             // Assemble Vec<TypeInfo> for the final entity
             let arch = &mut self.archetypes[loc.archetype as usize];
             let mut info = arch.types().to_vec();
+            // sparse set components are replaced in place, but the target archetype needs a sparse set for them
+            let sparse_info = arch
+                .sparse_set_types()
+                .filter(|ty| arch.has_component(ty.id(), loc.index))
+                .copied()
+                .collect::<Vec<_>>();
             for ty in components.type_info() {
                 if let Some(ptr) = arch.get_dynamic(ty.id(), ty.layout().size(), loc.index) {
                     ty.drop(ptr.as_ptr());
@@ -527,26 +688,19 @@ impl World {
                     info.push(ty);
                 }
             }
-            info.sort();
 
             // Find the archetype it'll live in
-            let elements = info.iter().map(|x| x.id()).collect::<Vec<_>>();
-            let target = match self.index.entry(elements) {
-                Entry::Occupied(x) => *x.get(),
-                Entry::Vacant(x) => {
-                    let index = self.archetypes.len() as u32;
-                    self.archetypes.push(Archetype::new(info));
-                    x.insert(index);
-                    self.archetype_generation += 1;
-                    index
-                }
-            };
+            let target = self.get_or_insert_archetype(info, &sparse_info);
 
             if target == loc.archetype {
                 // Update components in the current archetype
                 let arch = &mut self.archetypes[loc.archetype as usize];
                 components.put(|ptr, ty, size| {
-                    arch.put_dynamic(ptr, ty, size, loc.index, false);
+                    if arch.has_table_type(ty) {
+                        arch.put_dynamic(ptr, ty, size, loc.index, false, change_tick);
+                    } else {
+                        arch.put_sparse(ptr, ty, loc.index, change_tick);
+                    }
                     true
                 });
                 return Ok(());
@@ -559,21 +713,40 @@ impl World {
                 target as usize,
             );
             let target_index = target_arch.allocate(entity);
-            loc.archetype = target;
-            let old_index = mem::replace(&mut loc.index, target_index);
+            let old_index = loc.index;
+            *self.entities.get_mut(entity).unwrap() = Location {
+                archetype: target,
+                index: target_index,
+            };
             if let Some(moved) =
-                source_arch.move_to(old_index, |ptr, ty, size, is_added, is_mutated| {
-                    target_arch.put_dynamic(ptr, ty, size, target_index, false);
-                    let type_state = target_arch.get_type_state_mut(ty).unwrap();
-                    *type_state.added().as_ptr().add(target_index) = is_added;
-                    *type_state.mutated().as_ptr().add(target_index) = is_mutated;
+                source_arch.move_to(old_index, |ptr, ty, size, added_tick, mutated_tick| {
+                    if target_arch.has_table_type(ty) {
+                        target_arch.put_dynamic(ptr, ty, size, target_index, false, change_tick);
+                        let type_state = target_arch.get_type_state_mut(ty).unwrap();
+                        *type_state.added().as_ptr().add(target_index) = added_tick;
+                        *type_state.mutated().as_ptr().add(target_index) = mutated_tick;
+                    } else {
+                        target_arch.put_sparse_with_ticks(
+                            ptr,
+                            ty,
+                            target_index,
+                            added_tick,
+                            mutated_tick,
+                        );
+                    }
                 })
             {
                 self.entities.get_mut(moved).unwrap().index = old_index;
             }
 
             components.put(|ptr, ty, size| {
-                target_arch.put_dynamic(ptr, ty, size, target_index, true);
+                if target_arch.has_table_type(ty) {
+                    // components that replace an existing component count as mutated, not added
+                    let added = !source_arch.has_type(ty);
+                    target_arch.put_dynamic(ptr, ty, size, target_index, added, change_tick);
+                } else {
+                    target_arch.put_sparse(ptr, ty, target_index, change_tick);
+                }
                 true
             });
         }
@@ -611,53 +784,82 @@ impl World {
     /// assert_eq!(*world.get::<bool>(e).unwrap(), true);
     /// ```
     pub fn remove<T: Bundle>(&mut self, entity: Entity) -> Result<T, ComponentError> {
-        use std::collections::hash_map::Entry;
-
         self.flush();
-        let loc = self.entities.get_mut(entity)?;
+        let change_tick = self.change_tick();
+        let loc = *self.entities.get_mut(entity)?;
         unsafe {
             let removed = T::with_static_ids(|ids| ids.iter().copied().collect::<HashSet<_>>());
-            let info = self.archetypes[loc.archetype as usize]
+            let old_index = loc.index;
+            let source_arch = &self.archetypes[loc.archetype as usize];
+            let bundle = T::get(|ty, size| {
+                source_arch
+                    .get_dynamic(ty, size, old_index)
+                    .or_else(|| source_arch.get_sparse_dynamic(ty, old_index))
+            })?;
+
+            if !source_arch
+                .types()
+                .iter()
+                .any(|ty| removed.contains(&ty.id()))
+            {
+                // Only sparse set components are removed, so the entity stays in its archetype
+                let source_arch = &mut self.archetypes[loc.archetype as usize];
+                for &ty in &removed {
+                    source_arch.forget_sparse(ty, old_index);
+                    self.removed_components
+                        .entry(ty)
+                        .or_default()
+                        .push(entity, change_tick);
+                }
+                return Ok(bundle);
+            }
+
+            let info = source_arch
                 .types()
                 .iter()
                 .cloned()
                 .filter(|x| !removed.contains(&x.id()))
                 .collect::<Vec<_>>();
-            let elements = info.iter().map(|x| x.id()).collect::<Vec<_>>();
-            let target = match self.index.entry(elements) {
-                Entry::Occupied(x) => *x.get(),
-                Entry::Vacant(x) => {
-                    self.archetypes.push(Archetype::new(info));
-                    let index = (self.archetypes.len() - 1) as u32;
-                    x.insert(index);
-                    self.archetype_generation += 1;
-                    index
-                }
-            };
-            let old_index = loc.index;
-            let source_arch = &self.archetypes[loc.archetype as usize];
-            let bundle = T::get(|ty, size| source_arch.get_dynamic(ty, size, old_index))?;
+            let sparse_info = source_arch
+                .sparse_set_types()
+                .filter(|x| {
+                    !removed.contains(&x.id()) && source_arch.has_component(x.id(), old_index)
+                })
+                .copied()
+                .collect::<Vec<_>>();
+            let target = self.get_or_insert_archetype(info, &sparse_info);
             let (source_arch, target_arch) = index2(
                 &mut self.archetypes,
                 loc.archetype as usize,
                 target as usize,
             );
             let target_index = target_arch.allocate(entity);
-            loc.archetype = target;
-            loc.index = target_index;
+            *self.entities.get_mut(entity).unwrap() = Location {
+                archetype: target,
+                index: target_index,
+            };
             let removed_components = &mut self.removed_components;
             if let Some(moved) =
-                source_arch.move_to(old_index, |src, ty, size, is_added, is_mutated| {
+                source_arch.move_to(old_index, |src, ty, size, added_tick, mutated_tick| {
                     // Only move the components present in the target archetype, i.e. the non-removed ones.
-                    if let Some(dst) = target_arch.get_dynamic(ty, size, target_index) {
+                    if removed.contains(&ty) {
+                        removed_components
+                            .entry(ty)
+                            .or_default()
+                            .push(entity, change_tick);
+                    } else if let Some(dst) = target_arch.get_dynamic(ty, size, target_index) {
                         ptr::copy_nonoverlapping(src, dst.as_ptr(), size);
                         let state = target_arch.get_type_state_mut(ty).unwrap();
-                        *state.added().as_ptr().add(target_index) = is_added;
-                        *state.mutated().as_ptr().add(target_index) = is_mutated;
+                        *state.added().as_ptr().add(target_index) = added_tick;
+                        *state.mutated().as_ptr().add(target_index) = mutated_tick;
                     } else {
-                        let removed_entities =
-                            removed_components.entry(ty).or_insert_with(Vec::new);
-                        removed_entities.push(entity);
+                        target_arch.put_sparse_with_ticks(
+                            src,
+                            ty,
+                            target_index,
+                            added_tick,
+                            mutated_tick,
+                        );
                     }
                 })
             {
@@ -700,6 +902,7 @@ impl World {
     pub unsafe fn get_ref_mut_at_location_unchecked<T: Component>(
         &self,
         location: Location,
+        change_tick: u32,
     ) -> Result<RefMut<T>, ComponentError> {
         if location.archetype == 0 {
             return Err(MissingComponent::new::<T>().into());
@@ -707,6 +910,7 @@ impl World {
         Ok(RefMut::new(
             &self.archetypes[location.archetype as usize],
             location.index,
+            change_tick,
         )?)
     }
 
@@ -721,10 +925,9 @@ impl World {
             return Err(MissingComponent::new::<T>().into());
         }
         Ok(&*self.archetypes[location.archetype as usize]
-            .get::<T>()
+            .get_component::<T>(location.index)
             .ok_or_else(MissingComponent::new::<T>)?
-            .as_ptr()
-            .add(location.index as usize))
+            .as_ptr())
     }
 
     /// Borrow the `T` component at the given location, without safety checks
@@ -735,6 +938,7 @@ impl World {
     pub unsafe fn get_mut_at_location_unchecked<T: Component>(
         &self,
         location: Location,
+        change_tick: u32,
     ) -> Result<Mut<T>, ComponentError> {
         if location.archetype == 0 {
             return Err(MissingComponent::new::<T>().into());
@@ -742,6 +946,7 @@ impl World {
         Ok(Mut::new(
             &self.archetypes[location.archetype as usize],
             location.index,
+            change_tick,
         )?)
     }
 
@@ -762,10 +967,9 @@ impl World {
             return Err(MissingComponent::new::<T>().into());
         }
         Ok(&mut *self.archetypes[loc.archetype as usize]
-            .get::<T>()
+            .get_component::<T>(loc.index)
             .ok_or_else(MissingComponent::new::<T>)?
-            .as_ptr()
-            .add(loc.index as usize))
+            .as_ptr())
     }
 
     /// Convert all reserved entities into empty entities that can be iterated and accessed
@@ -828,13 +1032,51 @@ impl World {
         self.entities.get(entity).ok()
     }
 
-    /// Clears each entity's tracker state. For example, each entity's component "mutated" state will be reset to `false`.
+    /// The current change tick, which components mutated or added outside of a system are marked with
+    pub fn change_tick(&self) -> u32 {
+        self.change_tick.load(Ordering::Acquire)
+    }
+
+    /// Increments the change tick and returns its previous value. Every system run gets its own tick this way.
+    pub fn increment_change_tick(&self) -> u32 {
+        self.change_tick.fetch_add(1, Ordering::AcqRel)
+    }
+
+    /// The change tick at the last `clear_trackers` call. Queries made directly on the world detect the changes made
+    /// since then.
+    pub fn last_change_tick(&self) -> u32 {
+        self.last_change_tick
+    }
+
+    fn change_ticks(&self) -> ChangeTicks {
+        ChangeTicks::new(self.last_change_tick, self.change_tick())
+    }
+
+    /// Clears each entity's tracker state. Components that were added or mutated before this call no longer count
+    /// as added or mutated for queries made directly on the world, and the list of removed components is cleared.
+    ///
+    /// Systems track their own change ticks, so this doesn't affect the changes they detect. Removals are kept
+    /// until the next call, so systems that ran before a removal was made still see it the next time they run.
     pub fn clear_trackers(&mut self) {
-        for archetype in self.archetypes.iter_mut() {
-            archetype.clear_trackers();
+        self.last_change_tick = self.increment_change_tick();
+        for removals in self.removed_components.values_mut() {
+            removals.clear_previous();
         }
 
-        self.removed_components.clear();
+        let change_tick = self.change_tick();
+        if change_tick.wrapping_sub(self.last_check_tick) >= CHECK_TICK_THRESHOLD {
+            self.check_change_ticks();
+        }
+    }
+
+    /// Clamps the change ticks of all components so that very old changes don't appear new when the change tick
+    /// wraps around. This is called periodically by `clear_trackers`.
+    pub fn check_change_ticks(&mut self) {
+        let change_tick = self.change_tick();
+        for archetype in self.archetypes.iter_mut() {
+            archetype.check_change_ticks(change_tick);
+        }
+        self.last_check_tick = change_tick;
     }
 
     /// Gets an entity reserver, which can be used to reserve entity ids in a multi-threaded context.
@@ -846,6 +1088,30 @@ impl World {
 unsafe impl Send for World {}
 unsafe impl Sync for World {}
 
+/// The entities whose components of one type were removed, and the change tick of each removal. Removals are
+/// double buffered: the ones made before the last `clear_trackers` call are kept until the next call, after which even
+/// systems that haven't run since can no longer see them.
+#[derive(Default)]
+struct Removals {
+    entities: Vec<Entity>,
+    ticks: Vec<u32>,
+    /// The index of the first removal made since the last `clear_trackers` call
+    current: usize,
+}
+
+impl Removals {
+    fn push(&mut self, entity: Entity, change_tick: u32) {
+        self.entities.push(entity);
+        self.ticks.push(change_tick);
+    }
+
+    fn clear_previous(&mut self) {
+        self.entities.drain(..self.current);
+        self.ticks.drain(..self.current);
+        self.current = self.entities.len();
+    }
+}
+
 impl Default for World {
     fn default() -> Self {
         Self::new()
@@ -916,15 +1182,17 @@ pub struct Iter<'a> {
     entities: &'a Entities,
     current: Option<&'a Archetype>,
     index: usize,
+    change_tick: u32,
 }
 
 impl<'a> Iter<'a> {
-    fn new(archetypes: &'a [Archetype], entities: &'a Entities) -> Self {
+    fn new(archetypes: &'a [Archetype], entities: &'a Entities, change_tick: u32) -> Self {
         Self {
             archetypes: archetypes.iter(),
             entities,
             current: None,
             index: 0,
+            change_tick,
         }
     }
 }
@@ -950,7 +1218,9 @@ impl<'a> Iterator for Iter<'a> {
                     let index = self.index;
                     self.index += 1;
                     let id = current.get_entity(index);
-                    return Some((id, unsafe { EntityRef::new(current, index) }));
+                    return Some((id, unsafe {
+                        EntityRef::new(current, index, self.change_tick)
+                    }));
                 }
             }
         }
@@ -994,6 +1264,7 @@ where
     entities: &'a mut Entities,
     archetype_id: u32,
     archetype: &'a mut Archetype,
+    change_tick: u32,
 }
 
 impl<I> Drop for SpawnBatchIter<'_, I>
@@ -1019,7 +1290,8 @@ where
         unsafe {
             let index = self.archetype.allocate(entity);
             components.put(|ptr, ty, size| {
-                self.archetype.put_dynamic(ptr, ty, size, index, true);
+                self.archetype
+                    .put_new(ptr, ty, size, index, self.change_tick);
                 true
             });
             self.entities.meta[entity.id as usize].location = Location {
@@ -365,3 +365,130 @@ fn remove_tracking() {
         "world clears result in 'removed component' states"
     );
 }
+
+#[test]
+fn sparse_set_storage() {
+    #[derive(Debug, PartialEq)]
+    struct Selected(u32);
+
+    let mut world = World::new();
+    world
+        .set_storage_type::<Selected>(StorageType::SparseSet)
+        .unwrap();
+    let a = world.spawn((1, true));
+    let b = world.spawn((2, true, Selected(2)));
+    let c = world.spawn((3, true));
+    let archetypes = world.archetypes().len();
+
+    world.insert_one(a, Selected(1)).unwrap();
+    assert_eq!(world.archetypes().len(), archetypes);
+    assert_eq!(*world.get::<Selected>(a).unwrap(), Selected(1));
+    assert!(world.get::<Selected>(c).is_err());
+
+    let mut selected = world
+        .query::<(Entity, &i32, &Selected)>()
+        .iter()
+        .map(|(e, &i, s)| (e, i, s.0))
+        .collect::<Vec<_>>();
+    selected.sort();
+    assert_eq!(selected, &[(a, 1, 1), (b, 2, 2)]);
+    assert_eq!(
+        world
+            .query::<Without<Selected, (Entity, &i32)>>()
+            .iter()
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>(),
+        &[c]
+    );
+    assert_eq!(world.query::<With<Selected, &i32>>().iter().count(), 2);
+    let mut optional = world
+        .query::<(&i32, Option<&Selected>)>()
+        .iter()
+        .map(|(&i, s)| (i, s.map(|s| s.0)))
+        .collect::<Vec<_>>();
+    optional.sort();
+    assert_eq!(optional, &[(1, Some(1)), (2, Some(2)), (3, None)]);
+
+    // removing the entity in front of `b` moves `b` within the archetype
+    world.despawn(a).unwrap();
+    assert_eq!(world.removed::<Selected>(), &[a]);
+    assert_eq!(*world.get::<Selected>(b).unwrap(), Selected(2));
+
+    world.clear_trackers();
+    world.insert_one(c, Selected(3)).unwrap();
+    assert_eq!(
+        world
+            .query::<(Entity, Added<Selected>)>()
+            .iter()
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>(),
+        &[c]
+    );
+    assert_eq!(world.remove_one::<Selected>(b), Ok(Selected(2)));
+    assert_eq!(world.archetypes().len(), archetypes);
+    assert_eq!(world.removed::<Selected>(), &[b]);
+
+    // sparse set components move along with their entity
+    world.insert_one(c, "abc").unwrap();
+    assert_eq!(*world.get::<Selected>(c).unwrap(), Selected(3));
+    assert_eq!(world.remove_one::<i32>(c), Ok(3));
+    assert_eq!(*world.get::<Selected>(c).unwrap(), Selected(3));
+    assert!(world.get::<Selected>(b).is_err());
+}
+
+#[test]
+fn query_filters() {
+    struct Player;
+    struct Enemy;
+
+    let mut world = World::new();
+    world
+        .set_storage_type::<Enemy>(StorageType::SparseSet)
+        .unwrap();
+    let a = world.spawn((1, Player, 1.0f32));
+    let b = world.spawn((2, Player));
+    let c = world.spawn((3, Enemy));
+    let d = world.spawn((4, 4.0f32));
+
+    fn filtered<F: QueryFilter>(world: &World) -> Vec<Entity> {
+        let mut entities = world
+            .query::<Filtered<Entity, F>>()
+            .iter()
+            .collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    assert_eq!(filtered::<With<Player>>(&world), &[a, b]);
+    assert_eq!(filtered::<Without<Enemy>>(&world), &[a, b, d]);
+    assert_eq!(filtered::<(With<Player>, With<f32>)>(&world), &[a]);
+    assert_eq!(filtered::<Or<(With<Enemy>, With<f32>)>>(&world), &[a, c, d]);
+
+    // filters don't borrow the components they test
+    let mut players = unsafe { world.query_unchecked::<&mut Player>() };
+    let _players = players.iter();
+    assert_eq!(
+        world
+            .query::<Filtered<&i32, With<Player>>>()
+            .iter()
+            .copied()
+            .sum::<i32>(),
+        3
+    );
+    drop(players);
+
+    world.clear_trackers();
+    *world.get_mut::<i32>(b).unwrap() += 1;
+    world.insert_one(a, Enemy).unwrap();
+    assert_eq!(filtered::<Changed<i32>>(&world), &[b]);
+    assert_eq!(
+        filtered::<Or<(Mutated<i32>, Added<Enemy>)>>(&world),
+        &[a, b]
+    );
+    assert_eq!(filtered::<(With<Player>, Without<Enemy>)>(&world), &[b]);
+    assert!(world
+        .query_one::<Filtered<&i32, Added<Enemy>>>(b)
+        .unwrap()
+        .get()
+        .is_none());
+}
@@ -12,8 +12,11 @@ pub use world::*;
 pub mod prelude {
     pub use crate::{
         resource::{ChangedRes, FromResources, Local, OrRes, Res, ResMut, Resource, Resources},
+        schedule::{IntoSystemDescriptor, ShouldRun, State},
         system::{
-            Commands, IntoForEachSystem, IntoQuerySystem, IntoThreadLocalSystem, Query, System,
+            BoxedSystem, Commands, EntityCommands, In, IntoChainSystem, IntoFallibleSystem,
+            IntoForEachSystem, IntoQuerySystem, IntoThreadLocalSystem, Query, RemovedComponents,
+            System,
         },
         world::WorldBuilderSource,
         Added, Bundle, Changed, Component, Entity, Mut, Mutated, Or, Ref, RefMut, With, Without,
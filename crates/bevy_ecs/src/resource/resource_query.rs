@@ -1,9 +1,9 @@
 use super::{FromResources, Resources};
 use crate::{
-    system::{SystemId, TypeAccess},
+    system::{SystemChangeTicks, SystemId, TypeAccess},
     Resource, ResourceIndex,
 };
-use bevy_hecs::smaller_tuples_too;
+use bevy_hecs::{smaller_tuples_too, ChangeTicks, World};
 use core::{
     any::TypeId,
     ops::{Deref, DerefMut},
@@ -90,18 +90,21 @@ impl<'a, T: Resource> Deref for Res<'a, T> {
 pub struct ResMut<'a, T: Resource> {
     _marker: PhantomData<&'a T>,
     value: *mut T,
-    mutated: *mut bool,
+    mutated_tick: *mut u32,
+    change_tick: u32,
 }
 
 impl<'a, T: Resource> ResMut<'a, T> {
-    /// Creates a mutable reference cell to a Resource from a pointer
+    /// Creates a mutable reference cell to a Resource from a pointer. Mutably dereferencing it marks the resource as
+    /// mutated at `change_tick`.
     ///
     /// # Safety
-    /// The pointer must have correct lifetime / storage / ownership
-    pub unsafe fn new(value: NonNull<T>, mutated: NonNull<bool>) -> Self {
+    /// The pointers must have correct lifetime / storage / ownership
+    pub unsafe fn new(value: NonNull<T>, mutated_tick: NonNull<u32>, change_tick: u32) -> Self {
         Self {
             value: value.as_ptr(),
-            mutated: mutated.as_ptr(),
+            mutated_tick: mutated_tick.as_ptr(),
+            change_tick,
             _marker: Default::default(),
         }
     }
@@ -121,7 +124,7 @@ impl<'a, T: Resource> Deref for ResMut<'a, T> {
 impl<'a, T: Resource> DerefMut for ResMut<'a, T> {
     fn deref_mut(&mut self) -> &mut T {
         unsafe {
-            *self.mutated = true;
+            *self.mutated_tick = self.change_tick;
             &mut *self.value
         }
     }
@@ -131,7 +134,8 @@ impl<'a, T: Resource> UnsafeClone for ResMut<'a, T> {
     unsafe fn unsafe_clone(&self) -> Self {
         Self {
             value: self.value,
-            mutated: self.mutated,
+            mutated_tick: self.mutated_tick,
+            change_tick: self.change_tick,
             _marker: Default::default(),
         }
     }
@@ -186,10 +190,32 @@ pub trait FetchResource<'a>: Sized {
     #[allow(clippy::missing_safety_doc)]
     unsafe fn get(resources: &'a Resources, system_id: Option<SystemId>) -> Self::Item;
 
+    /// Fetches the item for a system. Fetches that read from the [World] the system runs on or that track changes
+    /// override this. `ticks` are the change ticks of the system run.
+    #[allow(clippy::missing_safety_doc)]
+    unsafe fn get_for_system(
+        _world: &'a World,
+        resources: &'a Resources,
+        system_id: SystemId,
+        _ticks: SystemChangeTicks,
+    ) -> Self::Item {
+        Self::get(resources, Some(system_id))
+    }
+
     #[allow(clippy::missing_safety_doc)]
     unsafe fn is_some(_resources: &'a Resources, _system_id: Option<SystemId>) -> bool {
         true
     }
+
+    /// Whether the item exists for a system. `ticks` are the resource change ticks of the system run.
+    #[allow(clippy::missing_safety_doc)]
+    unsafe fn is_some_for_system(
+        resources: &'a Resources,
+        system_id: SystemId,
+        _ticks: ChangeTicks,
+    ) -> bool {
+        Self::is_some(resources, Some(system_id))
+    }
 }
 
 impl<'a, T: Resource> ResourceQuery for Res<'a, T> {
@@ -235,9 +261,16 @@ impl<'a, T: Resource> FetchResource<'a> for FetchResourceChanged<T> {
         ChangedRes::new(resources.get_unsafe_ref::<T>(ResourceIndex::Global))
     }
 
-    unsafe fn is_some(resources: &'a Resources, _system_id: Option<SystemId>) -> bool {
-        let (added, mutated) = resources.get_unsafe_added_and_mutated::<T>(ResourceIndex::Global);
-        *added.as_ptr() || *mutated.as_ptr()
+    unsafe fn is_some(resources: &'a Resources, system_id: Option<SystemId>) -> bool {
+        Self::is_some_for_changes(resources, system_id, resources.change_ticks())
+    }
+
+    unsafe fn is_some_for_system(
+        resources: &'a Resources,
+        system_id: SystemId,
+        ticks: ChangeTicks,
+    ) -> bool {
+        Self::is_some_for_changes(resources, Some(system_id), ticks)
     }
 
     fn borrow(resources: &Resources) {
@@ -255,6 +288,17 @@ impl<'a, T: Resource> FetchResource<'a> for FetchResourceChanged<T> {
     }
 }
 
+impl<T: Resource> FetchResourceChanged<T> {
+    unsafe fn is_some_for_changes(
+        resources: &Resources,
+        _system_id: Option<SystemId>,
+        ticks: ChangeTicks,
+    ) -> bool {
+        let (added, mutated) = resources.get_unsafe_added_and_mutated::<T>(ResourceIndex::Global);
+        ticks.is_changed(*added.as_ptr()) || ticks.is_changed(*mutated.as_ptr())
+    }
+}
+
 impl<'a, T: Resource> ResourceQuery for ResMut<'a, T> {
     type Fetch = FetchResourceWrite<T>;
 }
@@ -268,7 +312,18 @@ impl<'a, T: Resource> FetchResource<'a> for FetchResourceWrite<T> {
     unsafe fn get(resources: &'a Resources, _system_id: Option<SystemId>) -> Self::Item {
         let (value, type_state) =
             resources.get_unsafe_ref_with_type_state::<T>(ResourceIndex::Global);
-        ResMut::new(value, type_state.mutated())
+        ResMut::new(value, type_state.mutated(), resources.change_tick())
+    }
+
+    unsafe fn get_for_system(
+        _world: &'a World,
+        resources: &'a Resources,
+        _system_id: SystemId,
+        ticks: SystemChangeTicks,
+    ) -> Self::Item {
+        let (value, type_state) =
+            resources.get_unsafe_ref_with_type_state::<T>(ResourceIndex::Global);
+        ResMut::new(value, type_state.mutated(), ticks.resources.change_tick)
     }
 
     fn borrow(resources: &Resources) {
@@ -347,11 +402,21 @@ macro_rules! tuple_impl {
                 ($($name::get(resources, system_id),)*)
             }
 
+            #[allow(unused_variables)]
+            unsafe fn get_for_system(world: &'a World, resources: &'a Resources, system_id: SystemId, ticks: SystemChangeTicks) -> Self::Item {
+                ($($name::get_for_system(world, resources, system_id, ticks),)*)
+            }
+
             #[allow(unused_variables)]
             unsafe fn is_some(resources: &'a Resources, system_id: Option<SystemId>) -> bool {
                 true $(&& $name::is_some(resources, system_id))*
             }
 
+            #[allow(unused_variables)]
+            unsafe fn is_some_for_system(resources: &'a Resources, system_id: SystemId, ticks: ChangeTicks) -> bool {
+                true $(&& $name::is_some_for_system(resources, system_id, ticks))*
+            }
+
             #[allow(unused_mut)]
             fn access() -> TypeAccess {
                 let mut access = TypeAccess::default();
@@ -406,11 +471,21 @@ macro_rules! tuple_impl_or {
                 OrRes(($($name::get(resources, system_id),)*))
             }
 
+            #[allow(unused_variables)]
+            unsafe fn get_for_system(world: &'a World, resources: &'a Resources, system_id: SystemId, ticks: SystemChangeTicks) -> Self::Item {
+                OrRes(($($name::get_for_system(world, resources, system_id, ticks),)*))
+            }
+
             #[allow(unused_variables)]
             unsafe fn is_some(resources: &'a Resources, system_id: Option<SystemId>) -> bool {
                 false $(|| $name::is_some(resources, system_id))*
             }
 
+            #[allow(unused_variables)]
+            unsafe fn is_some_for_system(resources: &'a Resources, system_id: SystemId, ticks: ChangeTicks) -> bool {
+                false $(|| $name::is_some_for_system(resources, system_id, ticks))*
+            }
+
             #[allow(unused_mut)]
             fn access() -> TypeAccess {
                 let mut access = TypeAccess::default();
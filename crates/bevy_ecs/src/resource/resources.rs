@@ -1,9 +1,12 @@
 use super::{FetchResource, ResourceQuery};
-use crate::system::SystemId;
-use bevy_hecs::{Archetype, Entity, Ref, RefMut, TypeInfo, TypeState};
+use crate::system::{SystemChangeTicks, SystemId};
+use bevy_hecs::{Archetype, ChangeTicks, Entity, Ref, RefMut, TypeInfo, TypeState, World};
 use bevy_utils::HashMap;
 use core::any::TypeId;
-use std::ptr::NonNull;
+use std::{
+    ptr::NonNull,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 /// A Resource type
 pub trait Resource: Send + Sync + 'static {}
@@ -21,9 +24,25 @@ pub enum ResourceIndex {
 }
 
 /// A collection of resource instances identified by their type.
-#[derive(Default)]
+///
+/// Resource changes are tracked with a change tick that is incremented every time a system runs, the same way the
+/// [World] tracks component changes. [ChangedRes](crate::ChangedRes) in a system detects the changes made since the
+/// system last ran. Resources queried directly with [Resources::query] detect the changes made since the last
+/// [Resources::clear_trackers] call.
 pub struct Resources {
     pub(crate) resource_data: HashMap<TypeId, ResourceData>,
+    change_tick: AtomicU32,
+    last_change_tick: u32,
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Resources {
+            resource_data: HashMap::default(),
+            change_tick: AtomicU32::new(1),
+            last_change_tick: 0,
+        }
+    }
 }
 
 impl Resources {
@@ -35,6 +54,14 @@ impl Resources {
         self.get_resource::<T>(ResourceIndex::Global).is_some()
     }
 
+    /// Returns the name of the resource type with the given id, if a resource of that type was inserted
+    #[cfg(feature = "graph_export")]
+    pub(crate) fn type_name(&self, type_id: TypeId) -> Option<&'static str> {
+        self.resource_data
+            .get(&type_id)
+            .map(|data| data.archetype.types()[0].type_name())
+    }
+
     pub fn get<T: Resource>(&self) -> Option<Ref<'_, T>> {
         self.get_resource(ResourceIndex::Global)
     }
@@ -65,6 +92,7 @@ impl Resources {
 
     fn insert_resource<T: Resource>(&mut self, mut resource: T, resource_index: ResourceIndex) {
         let type_id = TypeId::of::<T>();
+        let change_tick = self.change_tick();
         let data = self.resource_data.entry(type_id).or_insert_with(|| {
             let mut types = Vec::new();
             types.push(TypeInfo::of::<T>());
@@ -108,6 +136,7 @@ impl Resources {
                 core::mem::size_of::<T>(),
                 index,
                 added,
+                change_tick,
             );
             std::mem::forget(resource);
         }
@@ -136,7 +165,7 @@ impl Resources {
                     ResourceIndex::Global => data.default_index?,
                     ResourceIndex::System(id) => *data.system_id_to_archetype_index.get(&id.0)?,
                 };
-                RefMut::new(&data.archetype, index).ok()
+                RefMut::new(&data.archetype, index, self.change_tick()).ok()
             })
     }
 
@@ -150,13 +179,16 @@ impl Resources {
         }
     }
 
-    pub fn query_system<Q: ResourceQuery>(
-        &self,
+    /// Queries resources for the system with the given id, which runs on `world` with the given change ticks
+    pub fn query_system<'a, Q: ResourceQuery>(
+        &'a self,
+        world: &'a World,
         id: SystemId,
-    ) -> Option<<Q::Fetch as FetchResource>::Item> {
+        ticks: SystemChangeTicks,
+    ) -> Option<<Q::Fetch as FetchResource<'a>>::Item> {
         unsafe {
-            if Q::Fetch::is_some(&self, Some(id)) {
-                Some(Q::Fetch::get(&self, Some(id)))
+            if Q::Fetch::is_some_for_system(&self, id, ticks.resources) {
+                Some(Q::Fetch::get_for_system(world, &self, id, ticks))
             } else {
                 None
             }
@@ -200,7 +232,7 @@ impl Resources {
     pub unsafe fn get_unsafe_added_and_mutated<T: Resource>(
         &self,
         resource_index: ResourceIndex,
-    ) -> (NonNull<bool>, NonNull<bool>) {
+    ) -> (NonNull<u32>, NonNull<u32>) {
         self.get_resource_data_index::<T>(resource_index)
             .and_then(|(data, index)| {
                 let type_state = data.archetype.get_type_state(TypeId::of::<T>())?;
@@ -252,11 +284,31 @@ impl Resources {
         }
     }
 
-    /// Clears each resource's tracker state.
-    /// For example, each resource's component "mutated" state will be reset to `false`.
+    /// The current change tick, which resources that are mutated or inserted outside of systems are marked with
+    pub fn change_tick(&self) -> u32 {
+        self.change_tick.load(Ordering::Acquire)
+    }
+
+    /// Increments the change tick and returns its previous value. Every system run gets its own tick this way.
+    pub fn increment_change_tick(&self) -> u32 {
+        self.change_tick.fetch_add(1, Ordering::AcqRel)
+    }
+
+    /// The ticks resource changes are detected with outside of systems. Resources count as changed if they were
+    /// mutated or inserted since the last [Resources::clear_trackers] call.
+    pub fn change_ticks(&self) -> ChangeTicks {
+        ChangeTicks::new(self.last_change_tick, self.change_tick())
+    }
+
+    /// Clears each resource's tracker state for queries made directly on the resources.
+    /// For example, a resource that was mutated before this call will no longer count as changed.
+    ///
+    /// Systems track their own change ticks, so this doesn't affect the changes they detect.
     pub fn clear_trackers(&mut self) {
+        self.last_change_tick = self.increment_change_tick();
+        let change_tick = self.change_tick();
         for (_, resource_data) in self.resource_data.iter_mut() {
-            resource_data.archetype.clear_trackers();
+            resource_data.archetype.check_change_ticks(change_tick);
         }
     }
 }
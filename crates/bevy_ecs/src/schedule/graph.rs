@@ -0,0 +1,349 @@
+use super::{ExecutorStage, Schedule};
+use crate::{
+    resource::Resources,
+    system::{System, ThreadLocalExecution, TypeAccess},
+};
+use bevy_hecs::World;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// A snapshot of the stages and systems of a [Schedule], the archetypes and resources each system accesses and the
+/// dependencies the [ParallelExecutor](super::ParallelExecutor) derives from them. Systems in a stage only run in
+/// parallel if there is no path of dependencies between them.
+///
+/// Created by [Schedule::graph] and exported with [ScheduleGraph::to_dot] or [ScheduleGraph::to_json].
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleGraph {
+    /// The component types of each archetype in the world. Systems refer to archetypes by their index in this list.
+    pub archetypes: Vec<Vec<String>>,
+    pub stages: Vec<StageGraph>,
+}
+
+/// The systems of a stage in a [ScheduleGraph], in the order the executor starts them in
+#[derive(Debug, Clone, Serialize)]
+pub struct StageGraph {
+    pub name: String,
+    pub systems: Vec<SystemNode>,
+    pub dependencies: Vec<SystemDependency>,
+}
+
+/// A system in a [StageGraph] and the data it accesses
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemNode {
+    pub name: String,
+    /// Thread local systems run exclusively, so they wait for every system before them and every system after them
+    /// waits for them
+    pub thread_local: bool,
+    pub archetypes_read: Vec<usize>,
+    pub archetypes_written: Vec<usize>,
+    pub resources_read: Vec<String>,
+    pub resources_written: Vec<String>,
+}
+
+/// States that the system at index `system` of a stage doesn't start before the system at index `dependency` finished
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SystemDependency {
+    pub system: usize,
+    pub dependency: usize,
+    pub reason: DependencyReason,
+}
+
+/// Why a [SystemDependency] exists
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub enum DependencyReason {
+    /// The system was explicitly ordered after its dependency
+    Ordering,
+    /// One of the systems is thread local
+    ThreadLocal,
+    /// The systems access the same archetypes or resources, and at least one of them writes to them
+    Conflict,
+}
+
+impl ScheduleGraph {
+    pub(crate) fn new(schedule: &mut Schedule, world: &World, resources: &Resources) -> Self {
+        schedule.ensure_system_order();
+        let archetypes = world
+            .archetypes()
+            .map(|archetype| {
+                archetype
+                    .types()
+                    .iter()
+                    .chain(archetype.sparse_set_types())
+                    .map(|type_info| type_info.type_name().to_string())
+                    .collect()
+            })
+            .collect();
+
+        let mut stages = Vec::new();
+        for stage_name in schedule.stage_order.iter() {
+            let systems = schedule.stages.get_mut(stage_name).unwrap();
+            let ordering_dependencies = schedule
+                .stage_dependencies
+                .get(stage_name)
+                .map_or(&[][..], |dependencies| dependencies.as_slice());
+            let system_dependencies =
+                ExecutorStage::default().system_dependencies(world, systems, ordering_dependencies);
+
+            let mut dependencies = Vec::new();
+            for (system, system_dependencies) in system_dependencies.into_iter().enumerate() {
+                for dependency in system_dependencies {
+                    let reason = if ordering_dependencies
+                        .get(system)
+                        .map_or(false, |ordered_after| ordered_after.contains(&dependency))
+                    {
+                        DependencyReason::Ordering
+                    } else if is_thread_local(&*systems[system])
+                        || is_thread_local(&*systems[dependency])
+                    {
+                        DependencyReason::ThreadLocal
+                    } else {
+                        DependencyReason::Conflict
+                    };
+                    dependencies.push(SystemDependency {
+                        system,
+                        dependency,
+                        reason,
+                    });
+                }
+            }
+
+            stages.push(StageGraph {
+                name: stage_name.to_string(),
+                systems: systems
+                    .iter()
+                    .map(|system| {
+                        let archetype_access = system.archetype_access();
+                        let (resources_read, resources_written) =
+                            resource_names(system.resource_access(), resources);
+                        SystemNode {
+                            name: system.name().to_string(),
+                            thread_local: is_thread_local(&**system),
+                            archetypes_read: archetype_access.immutable.ones().collect(),
+                            archetypes_written: archetype_access.mutable.ones().collect(),
+                            resources_read,
+                            resources_written,
+                        }
+                    })
+                    .collect(),
+                dependencies,
+            });
+        }
+
+        ScheduleGraph { archetypes, stages }
+    }
+
+    /// Renders the graph in the Graphviz DOT format. Each stage is a cluster of systems, and each dependency is an edge
+    /// from the dependency to the system that waits for it.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph schedule {{").unwrap();
+        writeln!(dot, "    node [shape=box];").unwrap();
+        let archetypes = self
+            .archetypes
+            .iter()
+            .enumerate()
+            .map(|(index, types)| format!("{}: {}", index, types.join(", ")))
+            .collect::<Vec<_>>();
+        writeln!(
+            dot,
+            "    archetypes [shape=note, label=\"{}\"];",
+            escape(&archetypes.join("\n"))
+        )
+        .unwrap();
+
+        for (stage_index, stage) in self.stages.iter().enumerate() {
+            writeln!(dot, "    subgraph cluster_{} {{", stage_index).unwrap();
+            writeln!(dot, "        label=\"{}\";", escape(&stage.name)).unwrap();
+            for (system_index, system) in stage.systems.iter().enumerate() {
+                let mut label = system.name.clone();
+                let access = [
+                    ("reads archetypes", join_indices(&system.archetypes_read)),
+                    (
+                        "writes archetypes",
+                        join_indices(&system.archetypes_written),
+                    ),
+                    ("reads", system.resources_read.join(", ")),
+                    ("writes", system.resources_written.join(", ")),
+                ];
+                for (kind, accessed) in access.iter() {
+                    if !accessed.is_empty() {
+                        write!(label, "\n{}: {}", kind, accessed).unwrap();
+                    }
+                }
+                let style = if system.thread_local {
+                    ", style=filled, fillcolor=lightgrey"
+                } else {
+                    ""
+                };
+                writeln!(
+                    dot,
+                    "        s{}_{} [label=\"{}\"{}];",
+                    stage_index,
+                    system_index,
+                    escape(&label),
+                    style
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+
+            for dependency in stage.dependencies.iter() {
+                let style = match dependency.reason {
+                    DependencyReason::Ordering => "solid",
+                    DependencyReason::ThreadLocal => "dotted",
+                    DependencyReason::Conflict => "dashed",
+                };
+                writeln!(
+                    dot,
+                    "    s{0}_{1} -> s{0}_{2} [style={3}];",
+                    stage_index, dependency.dependency, dependency.system, style
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Serializes the graph to pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("schedule graphs are always serializable")
+    }
+}
+
+impl Schedule {
+    /// Creates a [ScheduleGraph] of the schedule's systems and their dependencies when they run on `world`. This
+    /// orders the systems and updates their archetype access like running the schedule does.
+    pub fn graph(&mut self, world: &World, resources: &Resources) -> ScheduleGraph {
+        ScheduleGraph::new(self, world, resources)
+    }
+}
+
+fn is_thread_local(system: &dyn System<In = (), Out = ()>) -> bool {
+    system.thread_local_execution() == ThreadLocalExecution::Immediate
+}
+
+/// Returns the sorted names of the resources read and written with `access`. Resources that haven't been inserted
+/// yet are named by their type id.
+fn resource_names(access: &TypeAccess, resources: &Resources) -> (Vec<String>, Vec<String>) {
+    let names = |types: &bevy_utils::HashSet<std::any::TypeId>| {
+        let mut names = types
+            .iter()
+            .map(|type_id| match resources.type_name(*type_id) {
+                Some(type_name) => type_name.to_string(),
+                None => format!("{:?}", type_id),
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    (names(&access.immutable), names(&access.mutable))
+}
+
+fn join_indices(indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DependencyReason;
+    use crate::{
+        resource::{Res, ResMut, Resources},
+        schedule::{IntoSystemDescriptor, Schedule},
+        system::{IntoQuerySystem, IntoThreadLocalSystem, Query},
+    };
+    use bevy_hecs::World;
+
+    #[test]
+    fn schedule_graph() {
+        fn read_u32(_count: Res<usize>, _query: Query<&u32>) {}
+        fn write_u32(_query: Query<&mut u32>) {}
+        fn write_count(_count: ResMut<usize>) {}
+        fn read_f32(_query: Query<&f32>) {}
+        fn thread_local(_world: &mut World, _resources: &mut Resources) {}
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0usize);
+        world.spawn((1u32,));
+        world.spawn((1u32, 1.0f32));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", thread_local.thread_local_system().label("first"));
+        schedule.add_system_to_stage("update", read_u32.system());
+        schedule.add_system_to_stage("update", write_u32.system());
+        schedule.add_system_to_stage("update", write_count.system());
+        schedule.add_system_to_stage("update", read_f32.system().after("first"));
+        schedule.initialize(&mut world, &mut resources);
+
+        let graph = schedule.graph(&world, &resources);
+        assert_eq!(graph.archetypes.len(), world.archetypes().len());
+        let stage = &graph.stages[0];
+        assert_eq!(stage.name, "update");
+        let index = |name: &str| {
+            stage
+                .systems
+                .iter()
+                .position(|system| system.name.ends_with(name))
+                .unwrap()
+        };
+        let (read_u32, write_u32, write_count, read_f32, thread_local) = (
+            index("read_u32"),
+            index("write_u32"),
+            index("write_count"),
+            index("read_f32"),
+            index("thread_local"),
+        );
+        assert!(stage.systems[thread_local].thread_local);
+        assert_eq!(stage.systems[read_u32].resources_read, vec!["usize"]);
+        assert_eq!(stage.systems[write_count].resources_written, vec!["usize"]);
+        assert_eq!(stage.systems[write_u32].archetypes_written.len(), 2);
+        assert_eq!(stage.systems[read_f32].archetypes_read.len(), 1);
+
+        let reason = |system: usize, dependency: usize| {
+            stage
+                .dependencies
+                .iter()
+                .find(|edge| edge.system == system && edge.dependency == dependency)
+                .map(|edge| edge.reason)
+        };
+        assert_eq!(
+            reason(read_f32, thread_local),
+            Some(DependencyReason::Ordering)
+        );
+        assert_eq!(
+            reason(read_u32, thread_local),
+            Some(DependencyReason::ThreadLocal)
+        );
+        assert_eq!(
+            reason(write_u32, read_u32),
+            Some(DependencyReason::Conflict)
+        );
+        assert_eq!(
+            reason(write_count, read_u32),
+            Some(DependencyReason::Conflict)
+        );
+        assert_eq!(reason(write_count, write_u32), None);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph schedule {"));
+        assert!(dot.contains(&format!(
+            "s0_{} -> s0_{} [style=solid];",
+            thread_local, read_f32
+        )));
+        let json = graph.to_json();
+        assert!(json.contains("\"reason\": \"Conflict\""));
+    }
+}
@@ -1,6 +1,16 @@
+#[cfg(feature = "graph_export")]
+mod graph;
 mod parallel_executor;
+mod run_criteria;
 #[allow(clippy::module_inception)]
 mod schedule;
+mod state;
+mod system_descriptor;
 
+#[cfg(feature = "graph_export")]
+pub use graph::*;
 pub use parallel_executor::*;
+pub use run_criteria::*;
 pub use schedule::*;
+pub use state::*;
+pub use system_descriptor::*;
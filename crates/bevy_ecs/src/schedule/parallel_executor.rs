@@ -1,7 +1,7 @@
-use super::Schedule;
+use super::{run_stage_with_criteria, Schedule};
 use crate::{
     resource::Resources,
-    system::{ArchetypeAccess, System, ThreadLocalExecution, TypeAccess},
+    system::{ArchetypeAccess, BoxedSystem, System, ThreadLocalExecution, TypeAccess},
 };
 use bevy_hecs::{ArchetypesGeneration, World};
 use bevy_tasks::{ComputeTaskPool, CountdownEvent, TaskPool};
@@ -15,6 +15,7 @@ use std::ops::Range;
 /// * in a given stage, systems the read archetype X cannot run before systems registered before them that write archetype X
 /// * in a given stage, systems that mutate resource Y cannot run before systems registered before them that read/write resource Y
 /// * in a given stage, systems the read resource Y cannot run before systems registered before them that write resource Y
+/// * in a given stage, systems cannot run before the systems they were ordered after
 
 #[derive(Debug)]
 pub struct ParallelExecutor {
@@ -48,6 +49,7 @@ impl ParallelExecutor {
     }
 
     pub fn run(&mut self, schedule: &mut Schedule, world: &mut World, resources: &mut Resources) {
+        schedule.ensure_system_order();
         let schedule_generation = schedule.generation();
         let schedule_changed = schedule.generation() != self.last_schedule_generation;
         if schedule_changed {
@@ -59,10 +61,30 @@ impl ParallelExecutor {
         {
             log::trace!("run stage {:?}", stage_name);
             if let Some(stage_systems) = schedule.stages.get_mut(stage_name) {
-                executor_stage.run(world, resources, stage_systems, schedule_changed);
+                let ordering_dependencies = schedule
+                    .stage_dependencies
+                    .get(stage_name)
+                    .map_or(&[][..], |dependencies| dependencies.as_slice());
+                let mut stage_changed = schedule_changed;
+                run_stage_with_criteria(
+                    schedule.run_criteria.get_mut(stage_name),
+                    world,
+                    resources,
+                    |world, resources| {
+                        executor_stage.run(
+                            world,
+                            resources,
+                            stage_systems,
+                            ordering_dependencies,
+                            stage_changed,
+                        );
+                        stage_changed = false;
+                    },
+                );
             }
         }
 
+        schedule.check_change_ticks(world.change_tick());
         if self.clear_trackers {
             world.clear_trackers();
             resources.clear_trackers();
@@ -84,6 +106,11 @@ pub struct ExecutorStage {
     ready_events_of_dependents: Vec<Vec<CountdownEvent>>,
     /// each system's dependents (the systems that can't run until this system has run)
     system_dependents: Vec<Vec<usize>>,
+    /// the systems each system was explicitly ordered after
+    ordering_dependencies: Vec<Vec<usize>>,
+    /// whether the stage has been prepared for the current schedule. Stages with run criteria don't necessarily run
+    /// when the schedule changes, so they are prepared the first time they run instead
+    prepared: bool,
     /// stores the indices of thread local systems in this stage, which are used during stage.prepare()
     thread_local_system_indices: Vec<usize>,
     /// When archetypes change a counter is bumped - we cache the state of that counter when it was
@@ -95,6 +122,8 @@ impl Default for ExecutorStage {
     fn default() -> Self {
         Self {
             system_dependents: Default::default(),
+            ordering_dependencies: Default::default(),
+            prepared: false,
             system_dependency_count: Default::default(),
             ready_events: Default::default(),
             ready_events_of_dependents: Default::default(),
@@ -114,7 +143,7 @@ impl ExecutorStage {
     pub fn prepare_to_next_thread_local(
         &mut self,
         world: &World,
-        systems: &mut [Box<dyn System>],
+        systems: &mut [BoxedSystem],
         schedule_changed: bool,
         next_thread_local_index: usize,
     ) -> Range<usize> {
@@ -196,6 +225,17 @@ impl ExecutorStage {
                             }
                         }
 
+                        // systems that were ordered after earlier systems in this range depend on them even if
+                        // they are compatible
+                        for &dependency in &self.ordering_dependencies[system_index] {
+                            if dependency >= prepare_system_index_range.start
+                                && !self.system_dependencies[system_index].contains(dependency)
+                            {
+                                self.system_dependents[dependency].push(system_index);
+                                self.system_dependencies[system_index].insert(dependency);
+                            }
+                        }
+
                         current_archetype_access.union(archetype_access);
                         current_resource_access.union(resource_access);
 
@@ -279,6 +319,54 @@ impl ExecutorStage {
         }
     }
 
+    fn reset(&mut self, systems: &[BoxedSystem], ordering_dependencies: &[Vec<usize>]) {
+        self.system_dependencies.clear();
+        self.system_dependencies
+            .resize_with(systems.len(), || FixedBitSet::with_capacity(systems.len()));
+
+        self.system_dependency_count.clear();
+        self.system_dependency_count.resize(systems.len(), 0);
+
+        self.thread_local_system_indices = Vec::new();
+
+        self.system_dependents.clear();
+        self.system_dependents.resize(systems.len(), Vec::new());
+
+        self.ordering_dependencies = ordering_dependencies.to_vec();
+        self.ordering_dependencies.resize(systems.len(), Vec::new());
+
+        self.ready_events.resize(systems.len(), None);
+        self.ready_events_of_dependents
+            .resize(systems.len(), Vec::new());
+
+        for (system_index, system) in systems.iter().enumerate() {
+            if system.thread_local_execution() == ThreadLocalExecution::Immediate {
+                self.thread_local_system_indices.push(system_index);
+            }
+        }
+    }
+
+    /// Computes the dependencies between `systems` the same way running the stage does. Returns the indices of the
+    /// systems each system waits for.
+    pub fn system_dependencies(
+        &mut self,
+        world: &World,
+        systems: &mut [BoxedSystem],
+        ordering_dependencies: &[Vec<usize>],
+    ) -> Vec<Vec<usize>> {
+        self.reset(systems, ordering_dependencies);
+        // the stage still needs to be prepared before it runs, as only the dependencies are computed here
+        self.prepared = false;
+        for next_thread_local_index in 0..=self.thread_local_system_indices.len() {
+            self.prepare_to_next_thread_local(world, systems, true, next_thread_local_index);
+        }
+
+        self.system_dependencies
+            .iter()
+            .map(|dependencies| dependencies.ones().collect())
+            .collect()
+    }
+
     fn reset_system_ready_events(&mut self, prepare_system_index_range: Range<usize>) {
         for system_index in prepare_system_index_range {
             let dependency_count = self.system_dependency_count[system_index];
@@ -296,7 +384,7 @@ impl ExecutorStage {
         &self,
         world: &World,
         resources: &Resources,
-        systems: &mut [Box<dyn System>],
+        systems: &mut [BoxedSystem],
         prepared_system_range: Range<usize>,
         compute_pool: &TaskPool,
     ) {
@@ -373,7 +461,7 @@ impl ExecutorStage {
                         log::trace!("run {}", system.name());
                         #[cfg(feature = "profiler")]
                         crate::profiler_start(resources, system.name().clone());
-                        system.run(world_ref, resources_ref);
+                        system.run((), world_ref, resources_ref);
                         #[cfg(feature = "profiler")]
                         crate::profiler_stop(resources, system.name().clone());
                     }
@@ -392,37 +480,20 @@ impl ExecutorStage {
         &mut self,
         world: &mut World,
         resources: &mut Resources,
-        systems: &mut [Box<dyn System>],
+        systems: &mut [BoxedSystem],
+        ordering_dependencies: &[Vec<usize>],
         schedule_changed: bool,
     ) {
         let start_archetypes_generation = world.archetypes_generation();
         let compute_pool = resources.get_cloned::<ComputeTaskPool>().unwrap();
+        let schedule_changed = schedule_changed || !self.prepared;
+        self.prepared = true;
 
         // if the schedule has changed, clear executor state / fill it with new defaults
         // This is mostly zeroing out a bunch of arrays parallel to the systems array. They will get
         // repopulated by prepare_to_next_thread_local() calls
         if schedule_changed {
-            self.system_dependencies.clear();
-            self.system_dependencies
-                .resize_with(systems.len(), || FixedBitSet::with_capacity(systems.len()));
-
-            self.system_dependency_count.clear();
-            self.system_dependency_count.resize(systems.len(), 0);
-
-            self.thread_local_system_indices = Vec::new();
-
-            self.system_dependents.clear();
-            self.system_dependents.resize(systems.len(), Vec::new());
-
-            self.ready_events.resize(systems.len(), None);
-            self.ready_events_of_dependents
-                .resize(systems.len(), Vec::new());
-
-            for (system_index, system) in systems.iter().enumerate() {
-                if system.thread_local_execution() == ThreadLocalExecution::Immediate {
-                    self.thread_local_system_indices.push(system_index);
-                }
-            }
+            self.reset(systems, ordering_dependencies);
         }
 
         // index of next thread local system in thread_local_system_indices. (always incremented by one
@@ -462,7 +533,7 @@ impl ExecutorStage {
                 // if a thread local system is ready to run, run it exclusively on the main thread
                 let system = systems[thread_local_system_index].as_mut();
                 log::trace!("running thread local system {}", system.name());
-                system.run(world, resources);
+                system.run((), world, resources);
                 system.run_thread_local(world, resources);
             }
 
@@ -509,7 +580,7 @@ mod tests {
     use super::ParallelExecutor;
     use crate::{
         resource::{Res, ResMut, Resources},
-        schedule::Schedule,
+        schedule::{IntoSystemDescriptor, Schedule},
         system::{IntoQuerySystem, IntoThreadLocalSystem, Query},
         Commands,
     };
@@ -586,6 +657,44 @@ mod tests {
         executor.run(&mut schedule, &mut world, &mut resources);
     }
 
+    #[test]
+    fn ordered_compatible_systems() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(ComputeTaskPool(TaskPool::default()));
+        resources.insert(CompletedSystems::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+
+        fn first(completed_systems: Res<CompletedSystems>) {
+            let mut completed_systems = completed_systems.completed_systems.lock();
+            assert!(!completed_systems.contains("second"));
+            completed_systems.insert("first");
+        }
+
+        fn second(completed_systems: Res<CompletedSystems>) {
+            let mut completed_systems = completed_systems.completed_systems.lock();
+            assert!(completed_systems.contains("first"));
+            completed_systems.insert("second");
+        }
+
+        schedule.add_system_to_stage("update", second.system().after("first"));
+        schedule.add_system_to_stage("update", first.system().label("first"));
+
+        let mut executor = ParallelExecutor::default();
+        for _ in 0..100 {
+            executor.run(&mut schedule, &mut world, &mut resources);
+            resources
+                .get::<CompletedSystems>()
+                .unwrap()
+                .completed_systems
+                .lock()
+                .clear();
+        }
+        assert_eq!(executor.stages[0].system_dependents, vec![vec![1], vec![]]);
+    }
+
     #[test]
     fn schedule() {
         let mut world = World::new();
@@ -0,0 +1,69 @@
+use crate::{resource::Resources, system::System};
+use bevy_hecs::World;
+
+/// Determines whether a stage in a [Schedule](crate::Schedule) runs
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ShouldRun {
+    /// The stage doesn't run
+    No,
+    /// The stage runs once
+    Yes,
+    /// The stage runs, and then the run criteria is checked again
+    YesAndLoop,
+}
+
+/// Decides whether a stage in a [Schedule](crate::Schedule) runs, and whether it runs again. Run criteria run on the
+/// main thread with exclusive access to the [World] and [Resources], before the systems in their stage.
+///
+/// Any system that returns [ShouldRun] is a run criteria, so run criteria can read resources and queries like other
+/// systems. A system that is skipped because the resources it accesses aren't available doesn't run its stage.
+pub trait RunCriteria: Send + Sync + 'static {
+    fn should_run(&mut self, world: &mut World, resources: &mut Resources) -> ShouldRun;
+    fn initialize(&mut self, _world: &mut World, _resources: &mut Resources) {}
+    /// See [System::check_change_tick]
+    fn check_change_tick(&mut self, _change_tick: u32) {}
+}
+
+impl<S> RunCriteria for S
+where
+    S: System<In = (), Out = ShouldRun>,
+{
+    fn should_run(&mut self, world: &mut World, resources: &mut Resources) -> ShouldRun {
+        self.update_archetype_access(world);
+        let should_run = self.run((), world, resources);
+        self.run_thread_local(world, resources);
+        should_run.unwrap_or(ShouldRun::No)
+    }
+
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        System::initialize(self, world, resources);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        System::check_change_tick(self, change_tick);
+    }
+}
+
+/// Runs a stage as many times as its run criteria allows. Stages without run criteria run once.
+pub(crate) fn run_stage_with_criteria(
+    mut run_criteria: Option<&mut Box<dyn RunCriteria>>,
+    world: &mut World,
+    resources: &mut Resources,
+    mut run_stage: impl FnMut(&mut World, &mut Resources),
+) {
+    loop {
+        let should_run = match &mut run_criteria {
+            Some(run_criteria) => run_criteria.should_run(world, resources),
+            None => ShouldRun::Yes,
+        };
+        if should_run == ShouldRun::No {
+            break;
+        }
+
+        run_stage(world, resources);
+
+        if should_run == ShouldRun::Yes {
+            break;
+        }
+    }
+}
@@ -1,21 +1,71 @@
+use super::{run_stage_with_criteria, RunCriteria, SystemDescriptor};
 use crate::{
     resource::Resources,
-    system::{System, SystemId, ThreadLocalExecution},
+    system::{BoxedSystem, System, SystemId, ThreadLocalExecution},
 };
 use bevy_hecs::World;
 use bevy_utils::{HashMap, HashSet};
-use std::borrow::Cow;
+use std::{borrow::Cow, cmp::Reverse, collections::BinaryHeap};
+use thiserror::Error;
+
+/// An error that occurs when the systems in a [Schedule] can't be ordered
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[error("System {system} in stage {stage} is ordered relative to the unknown label {label}.")]
+    UnknownLabel {
+        stage: Cow<'static, str>,
+        system: Cow<'static, str>,
+        label: Cow<'static, str>,
+    },
+    #[error("System {system} in stage {stage} is ordered relative to the label {label}, which only exists in stage {label_stage}. Systems can only be ordered relative to systems in the same stage.")]
+    LabelInOtherStage {
+        stage: Cow<'static, str>,
+        system: Cow<'static, str>,
+        label: Cow<'static, str>,
+        label_stage: Cow<'static, str>,
+    },
+    #[error("Systems in stage {stage} have cyclic ordering constraints: {}", .systems.join(" -> "))]
+    DependencyCycle {
+        stage: Cow<'static, str>,
+        systems: Vec<Cow<'static, str>>,
+    },
+}
+
+/// The labels and ordering constraints of a system in a [Schedule]
+#[derive(Default)]
+struct SystemOrdering {
+    /// where the system was added to its stage. Systems that aren't ordered relative to each other run in this order
+    insertion_index: isize,
+    labels: Vec<Cow<'static, str>>,
+    before: Vec<Cow<'static, str>>,
+    after: Vec<Cow<'static, str>>,
+}
 
 /// An ordered collection of stages, which each contain an ordered list of [System]s.
 /// Schedules are essentially the "execution plan" for an App's systems.
 /// They are run on a given [World] and [Resources] reference.
+///
+/// Systems in a stage run in the order they were added in, unless they were ordered with
+/// [IntoSystemDescriptor::before](super::IntoSystemDescriptor::before) and
+/// [IntoSystemDescriptor::after](super::IntoSystemDescriptor::after).
+///
+/// Stages with [RunCriteria] only run when their criteria allows it, which can be never, once or multiple times per
+/// run of the schedule.
 #[derive(Default)]
 pub struct Schedule {
-    pub(crate) stages: HashMap<Cow<'static, str>, Vec<Box<dyn System>>>,
+    pub(crate) stages: HashMap<Cow<'static, str>, Vec<BoxedSystem>>,
     pub(crate) stage_order: Vec<Cow<'static, str>>,
     pub(crate) system_ids: HashSet<SystemId>,
+    /// each stage's systems' ordering constraints, parallel to the systems in `stages`
+    stage_orderings: HashMap<Cow<'static, str>, Vec<SystemOrdering>>,
+    /// the indices of the systems each system in a stage was explicitly ordered after
+    pub(crate) stage_dependencies: HashMap<Cow<'static, str>, Vec<Vec<usize>>>,
+    pub(crate) run_criteria: HashMap<Cow<'static, str>, Box<dyn RunCriteria>>,
+    /// the number of systems that were added to the schedule, used to give each system its insertion index
+    system_insertions: isize,
     generation: usize,
     last_initialize_generation: usize,
+    last_order_generation: usize,
 }
 
 impl Schedule {
@@ -25,6 +75,7 @@ impl Schedule {
             panic!("Stage already exists: {}", stage);
         } else {
             self.stages.insert(stage.clone(), Vec::new());
+            self.stage_orderings.insert(stage.clone(), Vec::new());
             self.stage_order.push(stage);
         }
     }
@@ -49,6 +100,7 @@ impl Schedule {
             .unwrap_or_else(|| panic!("Target stage does not exist: {}", target));
 
         self.stages.insert(stage.clone(), Vec::new());
+        self.stage_orderings.insert(stage.clone(), Vec::new());
         self.stage_order.insert(target_index + 1, stage);
     }
 
@@ -72,15 +124,49 @@ impl Schedule {
             .unwrap_or_else(|| panic!("Target stage does not exist: {}", target));
 
         self.stages.insert(stage.clone(), Vec::new());
+        self.stage_orderings.insert(stage.clone(), Vec::new());
         self.stage_order.insert(target_index, stage);
     }
 
     pub fn add_system_to_stage(
         &mut self,
         stage_name: impl Into<Cow<'static, str>>,
-        system: Box<dyn System>,
+        system: impl Into<SystemDescriptor>,
     ) -> &mut Self {
         let stage_name = stage_name.into();
+        let system = system.into();
+        let index = self
+            .stages
+            .get(&stage_name)
+            .map_or(0, |systems| systems.len());
+        self.system_insertions += 1;
+        let insertion_index = self.system_insertions;
+        self.insert_system(stage_name, index, insertion_index, system)
+    }
+
+    pub fn add_system_to_stage_front(
+        &mut self,
+        stage_name: impl Into<Cow<'static, str>>,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        self.system_insertions += 1;
+        let insertion_index = -self.system_insertions;
+        self.insert_system(stage_name.into(), 0, insertion_index, system.into())
+    }
+
+    fn insert_system(
+        &mut self,
+        stage_name: Cow<'static, str>,
+        index: usize,
+        insertion_index: isize,
+        system: SystemDescriptor,
+    ) -> &mut Self {
+        let SystemDescriptor {
+            system,
+            labels,
+            before,
+            after,
+        } = system;
         let systems = self
             .stages
             .get_mut(&stage_name)
@@ -93,83 +179,82 @@ impl Schedule {
             );
         }
         self.system_ids.insert(system.id());
-        systems.push(system);
+        systems.insert(index, system);
+        self.stage_orderings.get_mut(&stage_name).unwrap().insert(
+            index,
+            SystemOrdering {
+                insertion_index,
+                labels,
+                before,
+                after,
+            },
+        );
 
         self.generation += 1;
         self
     }
 
-    pub fn add_system_to_stage_front(
+    /// Sets the [RunCriteria] that decides whether the given stage runs, replacing its previous criteria
+    pub fn set_run_criteria(
         &mut self,
         stage_name: impl Into<Cow<'static, str>>,
-        system: Box<dyn System>,
+        run_criteria: impl RunCriteria,
     ) -> &mut Self {
         let stage_name = stage_name.into();
-        let systems = self
-            .stages
-            .get_mut(&stage_name)
-            .unwrap_or_else(|| panic!("Stage does not exist: {}", stage_name));
-        if self.system_ids.contains(&system.id()) {
-            panic!(
-                "System with id {:?} ({}) already exists",
-                system.id(),
-                system.name()
-            );
+        if !self.stages.contains_key(&stage_name) {
+            panic!("Stage does not exist: {}", stage_name);
         }
-        self.system_ids.insert(system.id());
-        systems.insert(0, system);
-
+        self.run_criteria.insert(stage_name, Box::new(run_criteria));
         self.generation += 1;
         self
     }
 
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        self.ensure_system_order();
         for stage_name in self.stage_order.iter() {
             if let Some(stage_systems) = self.stages.get_mut(stage_name) {
-                for system in stage_systems.iter_mut() {
-                    #[cfg(feature = "profiler")]
-                    crate::profiler_start(resources, system.name().clone());
-                    system.update_archetype_access(world);
-                    match system.thread_local_execution() {
-                        ThreadLocalExecution::NextFlush => system.run(world, resources),
-                        ThreadLocalExecution::Immediate => {
-                            system.run(world, resources);
-                            // NOTE: when this is made parallel a full sync is required here
-                            system.run_thread_local(world, resources);
-                        }
-                    }
-                    #[cfg(feature = "profiler")]
-                    crate::profiler_stop(resources, system.name().clone());
-                }
-
-                // "flush"
-                // NOTE: when this is made parallel a full sync is required here
-                for system in stage_systems.iter_mut() {
-                    match system.thread_local_execution() {
-                        ThreadLocalExecution::NextFlush => {
-                            system.run_thread_local(world, resources)
-                        }
-                        ThreadLocalExecution::Immediate => { /* already ran immediate */ }
-                    }
-                }
+                run_stage_with_criteria(
+                    self.run_criteria.get_mut(stage_name),
+                    world,
+                    resources,
+                    |world, resources| run_stage_systems(stage_systems, world, resources),
+                );
             }
         }
 
+        self.check_change_ticks(world.change_tick());
         world.clear_trackers();
         resources.clear_trackers();
     }
 
+    /// Clamps the change ticks of all systems in the schedule. See [System::check_change_tick].
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
+        for stage in self.stages.values_mut() {
+            for system in stage.iter_mut() {
+                system.check_change_tick(change_tick);
+            }
+        }
+        for run_criteria in self.run_criteria.values_mut() {
+            run_criteria.check_change_tick(change_tick);
+        }
+    }
+
     // TODO: move this code to ParallelExecutor
     pub fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
         if self.last_initialize_generation == self.generation {
             return;
         }
 
+        self.ensure_system_order();
+
         for stage in self.stages.values_mut() {
             for system in stage.iter_mut() {
                 system.initialize(world, resources);
             }
         }
+        for run_criteria in self.run_criteria.values_mut() {
+            run_criteria.initialize(world, resources);
+        }
 
         self.last_initialize_generation = self.generation;
     }
@@ -177,4 +262,357 @@ impl Schedule {
     pub fn generation(&self) -> usize {
         self.generation
     }
+
+    /// Sorts the systems in each stage so that they satisfy their ordering constraints. Systems that aren't ordered
+    /// relative to each other run in the order they were added in, which is tracked separately from the sorted systems
+    /// so that it is kept when systems are added later. This runs automatically when the schedule is initialized or
+    /// run, but should be called once all systems were added to catch ordering errors before the schedule runs.
+    pub fn order_systems(&mut self) -> Result<(), ScheduleError> {
+        if self.last_order_generation == self.generation {
+            return Ok(());
+        }
+
+        let mut label_stages = HashMap::<&str, &Cow<'static, str>>::default();
+        for stage_name in self.stage_order.iter() {
+            for ordering in self.stage_orderings[stage_name].iter() {
+                for label in ordering.labels.iter() {
+                    label_stages.entry(label).or_insert(stage_name);
+                }
+            }
+        }
+
+        let mut stage_orders = Vec::with_capacity(self.stage_order.len());
+        for stage_name in self.stage_order.iter() {
+            let systems = &self.stages[stage_name];
+            let orderings = &self.stage_orderings[stage_name];
+            let dependencies =
+                get_system_dependencies(stage_name, systems, orderings, &label_stages)?;
+            let order = sort_systems(stage_name, systems, orderings, &dependencies)?;
+            stage_orders.push((stage_name.clone(), dependencies, order));
+        }
+
+        for (stage_name, dependencies, order) in stage_orders {
+            let systems = self.stages.get_mut(&stage_name).unwrap();
+            let orderings = self.stage_orderings.get_mut(&stage_name).unwrap();
+
+            // move the systems, their orderings and their dependencies to their sorted indices
+            let mut new_indices = vec![0; order.len()];
+            for (new_index, old_index) in order.iter().enumerate() {
+                new_indices[*old_index] = new_index;
+            }
+            let mut old_systems = systems.drain(..).map(Some).collect::<Vec<_>>();
+            let mut old_orderings = orderings.drain(..).map(Some).collect::<Vec<_>>();
+            let mut stage_dependencies = Vec::with_capacity(order.len());
+            for old_index in order {
+                systems.push(old_systems[old_index].take().unwrap());
+                orderings.push(old_orderings[old_index].take().unwrap());
+                stage_dependencies.push(
+                    dependencies[old_index]
+                        .iter()
+                        .map(|dependency| new_indices[*dependency])
+                        .collect(),
+                );
+            }
+            self.stage_dependencies
+                .insert(stage_name, stage_dependencies);
+        }
+
+        self.last_order_generation = self.generation;
+        Ok(())
+    }
+
+    pub(crate) fn ensure_system_order(&mut self) {
+        if let Err(err) = self.order_systems() {
+            panic!("{}", err);
+        }
+    }
+}
+
+fn run_stage_systems(
+    stage_systems: &mut [BoxedSystem],
+    world: &mut World,
+    resources: &mut Resources,
+) {
+    for system in stage_systems.iter_mut() {
+        #[cfg(feature = "profiler")]
+        crate::profiler_start(resources, system.name().clone());
+        system.update_archetype_access(world);
+        match system.thread_local_execution() {
+            ThreadLocalExecution::NextFlush => {
+                system.run((), world, resources);
+            }
+            ThreadLocalExecution::Immediate => {
+                system.run((), world, resources);
+                // NOTE: when this is made parallel a full sync is required here
+                system.run_thread_local(world, resources);
+            }
+        }
+        #[cfg(feature = "profiler")]
+        crate::profiler_stop(resources, system.name().clone());
+    }
+
+    // "flush"
+    // NOTE: when this is made parallel a full sync is required here
+    for system in stage_systems.iter_mut() {
+        match system.thread_local_execution() {
+            ThreadLocalExecution::NextFlush => system.run_thread_local(world, resources),
+            ThreadLocalExecution::Immediate => { /* already ran immediate */ }
+        }
+    }
+}
+
+/// Gets the indices of the systems each system must run after
+fn get_system_dependencies(
+    stage_name: &str,
+    systems: &[BoxedSystem],
+    orderings: &[SystemOrdering],
+    label_stages: &HashMap<&str, &Cow<'static, str>>,
+) -> Result<Vec<Vec<usize>>, ScheduleError> {
+    let mut label_indices = HashMap::<&str, Vec<usize>>::default();
+    for (index, ordering) in orderings.iter().enumerate() {
+        for label in ordering.labels.iter() {
+            label_indices.entry(label).or_default().push(index);
+        }
+    }
+
+    let mut dependencies = vec![Vec::new(); systems.len()];
+    for (index, ordering) in orderings.iter().enumerate() {
+        let labeled_systems = |label: &Cow<'static, str>| {
+            label_indices.get(label.as_ref()).ok_or_else(|| {
+                let stage = stage_name.to_string().into();
+                let system = systems[index].name();
+                let label = label.clone();
+                match label_stages.get(label.as_ref()) {
+                    Some(label_stage) => ScheduleError::LabelInOtherStage {
+                        stage,
+                        system,
+                        label,
+                        label_stage: (*label_stage).clone(),
+                    },
+                    None => ScheduleError::UnknownLabel {
+                        stage,
+                        system,
+                        label,
+                    },
+                }
+            })
+        };
+        for label in ordering.after.iter() {
+            for other in labeled_systems(label)? {
+                dependencies[index].push(*other);
+            }
+        }
+        for label in ordering.before.iter() {
+            for other in labeled_systems(label)? {
+                dependencies[*other].push(index);
+            }
+        }
+    }
+
+    for (index, system_dependencies) in dependencies.iter_mut().enumerate() {
+        system_dependencies.retain(|dependency| *dependency != index);
+        system_dependencies.sort_unstable();
+        system_dependencies.dedup();
+    }
+
+    Ok(dependencies)
+}
+
+/// Topologically sorts systems by their dependencies. Whenever multiple systems are ready to run, the one that was
+/// added first comes first.
+fn sort_systems(
+    stage_name: &str,
+    systems: &[BoxedSystem],
+    orderings: &[SystemOrdering],
+    dependencies: &[Vec<usize>],
+) -> Result<Vec<usize>, ScheduleError> {
+    let mut dependents = vec![Vec::new(); systems.len()];
+    let mut dependency_counts = vec![0; systems.len()];
+    for (index, system_dependencies) in dependencies.iter().enumerate() {
+        dependency_counts[index] = system_dependencies.len();
+        for dependency in system_dependencies.iter() {
+            dependents[*dependency].push(index);
+        }
+    }
+
+    let mut ready = dependency_counts
+        .iter()
+        .enumerate()
+        .filter(|(_index, count)| **count == 0)
+        .map(|(index, _)| Reverse((orderings[index].insertion_index, index)))
+        .collect::<BinaryHeap<_>>();
+    let mut order = Vec::with_capacity(systems.len());
+    while let Some(Reverse((_, index))) = ready.pop() {
+        order.push(index);
+        for dependent in dependents[index].iter() {
+            dependency_counts[*dependent] -= 1;
+            if dependency_counts[*dependent] == 0 {
+                ready.push(Reverse((orderings[*dependent].insertion_index, *dependent)));
+            }
+        }
+    }
+
+    if order.len() == systems.len() {
+        return Ok(order);
+    }
+
+    // every system that wasn't sorted waits on another unsorted system, so following those dependencies has to
+    // lead to a cycle
+    let mut path = Vec::new();
+    let mut index = (0..systems.len())
+        .find(|index| dependency_counts[*index] > 0)
+        .unwrap();
+    while !path.contains(&index) {
+        path.push(index);
+        index = *dependencies[index]
+            .iter()
+            .find(|dependency| dependency_counts[**dependency] > 0)
+            .unwrap();
+    }
+    let cycle_start = path.iter().position(|i| *i == index).unwrap();
+    let mut cycle = path[cycle_start..]
+        .iter()
+        .rev()
+        .map(|index| systems[*index].name())
+        .collect::<Vec<_>>();
+    cycle.push(cycle[0].clone());
+    Err(ScheduleError::DependencyCycle {
+        stage: stage_name.to_string().into(),
+        systems: cycle,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schedule, ScheduleError};
+    use crate::{
+        resource::{Local, Res, ResMut, Resources},
+        schedule::{IntoSystemDescriptor, ShouldRun},
+        system::IntoQuerySystem,
+    };
+    use bevy_hecs::World;
+
+    fn a(mut order: ResMut<Vec<&'static str>>) {
+        order.push("a");
+    }
+
+    fn b(mut order: ResMut<Vec<&'static str>>) {
+        order.push("b");
+    }
+
+    fn c(mut order: ResMut<Vec<&'static str>>) {
+        order.push("c");
+    }
+
+    #[test]
+    fn ordered_systems() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(Vec::<&'static str>::new());
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", a.system().label("a").after("c"));
+        schedule.add_system_to_stage("update", b.system().label("b"));
+        schedule.add_system_to_stage("update", c.system().label("c").after("b"));
+
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *resources.get::<Vec<&'static str>>().unwrap(),
+            vec!["b", "c", "a"]
+        );
+
+        // systems without constraints keep the order they were added in
+        schedule.add_system_to_stage_front("update", a.system().before("b"));
+        resources.get_mut::<Vec<&'static str>>().unwrap().clear();
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *resources.get::<Vec<&'static str>>().unwrap(),
+            vec!["a", "b", "c", "a"]
+        );
+    }
+
+    #[test]
+    fn run_criteria() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(Vec::<&'static str>::new());
+
+        resources.insert(false);
+
+        fn enabled(enabled: Res<bool>) -> ShouldRun {
+            if *enabled {
+                ShouldRun::Yes
+            } else {
+                ShouldRun::No
+            }
+        }
+
+        fn twice(mut runs: Local<usize>) -> ShouldRun {
+            *runs += 1;
+            match *runs {
+                1 => ShouldRun::YesAndLoop,
+                2 => ShouldRun::Yes,
+                _ => ShouldRun::No,
+            }
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("disabled");
+        schedule.add_stage("twice");
+        schedule.add_system_to_stage("disabled", a.system());
+        schedule.add_system_to_stage("twice", b.system());
+        schedule.set_run_criteria("disabled", enabled.system());
+        schedule.set_run_criteria("twice", twice.system());
+
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *resources.get::<Vec<&'static str>>().unwrap(),
+            vec!["b", "b"]
+        );
+
+        *resources.get_mut::<bool>().unwrap() = true;
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *resources.get::<Vec<&'static str>>().unwrap(),
+            vec!["b", "b", "a"]
+        );
+    }
+
+    #[test]
+    fn ordering_errors() {
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", a.system().label("a").after("d"));
+        match schedule.order_systems() {
+            Err(ScheduleError::UnknownLabel { label, .. }) => assert_eq!(label, "d"),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", a.system().label("a").after("c"));
+        schedule.add_system_to_stage("update", b.system().label("b").after("a"));
+        schedule.add_system_to_stage("update", c.system().label("c").after("b"));
+        match schedule.order_systems() {
+            Err(ScheduleError::DependencyCycle { systems, .. }) => assert_eq!(systems.len(), 4),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("first");
+        schedule.add_stage("second");
+        schedule.add_system_to_stage("first", a.system().label("a"));
+        schedule.add_system_to_stage("second", b.system().after("a"));
+        match schedule.order_systems() {
+            Err(ScheduleError::LabelInOtherStage { label_stage, .. }) => {
+                assert_eq!(label_stage, "first")
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
 }
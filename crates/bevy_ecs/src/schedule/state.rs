@@ -0,0 +1,376 @@
+use super::{ParallelExecutor, Schedule, SystemDescriptor};
+use crate::resource::Resources;
+use bevy_hecs::World;
+use bevy_utils::HashMap;
+use std::{collections::VecDeque, hash::Hash, mem};
+use thiserror::Error;
+
+/// An error that occurs when queueing a [State] transition
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StateError {
+    #[error("The state is already active.")]
+    AlreadyInState,
+    #[error("Can't pop the only state on the stack.")]
+    StackEmpty,
+}
+
+#[derive(Debug)]
+enum StateTransition<T> {
+    Replace(T),
+    Push(T),
+    Pop,
+}
+
+/// A resource that stores the current state of a state machine, like the screen a game is on. Systems can be
+/// registered to run when a state is entered, while it is active and when it is exited with
+/// [StateSystems::on_enter], [StateSystems::on_update] and [StateSystems::on_exit].
+///
+/// States are kept on a stack, so a state like a pause menu can be pushed on top of the current state and popped
+/// again without exiting it. Only the state at the top of the stack is active. When the state above it is popped, it
+/// becomes active again and its [StateSystems::on_resume] systems run.
+///
+/// Transitions are queued and applied in the order they were queued by [state_system], which runs the exit systems of
+/// the exited state before the enter or resume systems of the state that becomes active.
+#[derive(Debug)]
+pub struct State<T> {
+    stack: Vec<T>,
+    queued: VecDeque<StateTransition<T>>,
+    entered: bool,
+}
+
+impl<T: Clone + Eq + Hash + Send + Sync + 'static> State<T> {
+    pub fn new(initial: T) -> Self {
+        State {
+            stack: vec![initial],
+            queued: VecDeque::new(),
+            entered: false,
+        }
+    }
+
+    /// The active state, which is at the top of the stack
+    pub fn current(&self) -> &T {
+        self.stack.last().unwrap()
+    }
+
+    /// The states below the active state on the stack, from the bottom up
+    pub fn inactives(&self) -> &[T] {
+        &self.stack[..self.stack.len() - 1]
+    }
+
+    /// Queues replacing the active state with the given state. The state is checked against the active state after
+    /// the transitions that are already queued.
+    pub fn set_next(&mut self, state: T) -> Result<(), StateError> {
+        if *self.queued_stack().last().unwrap() == &state {
+            return Err(StateError::AlreadyInState);
+        }
+        self.queued.push_back(StateTransition::Replace(state));
+        Ok(())
+    }
+
+    /// Queues pushing the given state on top of the active state, which becomes inactive without being exited
+    pub fn push(&mut self, state: T) -> Result<(), StateError> {
+        if *self.queued_stack().last().unwrap() == &state {
+            return Err(StateError::AlreadyInState);
+        }
+        self.queued.push_back(StateTransition::Push(state));
+        Ok(())
+    }
+
+    /// Queues exiting the active state, which makes the state below it active again
+    pub fn pop(&mut self) -> Result<(), StateError> {
+        if self.queued_stack().len() == 1 {
+            return Err(StateError::StackEmpty);
+        }
+        self.queued.push_back(StateTransition::Pop);
+        Ok(())
+    }
+
+    /// The stack after applying the queued transitions
+    fn queued_stack(&self) -> Vec<&T> {
+        let mut stack = self.stack.iter().collect::<Vec<_>>();
+        for transition in self.queued.iter() {
+            match transition {
+                StateTransition::Replace(next) => {
+                    stack.pop();
+                    stack.push(next);
+                }
+                StateTransition::Push(next) => stack.push(next),
+                StateTransition::Pop => {
+                    stack.pop();
+                }
+            }
+        }
+        stack
+    }
+}
+
+const STATE_STAGE: &str = "state";
+
+/// A set of systems that runs in its own [Schedule]
+struct SystemSet {
+    schedule: Schedule,
+    executor: ParallelExecutor,
+}
+
+impl Default for SystemSet {
+    fn default() -> Self {
+        let mut schedule = Schedule::default();
+        schedule.add_stage(STATE_STAGE);
+        SystemSet {
+            schedule,
+            // trackers are cleared by the schedule the state_system runs in
+            executor: ParallelExecutor::without_tracker_clears(),
+        }
+    }
+}
+
+impl SystemSet {
+    fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        self.executor.initialize(resources);
+        self.schedule.initialize(world, resources);
+        self.executor.run(&mut self.schedule, world, resources);
+    }
+}
+
+#[derive(Default)]
+struct StateSystemSets {
+    enter: SystemSet,
+    update: SystemSet,
+    resume: SystemSet,
+    exit: SystemSet,
+}
+
+/// A resource that stores the systems that run for each value of a [State]
+pub struct StateSystems<T> {
+    states: HashMap<T, StateSystemSets>,
+}
+
+impl<T> Default for StateSystems<T> {
+    fn default() -> Self {
+        StateSystems {
+            states: HashMap::default(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash + Send + Sync + 'static> StateSystems<T> {
+    /// Adds a system that runs once when the given state is entered
+    pub fn on_enter(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_system(state, |sets| &mut sets.enter, system)
+    }
+
+    /// Adds a system that runs on every update while the given state is active
+    pub fn on_update(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_system(state, |sets| &mut sets.update, system)
+    }
+
+    /// Adds a system that runs once when the given state becomes active again because the state above it on the stack
+    /// was popped
+    pub fn on_resume(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_system(state, |sets| &mut sets.resume, system)
+    }
+
+    /// Adds a system that runs once when the given state is exited
+    pub fn on_exit(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_system(state, |sets| &mut sets.exit, system)
+    }
+
+    fn add_system(
+        &mut self,
+        state: T,
+        get_set: impl FnOnce(&mut StateSystemSets) -> &mut SystemSet,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        let sets = self.states.entry(state).or_default();
+        get_set(sets)
+            .schedule
+            .add_system_to_stage(STATE_STAGE, system);
+        self
+    }
+
+    fn run(
+        &mut self,
+        state: &T,
+        get_set: impl FnOnce(&mut StateSystemSets) -> &mut SystemSet,
+        world: &mut World,
+        resources: &mut Resources,
+    ) {
+        if let Some(sets) = self.states.get_mut(state) {
+            get_set(sets).run(world, resources);
+        }
+    }
+}
+
+/// Applies the queued transitions of the [State] resource and runs the systems in the [StateSystems] resource. Enter
+/// and resume systems can queue more transitions, which are applied right after them.
+pub fn state_system<T: Clone + Eq + Hash + Send + Sync + 'static>(
+    world: &mut World,
+    resources: &mut Resources,
+) {
+    let mut state_systems = mem::take(
+        &mut *resources
+            .get_mut::<StateSystems<T>>()
+            .expect("StateSystems resource does not exist"),
+    );
+
+    loop {
+        let (exited, entered, resumed) = {
+            let mut state = resources
+                .get_mut::<State<T>>()
+                .expect("State resource does not exist");
+            if !state.entered {
+                state.entered = true;
+                (None, Some(state.current().clone()), None)
+            } else {
+                match state.queued.pop_front() {
+                    Some(StateTransition::Replace(next)) => {
+                        let exited = state.stack.pop();
+                        state.stack.push(next.clone());
+                        (exited, Some(next), None)
+                    }
+                    Some(StateTransition::Push(next)) => {
+                        state.stack.push(next.clone());
+                        (None, Some(next), None)
+                    }
+                    Some(StateTransition::Pop) => {
+                        let exited = state.stack.pop();
+                        (exited, None, Some(state.current().clone()))
+                    }
+                    None => break,
+                }
+            }
+        };
+
+        if let Some(exited) = exited {
+            state_systems.run(&exited, |sets| &mut sets.exit, world, resources);
+        }
+        if let Some(entered) = entered {
+            state_systems.run(&entered, |sets| &mut sets.enter, world, resources);
+        }
+        if let Some(resumed) = resumed {
+            state_systems.run(&resumed, |sets| &mut sets.resume, world, resources);
+        }
+    }
+
+    let current = resources.get::<State<T>>().unwrap().current().clone();
+    state_systems.run(&current, |sets| &mut sets.update, world, resources);
+
+    *resources.get_mut::<StateSystems<T>>().unwrap() = state_systems;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        resource::ResMut,
+        system::{IntoQuerySystem, IntoThreadLocalSystem},
+    };
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum AppState {
+        Menu,
+        Game,
+        Pause,
+    }
+
+    #[test]
+    fn state_transitions() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(ComputeTaskPool(TaskPool::default()));
+        resources.insert(State::new(AppState::Menu));
+        resources.insert(Vec::<&'static str>::new());
+
+        let mut state_systems = StateSystems::<AppState>::default();
+        state_systems
+            .on_enter(
+                AppState::Menu,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("enter menu")).system(),
+            )
+            .on_update(
+                AppState::Menu,
+                (|mut log: ResMut<Vec<&'static str>>, mut state: ResMut<State<AppState>>| {
+                    log.push("update menu");
+                    state.set_next(AppState::Game).unwrap();
+                })
+                .system(),
+            )
+            .on_exit(
+                AppState::Menu,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("exit menu")).system(),
+            )
+            .on_enter(
+                AppState::Game,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("enter game")).system(),
+            )
+            .on_update(
+                AppState::Game,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("update game")).system(),
+            )
+            .on_resume(
+                AppState::Game,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("resume game")).system(),
+            )
+            .on_enter(
+                AppState::Pause,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("enter pause")).system(),
+            )
+            .on_exit(
+                AppState::Pause,
+                (|mut log: ResMut<Vec<&'static str>>| log.push("exit pause")).system(),
+            );
+        resources.insert(state_systems);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", state_system::<AppState>.thread_local_system());
+        let mut run = |resources: &mut Resources| {
+            schedule.initialize(&mut world, resources);
+            schedule.run(&mut world, resources);
+            mem::take(&mut *resources.get_mut::<Vec<&'static str>>().unwrap())
+        };
+
+        assert_eq!(run(&mut resources), vec!["enter menu", "update menu"]);
+        assert_eq!(
+            run(&mut resources),
+            vec!["exit menu", "enter game", "update game"]
+        );
+
+        resources
+            .get_mut::<State<AppState>>()
+            .unwrap()
+            .push(AppState::Pause)
+            .unwrap();
+        assert_eq!(run(&mut resources), vec!["enter pause"]);
+        {
+            let mut state = resources.get_mut::<State<AppState>>().unwrap();
+            assert_eq!(state.inactives(), &[AppState::Game]);
+            state.pop().unwrap();
+            assert_eq!(state.pop(), Err(StateError::StackEmpty));
+        }
+        assert_eq!(
+            run(&mut resources),
+            vec!["exit pause", "resume game", "update game"]
+        );
+
+        // transitions queued in the same update are all applied, in order
+        {
+            let mut state = resources.get_mut::<State<AppState>>().unwrap();
+            state.push(AppState::Pause).unwrap();
+            assert_eq!(state.push(AppState::Pause), Err(StateError::AlreadyInState));
+            state.pop().unwrap();
+            state.set_next(AppState::Menu).unwrap();
+        }
+        assert_eq!(
+            run(&mut resources),
+            vec![
+                "enter pause",
+                "exit pause",
+                "resume game",
+                "enter menu",
+                "update menu"
+            ]
+        );
+    }
+}
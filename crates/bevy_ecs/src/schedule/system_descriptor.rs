@@ -0,0 +1,64 @@
+use crate::system::BoxedSystem;
+use std::borrow::Cow;
+
+/// A [System] with the labels and ordering constraints it was added to a [Schedule](crate::Schedule) with. Systems
+/// in a stage are ordered relative to the systems in the same stage that have the given labels.
+pub struct SystemDescriptor {
+    pub(crate) system: BoxedSystem,
+    pub(crate) labels: Vec<Cow<'static, str>>,
+    pub(crate) before: Vec<Cow<'static, str>>,
+    pub(crate) after: Vec<Cow<'static, str>>,
+}
+
+impl From<BoxedSystem> for SystemDescriptor {
+    fn from(system: BoxedSystem) -> Self {
+        SystemDescriptor {
+            system,
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+}
+
+/// Labels a [System] and orders it relative to other labeled systems in the same stage
+pub trait IntoSystemDescriptor {
+    /// Adds a label that other systems in the same stage can be ordered relative to. Multiple systems can share a
+    /// label.
+    fn label(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor;
+    /// Runs the system before all systems in the same stage with the given label
+    fn before(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor;
+    /// Runs the system after all systems in the same stage with the given label
+    fn after(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor;
+}
+
+impl IntoSystemDescriptor for SystemDescriptor {
+    fn label(mut self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        self.labels.push(label.into());
+        self
+    }
+
+    fn before(mut self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        self.before.push(label.into());
+        self
+    }
+
+    fn after(mut self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        self.after.push(label.into());
+        self
+    }
+}
+
+impl IntoSystemDescriptor for BoxedSystem {
+    fn label(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        SystemDescriptor::from(self).label(label)
+    }
+
+    fn before(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        SystemDescriptor::from(self).before(label)
+    }
+
+    fn after(self, label: impl Into<Cow<'static, str>>) -> SystemDescriptor {
+        SystemDescriptor::from(self).after(label)
+    }
+}
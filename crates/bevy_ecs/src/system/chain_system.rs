@@ -0,0 +1,106 @@
+use crate::{
+    resource::Resources,
+    system::{ArchetypeAccess, BoxedSystem, System, SystemId, ThreadLocalExecution, TypeAccess},
+};
+use bevy_hecs::World;
+use std::borrow::Cow;
+
+/// A [System] that passes the output of `system_a` to `system_b` as its input. Created with
+/// [IntoChainSystem::chain].
+pub struct ChainSystem<SystemA, SystemB> {
+    system_a: SystemA,
+    system_b: SystemB,
+    name: Cow<'static, str>,
+    id: SystemId,
+    archetype_access: ArchetypeAccess,
+    resource_access: TypeAccess,
+}
+
+impl<SystemA, SystemB> System for ChainSystem<SystemA, SystemB>
+where
+    SystemA: System,
+    SystemB: System<In = SystemA::Out>,
+{
+    type In = SystemA::In;
+    type Out = SystemB::Out;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn update_archetype_access(&mut self, world: &World) {
+        self.system_a.update_archetype_access(world);
+        self.system_b.update_archetype_access(world);
+        self.archetype_access.clear();
+        self.archetype_access
+            .union(self.system_a.archetype_access());
+        self.archetype_access
+            .union(self.system_b.archetype_access());
+    }
+
+    fn archetype_access(&self) -> &ArchetypeAccess {
+        &self.archetype_access
+    }
+
+    fn resource_access(&self) -> &TypeAccess {
+        &self.resource_access
+    }
+
+    fn thread_local_execution(&self) -> ThreadLocalExecution {
+        // the chain runs on the main thread if either of its systems does
+        if self.system_a.thread_local_execution() == ThreadLocalExecution::Immediate
+            || self.system_b.thread_local_execution() == ThreadLocalExecution::Immediate
+        {
+            ThreadLocalExecution::Immediate
+        } else {
+            ThreadLocalExecution::NextFlush
+        }
+    }
+
+    fn run(&mut self, input: Self::In, world: &World, resources: &Resources) -> Option<Self::Out> {
+        let output = self.system_a.run(input, world, resources)?;
+        self.system_b.run(output, world, resources)
+    }
+
+    fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources) {
+        self.system_a.run_thread_local(world, resources);
+        self.system_b.run_thread_local(world, resources);
+    }
+
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        self.system_a.initialize(world, resources);
+        self.system_b.initialize(world, resources);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system_a.check_change_tick(change_tick);
+        self.system_b.check_change_tick(change_tick);
+    }
+}
+
+/// Chains a [System] to another system that takes its output as input
+pub trait IntoChainSystem: System + Sized {
+    /// Creates a single system that runs `self` and then runs `system` with the output of `self`. If `self` is
+    /// skipped, `system` is skipped too. The chained systems access the data of both systems.
+    fn chain<SystemB>(self, system: SystemB) -> BoxedSystem<Self::In, SystemB::Out>
+    where
+        SystemB: System<In = Self::Out>,
+    {
+        let mut resource_access = self.resource_access().clone();
+        resource_access.union(system.resource_access());
+        Box::new(ChainSystem {
+            name: format!("Chain({}, {})", self.name(), system.name()).into(),
+            id: SystemId::new(),
+            archetype_access: ArchetypeAccess::default(),
+            resource_access,
+            system_a: self,
+            system_b: system,
+        })
+    }
+}
+
+impl<S: System> IntoChainSystem for S {}
@@ -2,19 +2,37 @@ use super::SystemId;
 use crate::resource::{Resource, Resources};
 use bevy_hecs::{Bundle, Component, DynamicBundle, Entity, EntityReserver, World};
 use parking_lot::Mutex;
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
 
 /// A queued command to mutate the current [World] or [Resources]
 pub enum Command {
     WriteWorld(Box<dyn WorldWriter>),
     WriteResources(Box<dyn ResourcesWriter>),
+    Write(CommandFn),
 }
 
-/// A [World] mutation
+/// A closure queued with [Commands::add]
+pub type CommandFn = Box<dyn FnOnce(&mut World, &mut Resources) + Send + Sync>;
+
+/// A [World] mutation. Closures taking the world are world mutations too, so they can be queued with
+/// [Commands::write_world].
 pub trait WorldWriter: Send + Sync {
     fn write(self: Box<Self>, world: &mut World);
 }
 
+impl<F> WorldWriter for F
+where
+    F: FnOnce(&mut World) + Send + Sync,
+{
+    fn write(self: Box<Self>, world: &mut World) {
+        (*self)(world)
+    }
+}
+
 pub(crate) struct Spawn<T>
 where
     T: DynamicBundle + Send + Sync + 'static,
@@ -112,10 +130,39 @@ where
     }
 }
 
+pub(crate) struct Remove<T>
+where
+    T: Bundle + Send + Sync + 'static,
+{
+    entity: Entity,
+    phantom: PhantomData<T>,
+}
+
+impl<T> WorldWriter for Remove<T>
+where
+    T: Bundle + Send + Sync + 'static,
+{
+    fn write(self: Box<Self>, world: &mut World) {
+        // like `World::remove`, nothing is removed unless the entity has every component of the bundle
+        let _ = world.remove::<T>(self.entity);
+    }
+}
+
+/// A [Resources] mutation. Like [WorldWriter], closures taking the resources can be queued with
+/// [Commands::write_resources].
 pub trait ResourcesWriter: Send + Sync {
     fn write(self: Box<Self>, resources: &mut Resources);
 }
 
+impl<F> ResourcesWriter for F
+where
+    F: FnOnce(&mut Resources) + Send + Sync,
+{
+    fn write(self: Box<Self>, resources: &mut Resources) {
+        (*self)(resources)
+    }
+}
+
 pub struct InsertResource<T: Resource> {
     resource: T,
 }
@@ -201,12 +248,28 @@ pub struct Commands {
 }
 
 impl Commands {
-    pub fn spawn(&mut self, components: impl DynamicBundle + Send + Sync + 'static) -> &mut Self {
-        {
+    /// Spawns an entity with the given components, and returns an [EntityCommands] bound to it
+    pub fn spawn(
+        &mut self,
+        components: impl DynamicBundle + Send + Sync + 'static,
+    ) -> EntityCommands<'_> {
+        let entity = {
             let mut commands = self.commands.lock();
             commands.spawn(components);
+            commands.current_entity.unwrap()
+        };
+        EntityCommands {
+            entity,
+            commands: self,
+        }
+    }
+
+    /// Returns an [EntityCommands] bound to an existing entity
+    pub fn entity(&mut self, entity: Entity) -> EntityCommands<'_> {
+        EntityCommands {
+            entity,
+            commands: self,
         }
-        self
     }
 
     pub fn spawn_batch<I>(&mut self, components_iter: I) -> &mut Self
@@ -281,6 +344,18 @@ impl Commands {
         self
     }
 
+    /// Queues a closure that mutates the [World] and [Resources] directly
+    pub fn add(
+        &mut self,
+        command: impl FnOnce(&mut World, &mut Resources) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.commands
+            .lock()
+            .commands
+            .push(Command::Write(Box::new(command)));
+        self
+    }
+
     pub fn apply(&self, world: &mut World, resources: &mut Resources) {
         let mut commands = self.commands.lock();
         for command in commands.commands.drain(..) {
@@ -289,6 +364,7 @@ impl Commands {
                     writer.write(world);
                 }
                 Command::WriteResources(writer) => writer.write(resources),
+                Command::Write(command) => command(world, resources),
             }
         }
     }
@@ -319,11 +395,99 @@ impl Commands {
         })
     }
 
+    /// Removes the components of the bundle `T` from `entity`. Like [World::remove], this is all or nothing: if the
+    /// entity lacks any of the components, none of them are removed.
+    pub fn remove<T>(&mut self, entity: Entity) -> &mut Self
+    where
+        T: Bundle + Send + Sync + 'static,
+    {
+        self.write_world(Remove::<T> {
+            entity,
+            phantom: PhantomData,
+        })
+    }
+
     pub fn set_entity_reserver(&self, entity_reserver: EntityReserver) {
         self.commands.lock().entity_reserver = Some(entity_reserver);
     }
 }
 
+/// Queues commands for a single entity. Returned by [Commands::spawn] and [Commands::entity].
+///
+/// Dereferences to the [Commands] it was created from, so commands for other entities can be chained.
+pub struct EntityCommands<'a> {
+    entity: Entity,
+    commands: &'a mut Commands,
+}
+
+impl<'a> EntityCommands<'a> {
+    /// The entity the commands are queued for
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn commands(&mut self) -> &mut Commands {
+        self.commands
+    }
+
+    pub fn insert(&mut self, components: impl DynamicBundle + Send + Sync + 'static) -> &mut Self {
+        self.commands.insert(self.entity, components);
+        self
+    }
+
+    pub fn insert_one(&mut self, component: impl Component) -> &mut Self {
+        self.commands.insert_one(self.entity, component);
+        self
+    }
+
+    pub fn with(&mut self, component: impl Component) -> &mut Self {
+        self.insert_one(component)
+    }
+
+    pub fn with_bundle(
+        &mut self,
+        components: impl DynamicBundle + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.insert(components)
+    }
+
+    /// Removes the components of the bundle `T` from the entity, if it has all of them. See [Commands::remove].
+    pub fn remove<T>(&mut self) -> &mut Self
+    where
+        T: Bundle + Send + Sync + 'static,
+    {
+        self.commands.remove::<T>(self.entity);
+        self
+    }
+
+    pub fn remove_one<T>(&mut self) -> &mut Self
+    where
+        T: Component,
+    {
+        self.commands.remove_one::<T>(self.entity);
+        self
+    }
+
+    pub fn despawn(&mut self) -> &mut Self {
+        self.commands.despawn(self.entity);
+        self
+    }
+}
+
+impl<'a> Deref for EntityCommands<'a> {
+    type Target = Commands;
+
+    fn deref(&self) -> &Commands {
+        self.commands
+    }
+}
+
+impl<'a> DerefMut for EntityCommands<'a> {
+    fn deref_mut(&mut self) -> &mut Commands {
+        self.commands
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Commands;
@@ -347,4 +511,36 @@ mod tests {
         assert_eq!(results, vec![(1u32, 2u64)]);
         assert_eq!(*resources.get::<f32>().unwrap(), 3.14f32);
     }
+
+    #[test]
+    fn entity_commands() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+        let a = commands.spawn((1u32,)).id();
+        let b = commands.spawn((2u32, 2u64)).id();
+        commands.entity(a).with(1u64).insert((true, 1.0f32));
+        commands.entity(b).remove::<(u32, u64)>().with(false);
+        // `a` has no `i64`, so its `u32` is kept
+        commands.entity(a).remove::<(u32, i64)>();
+        let c = commands.spawn((3u32,)).despawn().id();
+        commands.write_world(move |world: &mut World| {
+            *world.get_mut::<u32>(a).unwrap() += 1;
+        });
+        commands.write_resources(|resources: &mut Resources| resources.insert(1u32));
+        commands.add(move |world, resources| {
+            resources.insert(*world.get::<u64>(a).unwrap());
+        });
+        commands.apply(&mut world, &mut resources);
+
+        assert_eq!(*world.get::<u32>(a).unwrap(), 2);
+        assert_eq!(*world.get::<u64>(a).unwrap(), 1);
+        assert!(*world.get::<bool>(a).unwrap());
+        assert!(world.get::<u32>(b).is_err());
+        assert!(!*world.get::<bool>(b).unwrap());
+        assert!(!world.contains(c));
+        assert_eq!(*resources.get::<u32>().unwrap(), 1);
+        assert_eq!(*resources.get::<u64>().unwrap(), 1);
+    }
 }
@@ -0,0 +1,142 @@
+use crate::{
+    resource::Resources,
+    system::{ArchetypeAccess, BoxedSystem, System, SystemId, ThreadLocalExecution, TypeAccess},
+};
+use bevy_hecs::World;
+use std::{any::TypeId, borrow::Cow, error::Error, fmt};
+
+/// An error returned by a fallible system
+#[derive(Debug)]
+pub struct SystemError {
+    /// The name of the system that returned the error
+    pub system_name: Cow<'static, str>,
+    pub error: Box<dyn Error + Send + Sync>,
+}
+
+impl fmt::Display for SystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "system {} failed: {}", self.system_name, self.error)
+    }
+}
+
+/// A resource that receives the [SystemError]s of the systems created with [IntoFallibleSystem::handle_errors].
+/// The default handler logs the errors. Replace the resource to handle errors differently. If the resource is missing,
+/// errors are logged like the default handler does.
+pub struct SystemErrorHandler {
+    handler: Box<dyn Fn(SystemError) + Send + Sync>,
+}
+
+impl SystemErrorHandler {
+    pub fn new(handler: impl Fn(SystemError) + Send + Sync + 'static) -> Self {
+        SystemErrorHandler {
+            handler: Box::new(handler),
+        }
+    }
+
+    pub fn handle(&self, error: SystemError) {
+        (self.handler)(error)
+    }
+}
+
+impl Default for SystemErrorHandler {
+    fn default() -> Self {
+        SystemErrorHandler::new(log_error)
+    }
+}
+
+fn log_error(error: SystemError) {
+    log::error!("{}", error);
+}
+
+/// A [System] that passes the errors returned by a fallible system to the [SystemErrorHandler]. Created with
+/// [IntoFallibleSystem::handle_errors].
+pub struct FallibleSystem<S> {
+    system: S,
+    resource_access: TypeAccess,
+}
+
+impl<S, E> System for FallibleSystem<S>
+where
+    S: System<Out = Result<(), E>>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    type In = S::In;
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn id(&self) -> SystemId {
+        self.system.id()
+    }
+
+    fn update_archetype_access(&mut self, world: &World) {
+        self.system.update_archetype_access(world);
+    }
+
+    fn archetype_access(&self) -> &ArchetypeAccess {
+        self.system.archetype_access()
+    }
+
+    fn resource_access(&self) -> &TypeAccess {
+        &self.resource_access
+    }
+
+    fn thread_local_execution(&self) -> ThreadLocalExecution {
+        self.system.thread_local_execution()
+    }
+
+    fn run(&mut self, input: Self::In, world: &World, resources: &Resources) -> Option<()> {
+        if let Err(error) = self.system.run(input, world, resources)? {
+            let error = SystemError {
+                system_name: self.system.name(),
+                error: error.into(),
+            };
+            match resources.get::<SystemErrorHandler>() {
+                Some(handler) => handler.handle(error),
+                None => log_error(error),
+            }
+        }
+        Some(())
+    }
+
+    fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources) {
+        self.system.run_thread_local(world, resources);
+    }
+
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        if !resources.contains::<SystemErrorHandler>() {
+            resources.insert(SystemErrorHandler::default());
+        }
+        self.system.initialize(world, resources);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system.check_change_tick(change_tick);
+    }
+}
+
+/// Handles the errors returned by a [System] that outputs a [Result]
+pub trait IntoFallibleSystem<E>: System<Out = Result<(), E>> + Sized {
+    /// Passes the errors returned by the system to the [SystemErrorHandler] resource instead of returning them.
+    /// The handler is inserted when the system is initialized if it doesn't exist yet.
+    fn handle_errors(self) -> BoxedSystem<Self::In, ()>;
+}
+
+impl<S, E> IntoFallibleSystem<E> for S
+where
+    S: System<Out = Result<(), E>>,
+    E: Into<Box<dyn Error + Send + Sync>> + 'static,
+{
+    fn handle_errors(self) -> BoxedSystem<Self::In, ()> {
+        let mut resource_access = self.resource_access().clone();
+        resource_access
+            .immutable
+            .insert(TypeId::of::<SystemErrorHandler>());
+        Box::new(FallibleSystem {
+            system: self,
+            resource_access,
+        })
+    }
+}
@@ -2,14 +2,33 @@ pub use super::Query;
 use super::TypeAccess;
 use crate::{
     resource::{FetchResource, ResourceQuery, Resources, UnsafeClone},
-    system::{ArchetypeAccess, Commands, System, SystemId, ThreadLocalExecution},
+    system::{
+        ArchetypeAccess, BoxedSystem, Commands, System, SystemChangeTicks, SystemId,
+        ThreadLocalExecution,
+    },
 };
-use bevy_hecs::{Fetch, Query as HecsQuery, World};
-use std::borrow::Cow;
+use bevy_hecs::{
+    ChangeTicks, Fetch, Filtered, Query as HecsQuery, QueryFilter, World, MAX_CHANGE_AGE,
+};
+use std::{borrow::Cow, marker::PhantomData};
+
+/// The input of a system, passed to it by the system it is chained to with
+/// [IntoChainSystem](super::IntoChainSystem). A query system takes its input as its first parameter.
+#[derive(Debug)]
+pub struct In<T>(pub T);
 
-pub(crate) struct SystemFn<State, F, ThreadLocalF, Init, SetArchetypeAccess>
+pub(crate) struct SystemFn<In, Out, State, F, ThreadLocalF, Init, SetArchetypeAccess>
 where
-    F: FnMut(&World, &Resources, &ArchetypeAccess, &mut State) + Send + Sync,
+    F: FnMut(
+            In,
+            &World,
+            &Resources,
+            &ArchetypeAccess,
+            SystemChangeTicks,
+            &mut State,
+        ) -> Option<Out>
+        + Send
+        + Sync,
     ThreadLocalF: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync,
     Init: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync,
     SetArchetypeAccess: FnMut(&World, &mut ArchetypeAccess, &mut State) + Send + Sync,
@@ -25,17 +44,35 @@ where
     pub id: SystemId,
     pub archetype_access: ArchetypeAccess,
     pub set_archetype_access: SetArchetypeAccess,
+    pub last_change_tick: u32,
+    pub last_resource_change_tick: u32,
+    pub marker: PhantomData<fn(In) -> Out>,
 }
 
-impl<State, F, ThreadLocalF, Init, SetArchetypeAccess> System
-    for SystemFn<State, F, ThreadLocalF, Init, SetArchetypeAccess>
+impl<In, Out, State, F, ThreadLocalF, Init, SetArchetypeAccess> System
+    for SystemFn<In, Out, State, F, ThreadLocalF, Init, SetArchetypeAccess>
 where
-    F: FnMut(&World, &Resources, &ArchetypeAccess, &mut State) + Send + Sync,
-    ThreadLocalF: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync,
-    Init: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync,
-    SetArchetypeAccess: FnMut(&World, &mut ArchetypeAccess, &mut State) + Send + Sync,
-    State: Send + Sync,
+    In: 'static,
+    Out: 'static,
+    F: FnMut(
+            In,
+            &World,
+            &Resources,
+            &ArchetypeAccess,
+            SystemChangeTicks,
+            &mut State,
+        ) -> Option<Out>
+        + Send
+        + Sync
+        + 'static,
+    ThreadLocalF: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync + 'static,
+    Init: FnMut(&mut World, &mut Resources, &mut State) + Send + Sync + 'static,
+    SetArchetypeAccess: FnMut(&World, &mut ArchetypeAccess, &mut State) + Send + Sync + 'static,
+    State: Send + Sync + 'static,
 {
+    type In = In;
+    type Out = Out;
+
     fn name(&self) -> Cow<'static, str> {
         self.name.clone()
     }
@@ -57,8 +94,32 @@ where
     }
 
     #[inline]
-    fn run(&mut self, world: &World, resources: &Resources) {
-        (self.func)(world, resources, &self.archetype_access, &mut self.state);
+    fn run(&mut self, input: In, world: &World, resources: &Resources) -> Option<Out> {
+        let change_tick = world.increment_change_tick();
+        let resource_change_tick = resources.increment_change_tick();
+        // resource ticks aren't clamped by the schedule, so they are clamped here before they are compared
+        if resource_change_tick.wrapping_sub(self.last_resource_change_tick) > MAX_CHANGE_AGE {
+            self.last_resource_change_tick = resource_change_tick.wrapping_sub(MAX_CHANGE_AGE);
+        }
+        let ticks = SystemChangeTicks {
+            world: ChangeTicks::new(self.last_change_tick, change_tick),
+            resources: ChangeTicks::new(self.last_resource_change_tick, resource_change_tick),
+        };
+        // a system that is skipped because its resources aren't available keeps its last change tick, so it still
+        // detects the changes made before it actually runs
+        let output = (self.func)(
+            input,
+            world,
+            resources,
+            &self.archetype_access,
+            ticks,
+            &mut self.state,
+        );
+        if output.is_some() {
+            self.last_change_tick = change_tick;
+            self.last_resource_change_tick = resource_change_tick;
+        }
+        output
     }
 
     fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources) {
@@ -69,6 +130,12 @@ where
         (self.init_func)(world, resources, &mut self.state);
     }
 
+    fn check_change_tick(&mut self, change_tick: u32) {
+        if change_tick.wrapping_sub(self.last_change_tick) > MAX_CHANGE_AGE {
+            self.last_change_tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
+        }
+    }
+
     fn id(&self) -> SystemId {
         self.id
     }
@@ -76,7 +143,7 @@ where
 
 /// Converts `Self` into a For-Each system
 pub trait IntoForEachSystem<CommandBuffer, R, C> {
-    fn system(self) -> Box<dyn System>;
+    fn system(self) -> BoxedSystem;
 }
 
 macro_rules! impl_into_foreach_system {
@@ -96,23 +163,24 @@ macro_rules! impl_into_foreach_system {
             #[allow(non_snake_case)]
             #[allow(unused_variables)]
             #[allow(unused_unsafe)]
-            fn system(mut self) -> Box<dyn System> {
+            fn system(mut self) -> BoxedSystem {
                 let id = SystemId::new();
                 Box::new(SystemFn {
                     state: Commands::default(),
                     thread_local_execution: ThreadLocalExecution::NextFlush,
                     name: core::any::type_name::<Self>().into(),
                     id,
-                    func: move |world, resources, _archetype_access, state| {
-                        {
-                            if let Some(($($resource,)*)) = resources.query_system::<($($resource,)*)>(id) {
-                                // SAFE: the scheduler has ensured that there is no archetype clashing here
-                                unsafe {
-                                    for ($($component,)*) in world.query_unchecked::<($($component,)*)>().iter() {
-                                        fn_call!(self, ($($commands, state)*), ($($resource),*), ($($component),*))
-                                    }
+                    func: move |_input, world, resources, _archetype_access, ticks, state| {
+                        if let Some(($($resource,)*)) = resources.query_system::<($($resource,)*)>(world, id, ticks) {
+                            // SAFE: the scheduler has ensured that there is no archetype clashing here
+                            unsafe {
+                                for ($($component,)*) in world.query_unchecked_with_ticks::<($($component,)*)>(ticks.world).iter() {
+                                    fn_call!(self, (), ($($commands, state)*), ($($resource),*), ($($component),*))
                                 }
                             }
+                            Some(())
+                        } else {
+                            None
                         }
                     },
                     thread_local_func: move |world, resources, state| {
@@ -128,6 +196,9 @@ macro_rules! impl_into_foreach_system {
                         archetype_access.clear();
                         archetype_access.set_access_for_query::<($($component,)*)>(world);
                     },
+                    last_change_tick: 0,
+                    last_resource_change_tick: 0,
+                    marker: PhantomData,
                 })
             }
         }
@@ -139,30 +210,41 @@ struct QuerySystemState {
     commands: Commands,
 }
 
-/// Converts `Self` into a Query System
-pub trait IntoQuerySystem<Commands, R, Q> {
-    fn system(self) -> Box<dyn System>;
+/// Converts `Self` into a Query System. A query system can take an [In] parameter before its other parameters and
+/// return a value, which are the input and output of the system.
+pub trait IntoQuerySystem<Input, Commands, R, Q> {
+    type In;
+    type Out;
+    fn system(self) -> BoxedSystem<Self::In, Self::Out>;
 }
 
 macro_rules! impl_into_query_system {
-    (($($commands: ident)*), ($($resource: ident),*), ($($query: ident),*)) => {
-        impl<Func, $($resource,)* $($query,)*> IntoQuerySystem<($($commands,)*), ($($resource,)*), ($($query,)*)> for Func where
+    (($($input: ident)*), ($($commands: ident)*), ($($resource: ident),*), ($($query: ident),*), ($($filter: ident),*)) => {
+        impl<Func, Out, $($input,)* $($resource,)* $($query,)* $($filter,)*> IntoQuerySystem<($(In<$input>,)*), ($($commands,)*), ($($resource,)*), ($(($query, $filter),)*)> for Func where
             Func:
-                FnMut($($commands,)* $($resource,)* $(Query<$query>,)*) +
+                FnMut($(In<$input>,)* $($commands,)* $($resource,)* $(Query<$query, $filter>,)*) -> Out +
                 FnMut(
+                    $(In<$input>,)*
                     $($commands,)*
                     $(<<$resource as ResourceQuery>::Fetch as FetchResource>::Item,)*
-                    $(Query<$query>,)*) +
+                    $(Query<$query, $filter>,)*) -> Out +
                 Send + Sync +'static,
+            Out: 'static,
+            $($input: 'static,)*
             $($query: HecsQuery,)*
+            $($filter: QueryFilter,)*
             $($resource: ResourceQuery,)*
         {
+            #[allow(unused_parens)]
+            type In = ($($input)*);
+            type Out = Out;
+
             #[allow(non_snake_case)]
             #[allow(unused_variables)]
             #[allow(unused_unsafe)]
             #[allow(unused_assignments)]
             #[allow(unused_mut)]
-            fn system(mut self) -> Box<dyn System> {
+            fn system(mut self) -> BoxedSystem<Self::In, Self::Out> {
                 let id = SystemId::new();
                 $(let $query = ArchetypeAccess::default();)*
                 Box::new(SystemFn {
@@ -175,18 +257,18 @@ macro_rules! impl_into_query_system {
                     thread_local_execution: ThreadLocalExecution::NextFlush,
                     id,
                     name: core::any::type_name::<Self>().into(),
-                    func: move |world, resources, archetype_access, state| {
-                        {
-                            if let Some(($($resource,)*)) = resources.query_system::<($($resource,)*)>(id) {
-                                let mut i = 0;
-                                $(
-                                    let $query = Query::<$query>::new(world, &state.archetype_accesses[i]);
-                                    i += 1;
-                                )*
-
-                                let commands = &state.commands;
-                                fn_call!(self, ($($commands, commands)*), ($($resource),*), ($($query),*))
-                            }
+                    func: move |input, world, resources, archetype_access, ticks, state| {
+                        if let Some(($($resource,)*)) = resources.query_system::<($($resource,)*)>(world, id, ticks) {
+                            let mut i = 0;
+                            $(
+                                let $query = Query::<$query, $filter>::new(world, &state.archetype_accesses[i], ticks.world);
+                                i += 1;
+                            )*
+
+                            let commands = &state.commands;
+                            Some(fn_call!(self, ($(In::<$input>(input)),*), ($($commands, commands)*), ($($resource),*), ($($query),*)))
+                        } else {
+                            None
                         }
                     },
                     thread_local_func: move |world, resources, state| {
@@ -206,11 +288,14 @@ macro_rules! impl_into_query_system {
                         $(
                             access = &mut state.archetype_accesses[i];
                             access.clear();
-                            access.set_access_for_query::<$query>(world);
+                            access.set_access_for_query::<Filtered<$query, $filter>>(world);
                             archetype_access.union(access);
                             i += 1;
                          )*
                     },
+                    last_change_tick: 0,
+                    last_resource_change_tick: 0,
+                    marker: PhantomData,
                 })
             }
         }
@@ -218,20 +303,24 @@ macro_rules! impl_into_query_system {
 }
 
 macro_rules! fn_call {
-    ($self:ident, ($($commands: ident, $commands_var: ident)*), ($($resource: ident),*), ($($a: ident),*)) => {
-        unsafe { $self($($commands_var.clone(),)* $($resource.unsafe_clone(),)* $($a,)*) }
+    ($self:ident, ($($input: expr),*), ($($commands: ident, $commands_var: ident)*), ($($resource: ident),*), ($($a: ident),*)) => {
+        unsafe { $self($($input,)* $($commands_var.clone(),)* $($resource.unsafe_clone(),)* $($a,)*) }
     };
-    ($self:ident, (), ($($resource: ident),*), ($($a: ident),*)) => {
-        unsafe { $self($($resource.unsafe_clone(),)* $($a,)*) }
+    ($self:ident, ($($input: expr),*), (), ($($resource: ident),*), ($($a: ident),*)) => {
+        unsafe { $self($($input,)* $($resource.unsafe_clone(),)* $($a,)*) }
     };
 }
 
 macro_rules! impl_into_query_systems {
-    (($($resource: ident,)*), ($($query: ident),*)) => {
+    (($($resource: ident,)*), ($($query: ident),*), ($($filter: ident),*)) => {
         #[rustfmt::skip]
-        impl_into_query_system!((), ($($resource),*), ($($query),*));
+        impl_into_query_system!((), (), ($($resource),*), ($($query),*), ($($filter),*));
         #[rustfmt::skip]
-        impl_into_query_system!((Commands), ($($resource),*), ($($query),*));
+        impl_into_query_system!((), (Commands), ($($resource),*), ($($query),*), ($($filter),*));
+        #[rustfmt::skip]
+        impl_into_query_system!((Input), (), ($($resource),*), ($($query),*), ($($filter),*));
+        #[rustfmt::skip]
+        impl_into_query_system!((Input), (Commands), ($($resource),*), ($($query),*), ($($filter),*));
     }
 }
 
@@ -264,19 +353,19 @@ macro_rules! impl_into_systems {
         impl_into_foreach_systems!(($($resource,)*), (A,B,C,D,E,F,G,H));
 
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), ());
+        impl_into_query_systems!(($($resource,)*), (), ());
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A));
+        impl_into_query_systems!(($($resource,)*), (A), (FA));
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A,B));
+        impl_into_query_systems!(($($resource,)*), (A,B), (FA,FB));
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A,B,C));
+        impl_into_query_systems!(($($resource,)*), (A,B,C), (FA,FB,FC));
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A,B,C,D));
+        impl_into_query_systems!(($($resource,)*), (A,B,C,D), (FA,FB,FC,FD));
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A,B,C,D,E));
+        impl_into_query_systems!(($($resource,)*), (A,B,C,D,E), (FA,FB,FC,FD,FE));
         #[rustfmt::skip]
-        impl_into_query_systems!(($($resource,)*), (A,B,C,D,E,F));
+        impl_into_query_systems!(($($resource,)*), (A,B,C,D,E,F), (FA,FB,FC,FD,FE,FF));
     };
 }
 
@@ -305,20 +394,20 @@ impl_into_systems!(Ra,Rb,Rc,Rd,Re,Rf,Rg,Rh,Ri,Rj);
 
 /// Converts `Self` into a thread local system
 pub trait IntoThreadLocalSystem {
-    fn thread_local_system(self) -> Box<dyn System>;
+    fn thread_local_system(self) -> BoxedSystem;
 }
 
 impl<F> IntoThreadLocalSystem for F
 where
     F: ThreadLocalSystemFn,
 {
-    fn thread_local_system(mut self) -> Box<dyn System> {
+    fn thread_local_system(mut self) -> BoxedSystem {
         Box::new(SystemFn {
             state: (),
             thread_local_func: move |world, resources, _| {
                 self.run(world, resources);
             },
-            func: |_, _, _, _| {},
+            func: |_, _, _, _, _, _| Some(()),
             init_func: |_, _, _| {},
             set_archetype_access: |_, _, _| {},
             thread_local_execution: ThreadLocalExecution::Immediate,
@@ -326,6 +415,9 @@ where
             id: SystemId::new(),
             resource_access: TypeAccess::default(),
             archetype_access: ArchetypeAccess::default(),
+            last_change_tick: 0,
+            last_resource_change_tick: 0,
+            marker: PhantomData,
         })
     }
 }
@@ -346,13 +438,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{IntoForEachSystem, IntoQuerySystem, Query};
+    use super::{In, IntoForEachSystem, IntoQuerySystem, Query};
     use crate::{
-        resource::{ResMut, Resources},
-        schedule::Schedule,
+        resource::{Res, ResMut, Resources},
+        schedule::{IntoSystemDescriptor, Schedule},
+        system::{IntoChainSystem, IntoFallibleSystem, SystemErrorHandler},
         ChangedRes, Mut,
     };
-    use bevy_hecs::{Entity, With, World};
+    use bevy_hecs::{Changed, Entity, StorageType, With, Without, World};
 
     struct A;
     struct B;
@@ -421,6 +514,84 @@ mod tests {
         assert!(*resources.get::<bool>().unwrap(), "system ran");
     }
 
+    #[test]
+    fn filtered_query_system() {
+        fn count_moved_players(
+            mut count: ResMut<usize>,
+            mut query: Query<&A, (With<B>, Changed<i32>)>,
+        ) {
+            *count += query.iter().iter().count();
+        }
+
+        fn increment(mut i: Mut<i32>) {
+            *i += 1;
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0usize);
+        let player = world.spawn((A, B, 0));
+        world.spawn((A, B));
+        world.spawn((A, 0));
+        world.spawn((B, C, 0));
+
+        let mut system = count_moved_players.system();
+        system.initialize(&mut world, &mut resources);
+        system.update_archetype_access(&world);
+        let access = system.archetype_access();
+        assert_eq!(
+            access.immutable.ones().collect::<Vec<_>>(),
+            vec![world.get_entity_location(player).unwrap().archetype as usize],
+            "only archetypes matching the filter are accessed"
+        );
+        assert_eq!(access.mutable.count_ones(..), 0);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", system);
+        schedule.add_stage_after("update", "increment");
+        schedule.add_system_to_stage("increment", increment.system());
+
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 1);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 2);
+    }
+
+    #[test]
+    fn sparse_filter_partitions_dont_conflict() {
+        struct Selected;
+
+        fn add_unselected(
+            mut selected: Query<Mut<i32>, With<Selected>>,
+            mut unselected: Query<Mut<i32>, Without<Selected>>,
+        ) {
+            for mut total in &mut selected.iter() {
+                for mut i in &mut unselected.iter() {
+                    *total += *i;
+                    *i = 0;
+                }
+            }
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        world
+            .set_storage_type::<Selected>(StorageType::SparseSet)
+            .unwrap();
+        let selected = world.spawn((1, Selected));
+        let unselected = world.spawn((2,));
+        world.spawn((3,));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", add_unselected.system());
+        schedule.run(&mut world, &mut resources);
+
+        assert_eq!(*world.get::<i32>(selected).unwrap(), 6);
+        assert_eq!(*world.get::<i32>(unselected).unwrap(), 0);
+    }
+
     #[test]
     fn changed_resource_system() {
         fn incr_e_on_flip(_run_on_flip: ChangedRes<bool>, mut i: Mut<i32>) {
@@ -446,4 +617,164 @@ mod tests {
         schedule.run(&mut world, &mut resources);
         assert_eq!(*(world.get::<i32>(ent).unwrap()), 2);
     }
+
+    #[test]
+    fn changed_resource_between_runs() {
+        fn flip(mut flag: ResMut<bool>) {
+            *flag = !*flag;
+        }
+
+        fn count_changed(_flag: ChangedRes<bool>, mut count: ResMut<usize>) {
+            *count += 1;
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(false);
+        resources.insert(0usize);
+
+        let mut flip_schedule = Schedule::default();
+        flip_schedule.add_stage("update");
+        flip_schedule.add_system_to_stage("update", flip.system());
+        let mut count_schedule = Schedule::default();
+        count_schedule.add_stage("update");
+        count_schedule.add_system_to_stage("update", count_changed.system());
+
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 1);
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 1);
+
+        // changes made while a system doesn't run are detected in its next run, even after the trackers were cleared
+        flip_schedule.run(&mut world, &mut resources);
+        flip_schedule.run(&mut world, &mut resources);
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 2);
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 2);
+
+        *resources.get_mut::<bool>().unwrap() = true;
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 3);
+    }
+
+    #[test]
+    fn changed_query_between_runs() {
+        fn increment(mut i: Mut<i32>) {
+            *i += 1;
+        }
+
+        fn count_changed(mut count: ResMut<usize>, mut query: Query<Changed<i32>>) {
+            *count += query.iter().iter().count();
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(0usize);
+        world.spawn((0,));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", count_changed.system().before("increment"));
+        schedule.add_system_to_stage("update", increment.system().label("increment"));
+
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(
+            *resources.get::<usize>().unwrap(),
+            1,
+            "spawned components count as changed"
+        );
+        // the counting system runs before the mutation, so it detects it in its next run
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 2);
+
+        let mut increment_schedule = Schedule::default();
+        increment_schedule.add_stage("update");
+        increment_schedule.add_system_to_stage("update", increment.system());
+        let mut count_schedule = Schedule::default();
+        count_schedule.add_stage("update");
+        count_schedule.add_system_to_stage("update", count_changed.system());
+
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 3);
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 3);
+
+        // changes made while a system doesn't run are detected in its next run
+        increment_schedule.run(&mut world, &mut resources);
+        increment_schedule.run(&mut world, &mut resources);
+        count_schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 4);
+    }
+
+    #[test]
+    fn chained_systems() {
+        fn sum(mut query: Query<&i32>) -> i32 {
+            query.iter().iter().sum()
+        }
+
+        fn scale(In(sum): In<i32>, factor: ChangedRes<i32>) -> i32 {
+            sum * *factor
+        }
+
+        fn store(In(result): In<i32>, mut stored: ResMut<Vec<i32>>) {
+            stored.push(result);
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(10);
+        resources.insert(Vec::<i32>::new());
+        world.spawn((1,));
+        world.spawn((2,));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage(
+            "update",
+            sum.system().chain(scale.system()).chain(store.system()),
+        );
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<Vec<i32>>().unwrap(), vec![30]);
+
+        // a skipped system skips the systems it is chained to
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<Vec<i32>>().unwrap(), vec![30]);
+
+        *resources.get_mut::<i32>().unwrap() = 2;
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<Vec<i32>>().unwrap(), vec![30, 6]);
+    }
+
+    #[test]
+    fn fallible_systems() {
+        fn check(value: Res<i32>) -> Result<(), String> {
+            if *value < 0 {
+                Err(format!("{} is negative", *value))
+            } else {
+                Ok(())
+            }
+        }
+
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(1);
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_errors = errors.clone();
+        resources.insert(SystemErrorHandler::new(move |error| {
+            handler_errors.lock().unwrap().push(error.to_string())
+        }));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", check.system().handle_errors());
+        schedule.run(&mut world, &mut resources);
+        assert!(errors.lock().unwrap().is_empty());
+
+        *resources.get_mut::<i32>().unwrap() = -1;
+        schedule.run(&mut world, &mut resources);
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].ends_with("failed: -1 is negative"));
+    }
 }
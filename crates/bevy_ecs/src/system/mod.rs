@@ -1,14 +1,20 @@
+mod chain_system;
 mod commands;
+mod fallible_system;
 mod into_system;
 #[cfg(feature = "profiler")]
 mod profiler;
 mod query;
+mod removed_components;
 #[allow(clippy::module_inception)]
 mod system;
 
+pub use chain_system::*;
 pub use commands::*;
+pub use fallible_system::*;
 pub use into_system::*;
 #[cfg(feature = "profiler")]
 pub use profiler::*;
 pub use query::*;
+pub use removed_components::*;
 pub use system::*;
@@ -1,16 +1,20 @@
-use crate::ArchetypeAccess;
+use crate::{ArchetypeAccess, RemovedComponents};
 use bevy_hecs::{
-    Archetype, Component, ComponentError, Entity, Fetch, Query as HecsQuery, Ref, RefMut, With,
-    Without, World,
+    Archetype, ChangeTicks, Component, ComponentError, Entity, Fetch, Filtered, Query as HecsQuery,
+    QueryFilter, Ref, RefMut, With, Without, World,
 };
 use bevy_tasks::ParallelIterator;
 use std::marker::PhantomData;
 
-/// Provides scoped access to a World according to a given [HecsQuery]
-pub struct Query<'a, Q: HecsQuery> {
+/// Provides scoped access to a World according to a given [HecsQuery], yielding only the entities that match the
+/// [QueryFilter] `F`. Filters like `(With<Player>, Changed<Velocity>)` test components without borrowing them. Change
+/// detection queries and filters like [Changed](bevy_hecs::Changed) detect the changes made since the system the
+/// query belongs to last ran.
+pub struct Query<'a, Q: HecsQuery, F: QueryFilter = ()> {
     pub(crate) world: &'a World,
     pub(crate) archetype_access: &'a ArchetypeAccess,
-    _marker: PhantomData<Q>,
+    pub(crate) ticks: ChangeTicks,
+    _marker: PhantomData<(Q, F)>,
 }
 
 /// An error that occurs when using a [Query]
@@ -22,19 +26,24 @@ pub enum QueryError {
     NoSuchEntity,
 }
 
-impl<'a, Q: HecsQuery> Query<'a, Q> {
+impl<'a, Q: HecsQuery, F: QueryFilter> Query<'a, Q, F> {
     #[inline]
-    pub fn new(world: &'a World, archetype_access: &'a ArchetypeAccess) -> Self {
+    pub fn new(
+        world: &'a World,
+        archetype_access: &'a ArchetypeAccess,
+        ticks: ChangeTicks,
+    ) -> Self {
         Self {
             world,
             archetype_access,
+            ticks,
             _marker: PhantomData::default(),
         }
     }
 
     #[inline]
-    pub fn iter(&mut self) -> QueryBorrowChecked<'_, Q> {
-        QueryBorrowChecked::new(&self.world.archetypes, self.archetype_access)
+    pub fn iter(&mut self) -> QueryBorrowChecked<'_, Filtered<Q, F>> {
+        QueryBorrowChecked::new(&self.world.archetypes, self.archetype_access, self.ticks)
     }
 
     // TODO: find a way to make `iter`, `get`, `get_mut`, and `entity` safe without using tracking pointers with global locks
@@ -66,7 +75,10 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
         }
     }
 
-    pub fn entity(&mut self, entity: Entity) -> Result<QueryOneChecked<'_, Q>, QueryError> {
+    pub fn entity(
+        &mut self,
+        entity: Entity,
+    ) -> Result<QueryOneChecked<'_, Filtered<Q, F>>, QueryError> {
         if let Some(location) = self.world.get_entity_location(entity) {
             if self
                 .archetype_access
@@ -82,6 +94,7 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
                     QueryOneChecked::new(
                         &self.world.archetypes[location.archetype as usize],
                         location.index,
+                        self.ticks,
                     )
                 })
             } else {
@@ -108,7 +121,7 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
             // SAFE: RefMut does exclusivity checks and we have already validated the entity
             unsafe {
                 self.world
-                    .get_ref_mut_at_location_unchecked(location)
+                    .get_ref_mut_at_location_unchecked(location, self.ticks.change_tick)
                     .map_err(QueryError::ComponentError)
             }
         } else {
@@ -116,8 +129,10 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
         }
     }
 
-    pub fn removed<C: Component>(&self) -> &[Entity] {
-        self.world.removed::<C>()
+    /// The entities whose `C` component was removed since the system this query belongs to last ran, like
+    /// [RemovedComponents]
+    pub fn removed<C: Component>(&self) -> RemovedComponents<'a, C> {
+        RemovedComponents::new(self.world, self.ticks)
     }
 
     /// Sets the entity's component to the given value. This will fail if the entity does not already have
@@ -135,16 +150,22 @@ impl<'a, Q: HecsQuery> Query<'a, Q> {
 pub struct QueryBorrowChecked<'w, Q: HecsQuery> {
     archetypes: &'w [Archetype],
     archetype_access: &'w ArchetypeAccess,
+    ticks: ChangeTicks,
     borrowed: bool,
     _marker: PhantomData<Q>,
 }
 
 impl<'w, Q: HecsQuery> QueryBorrowChecked<'w, Q> {
-    pub(crate) fn new(archetypes: &'w [Archetype], archetype_access: &'w ArchetypeAccess) -> Self {
+    pub(crate) fn new(
+        archetypes: &'w [Archetype],
+        archetype_access: &'w ArchetypeAccess,
+        ticks: ChangeTicks,
+    ) -> Self {
         Self {
             archetypes,
             borrowed: false,
             archetype_access,
+            ticks,
             _marker: PhantomData,
         }
     }
@@ -254,10 +275,11 @@ impl<'q, 'w, Q: HecsQuery> Iterator for QueryIter<'q, 'w, Q> {
                     let archetype = self.borrow.archetypes.get(self.archetype_index as usize)?;
                     self.archetype_index += 1;
                     unsafe {
-                        self.iter = Q::Fetch::get(archetype, 0).map(|fetch| ChunkIter {
-                            fetch,
-                            len: archetype.len(),
-                        });
+                        self.iter =
+                            Q::Fetch::get(archetype, 0, self.borrow.ticks).map(|fetch| ChunkIter {
+                                fetch,
+                                len: archetype.len(),
+                            });
                     }
                 }
                 Some(ref mut iter) => match unsafe { iter.next() } {
@@ -306,7 +328,7 @@ impl<Q: HecsQuery> ChunkIter<Q> {
             self.len -= 1;
             if self.fetch.should_skip() {
                 // we still need to progress the iterator
-                let _ = self.fetch.next();
+                self.fetch.skip();
                 continue;
             }
 
@@ -335,7 +357,9 @@ impl<'q, 'w, Q: HecsQuery> ParallelIterator<Batch<'q, Q>> for ParIter<'q, 'w, Q>
                 self.batch = 0;
                 continue;
             }
-            if let Some(fetch) = unsafe { Q::Fetch::get(archetype, offset as usize) } {
+            if let Some(fetch) =
+                unsafe { Q::Fetch::get(archetype, offset as usize, self.borrow.ticks) }
+            {
                 self.batch += 1;
                 return Some(Batch {
                     _marker: PhantomData,
@@ -377,6 +401,7 @@ unsafe impl<'q, Q: HecsQuery> Send for Batch<'q, Q> {}
 pub struct QueryOneChecked<'a, Q: HecsQuery> {
     archetype: &'a Archetype,
     index: usize,
+    ticks: ChangeTicks,
     borrowed: bool,
     _marker: PhantomData<Q>,
 }
@@ -387,10 +412,11 @@ impl<'a, Q: HecsQuery> QueryOneChecked<'a, Q> {
     /// # Safety
     ///
     /// `index` must be in-bounds for `archetype`
-    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize) -> Self {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: usize, ticks: ChangeTicks) -> Self {
         Self {
             archetype,
             index,
+            ticks,
             borrowed: false,
             _marker: PhantomData,
         }
@@ -404,7 +430,10 @@ impl<'a, Q: HecsQuery> QueryOneChecked<'a, Q> {
     /// pre-existing borrow.
     pub fn get(&mut self) -> Option<<Q::Fetch as Fetch<'_>>::Item> {
         unsafe {
-            let mut fetch = Q::Fetch::get(self.archetype, self.index as usize)?;
+            let mut fetch = Q::Fetch::get(self.archetype, self.index as usize, self.ticks)?;
+            if fetch.is_missing() {
+                return None;
+            }
             self.borrowed = true;
             Q::Fetch::borrow(self.archetype);
             Some(fetch.next())
@@ -430,6 +459,7 @@ impl<'a, Q: HecsQuery> QueryOneChecked<'a, Q> {
         QueryOneChecked {
             archetype: self.archetype,
             index: self.index,
+            ticks: self.ticks,
             borrowed: self.borrowed,
             _marker: PhantomData,
         }
@@ -0,0 +1,171 @@
+use crate::{
+    resource::{FetchResource, ResourceQuery, Resources, UnsafeClone},
+    system::{SystemChangeTicks, SystemId, TypeAccess},
+};
+use bevy_hecs::{ChangeTicks, Component, Entity, World};
+use std::marker::PhantomData;
+
+/// A system parameter that yields the entities whose `T` component was removed since the system last ran, including
+/// entities that were despawned while they had a `T` component. [Query::removed](crate::Query::removed) yields the same
+/// entities.
+///
+/// Removals made by [Commands](crate::Commands) are recorded when the commands are applied at the end of a stage,
+/// so systems in later stages see them in the same update and systems in earlier stages see them in the next update.
+///
+/// The world only keeps the removals of the current and the previous update, so a system that doesn't run for two
+/// updates in a row, for example because of its run criteria, misses the removals made in the update it skipped
+/// first.
+///
+/// This parameter can only be used by systems: querying it from [Resources] directly returns `None`.
+pub struct RemovedComponents<'a, T: Component> {
+    entities: &'a [Entity],
+    ticks: &'a [u32],
+    change_ticks: ChangeTicks,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<'a, T: Component> RemovedComponents<'a, T> {
+    pub(crate) fn new(world: &'a World, change_ticks: ChangeTicks) -> Self {
+        let (entities, ticks) = world.removed_with_ticks::<T>();
+        Self {
+            entities,
+            ticks,
+            change_ticks,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Iterates over the entities whose `T` component was removed since the system last ran
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + 'a {
+        let change_ticks = self.change_ticks;
+        self.entities
+            .iter()
+            .zip(self.ticks.iter())
+            .filter(move |(_, &tick)| change_ticks.is_changed(tick))
+            .map(|(entity, _)| *entity)
+    }
+}
+
+impl<'a, T: Component> UnsafeClone for RemovedComponents<'a, T> {
+    unsafe fn unsafe_clone(&self) -> Self {
+        Self {
+            entities: self.entities,
+            ticks: self.ticks,
+            change_ticks: self.change_ticks,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Component> ResourceQuery for RemovedComponents<'a, T> {
+    type Fetch = FetchRemovedComponents<T>;
+}
+
+/// Fetches the [RemovedComponents] of a system
+pub struct FetchRemovedComponents<T>(PhantomData<fn(T)>);
+
+impl<'a, T: Component> FetchResource<'a> for FetchRemovedComponents<T> {
+    type Item = RemovedComponents<'a, T>;
+
+    unsafe fn get(_resources: &'a Resources, _system_id: Option<SystemId>) -> Self::Item {
+        // `is_some` keeps `Resources::query` from getting here, but there are no removals without a world anyway
+        RemovedComponents {
+            entities: &[],
+            ticks: &[],
+            change_ticks: ChangeTicks::new(0, 0),
+            _marker: PhantomData,
+        }
+    }
+
+    unsafe fn get_for_system(
+        world: &'a World,
+        _resources: &'a Resources,
+        _system_id: SystemId,
+        ticks: SystemChangeTicks,
+    ) -> Self::Item {
+        RemovedComponents::new(world, ticks.world)
+    }
+
+    unsafe fn is_some(_resources: &'a Resources, _system_id: Option<SystemId>) -> bool {
+        false
+    }
+
+    unsafe fn is_some_for_system(
+        _resources: &'a Resources,
+        _system_id: SystemId,
+        _ticks: ChangeTicks,
+    ) -> bool {
+        true
+    }
+
+    fn borrow(_resources: &Resources) {}
+
+    fn release(_resources: &Resources) {}
+
+    fn access() -> TypeAccess {
+        // removals are only recorded while the world is borrowed mutably, so reading them never conflicts
+        TypeAccess::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        resource::ResMut,
+        schedule::Schedule,
+        system::{Commands, IntoQuerySystem, Query},
+    };
+
+    #[test]
+    fn removed_components() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(Vec::<Entity>::new());
+        resources.insert(None::<Entity>);
+        let a = world.spawn((1u32,));
+        let b = world.spawn((2u32, true));
+        let c = world.spawn((3u32,));
+
+        fn track_removed(
+            mut log: ResMut<Vec<Entity>>,
+            removed: RemovedComponents<u32>,
+            query: Query<&u32>,
+        ) {
+            assert!(query.removed::<u32>().iter().eq(removed.iter()));
+            log.extend(removed.iter());
+        }
+
+        fn remove_marked(mut commands: Commands, mut marked: ResMut<Option<Entity>>) {
+            if let Some(entity) = marked.take() {
+                commands.remove_one::<u32>(entity);
+            }
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_stage_after("update", "late");
+        schedule.add_system_to_stage("update", track_removed.system());
+        schedule.add_system_to_stage("late", remove_marked.system());
+        let mut run = |world: &mut World, resources: &mut Resources| {
+            schedule.initialize(world, resources);
+            schedule.run(world, resources);
+            std::mem::take(&mut *resources.get_mut::<Vec<Entity>>().unwrap())
+        };
+
+        assert_eq!(run(&mut world, &mut resources), vec![]);
+
+        world.despawn(a).unwrap();
+        world.remove_one::<u32>(b).unwrap();
+        assert_eq!(run(&mut world, &mut resources), vec![a, b]);
+        assert_eq!(run(&mut world, &mut resources), vec![]);
+
+        // removed after the tracking system ran, and after the trackers were cleared at the end of the update
+        *resources.get_mut::<Option<Entity>>().unwrap() = Some(c);
+        assert_eq!(run(&mut world, &mut resources), vec![]);
+        assert_eq!(run(&mut world, &mut resources), vec![c]);
+        assert_eq!(run(&mut world, &mut resources), vec![]);
+
+        assert!(resources.query::<RemovedComponents<u32>>().is_none());
+    }
+}
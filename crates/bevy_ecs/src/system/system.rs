@@ -1,5 +1,5 @@
 use crate::resource::Resources;
-use bevy_hecs::{Access, Query, World};
+use bevy_hecs::{Access, ChangeTicks, Query, World};
 use bevy_utils::HashSet;
 use fixedbitset::FixedBitSet;
 use std::{any::TypeId, borrow::Cow};
@@ -21,17 +21,82 @@ impl SystemId {
     }
 }
 
-/// An ECS system that can be added to a [Schedule](crate::Schedule)
-pub trait System: Send + Sync {
+/// The change ticks of a system run. The [World] and [Resources] each count their own change ticks, so a system
+/// tracks the tick it last ran at for both of them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SystemChangeTicks {
+    pub world: ChangeTicks,
+    pub resources: ChangeTicks,
+}
+
+/// An ECS system that can be added to a [Schedule](crate::Schedule). Systems take an input and produce an output,
+/// which lets the output of one system be passed to another with [IntoChainSystem](super::IntoChainSystem). Only
+/// systems without input and output can be added to a schedule.
+pub trait System: Send + Sync + 'static {
+    type In;
+    type Out;
     fn name(&self) -> Cow<'static, str>;
     fn id(&self) -> SystemId;
     fn update_archetype_access(&mut self, world: &World);
     fn archetype_access(&self) -> &ArchetypeAccess;
     fn resource_access(&self) -> &TypeAccess;
     fn thread_local_execution(&self) -> ThreadLocalExecution;
-    fn run(&mut self, world: &World, resources: &Resources);
+    /// Runs the system with the given input. Returns `None` if the system was skipped because the resources it
+    /// accesses aren't available.
+    fn run(&mut self, input: Self::In, world: &World, resources: &Resources) -> Option<Self::Out>;
     fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources);
     fn initialize(&mut self, _world: &mut World, _resources: &mut Resources) {}
+    /// Clamps the change tick the system last ran at, so a system that hasn't run in a very long time doesn't miss
+    /// changes when the world's change tick wraps around
+    fn check_change_tick(&mut self, _change_tick: u32) {}
+}
+
+/// A boxed [System], which is how systems are stored in a [Schedule](crate::Schedule)
+pub type BoxedSystem<In = (), Out = ()> = Box<dyn System<In = In, Out = Out>>;
+
+impl<S: System + ?Sized> System for Box<S> {
+    type In = S::In;
+    type Out = S::Out;
+
+    fn name(&self) -> Cow<'static, str> {
+        (**self).name()
+    }
+
+    fn id(&self) -> SystemId {
+        (**self).id()
+    }
+
+    fn update_archetype_access(&mut self, world: &World) {
+        (**self).update_archetype_access(world)
+    }
+
+    fn archetype_access(&self) -> &ArchetypeAccess {
+        (**self).archetype_access()
+    }
+
+    fn resource_access(&self) -> &TypeAccess {
+        (**self).resource_access()
+    }
+
+    fn thread_local_execution(&self) -> ThreadLocalExecution {
+        (**self).thread_local_execution()
+    }
+
+    fn run(&mut self, input: Self::In, world: &World, resources: &Resources) -> Option<Self::Out> {
+        (**self).run(input, world, resources)
+    }
+
+    fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources) {
+        (**self).run_thread_local(world, resources)
+    }
+
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        (**self).initialize(world, resources)
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        (**self).check_change_tick(change_tick)
+    }
 }
 
 /// Provides information about the archetypes a [System] reads and writes
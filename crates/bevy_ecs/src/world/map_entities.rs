@@ -0,0 +1,56 @@
+use bevy_hecs::Entity;
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MapEntitiesError {
+    #[error("The given entity does not exist in the map.")]
+    EntityNotFound(Entity),
+}
+
+/// Components that refer to other entities implement this to update their references when they are copied to
+/// another world, for example when a scene is spawned
+pub trait MapEntities {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError>;
+}
+
+/// Maps the entities of one world to the entities of another. The entities mapped from are keyed by id alone:
+/// scenes don't store the generation of their entities, so a reference taken with any generation maps to the entity
+/// with the same id.
+#[derive(Default, Debug)]
+pub struct EntityMap {
+    map: HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    pub fn insert(&mut self, from: Entity, to: Entity) {
+        self.map.insert(Self::key(from), to);
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        self.map.remove(&Self::key(entity));
+    }
+
+    pub fn get(&self, entity: Entity) -> Result<Entity, MapEntitiesError> {
+        self.map
+            .get(&Self::key(entity))
+            .cloned()
+            .ok_or(MapEntitiesError::EntityNotFound(entity))
+    }
+
+    pub fn get_or_insert_with(&mut self, entity: Entity, f: impl FnOnce() -> Entity) -> Entity {
+        *self.map.entry(Self::key(entity)).or_insert_with(f)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.map.keys().cloned()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.map.values().cloned()
+    }
+
+    fn key(entity: Entity) -> Entity {
+        Entity::new(entity.id())
+    }
+}
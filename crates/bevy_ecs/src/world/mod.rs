@@ -1,3 +1,5 @@
+mod map_entities;
 mod world_builder;
 
+pub use map_entities::*;
 pub use world_builder::*;
@@ -1,9 +1,20 @@
 mod loader;
+mod processor;
 pub use loader::*;
+pub use processor::*;
 
+pub mod prelude {
+    pub use crate::{Gltf, GltfMesh, GltfNode, GltfPrimitive};
+}
+
+use bevy_animation::AnimationClip;
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
-use bevy_render::mesh::Mesh;
+use bevy_asset::{AddAsset, Handle};
+use bevy_pbr::prelude::StandardMaterial;
+use bevy_render::{mesh::Mesh, texture::Texture};
+use bevy_scene::Scene;
+use bevy_transform::prelude::Transform;
+use bevy_utils::HashMap;
 
 /// Adds support for GLTF file loading to Apps
 #[derive(Default)]
@@ -11,6 +22,54 @@ pub struct GltfPlugin;
 
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_asset_loader::<Mesh, GltfLoader>();
+        app.add_asset::<Gltf>()
+            .add_asset_loader::<Gltf, GltfLoader>()
+            .add_asset_processor::<GltfProcessor>();
     }
 }
+
+/// The contents of a GLTF file: its scenes, node hierarchy, meshes, materials, images and animations.
+///
+/// Scenes, meshes, materials, images and animations are stored in their own [Assets](bevy_asset::Assets) collections
+/// and can also be loaded directly using labeled paths, such as `models/scene.gltf#Scene0`.
+pub struct Gltf {
+    pub scenes: Vec<Handle<Scene>>,
+    pub named_scenes: HashMap<String, Handle<Scene>>,
+    pub default_scene: Option<Handle<Scene>>,
+    pub nodes: Vec<GltfNode>,
+    pub named_nodes: HashMap<String, usize>,
+    pub meshes: Vec<GltfMesh>,
+    pub named_meshes: HashMap<String, usize>,
+    pub materials: Vec<Handle<StandardMaterial>>,
+    pub named_materials: HashMap<String, Handle<StandardMaterial>>,
+    /// The textures loaded from the file's images, in the order they are defined in the file. Images that are stored
+    /// in their own files are loaded as dependencies of the GLTF file. Images in formats that can't be decoded are
+    /// skipped with a warning and are `None`.
+    pub images: Vec<Option<Handle<Texture>>>,
+    pub animations: Vec<Handle<AnimationClip>>,
+    pub named_animations: HashMap<String, Handle<AnimationClip>>,
+}
+
+/// A node in a GLTF file's node hierarchy
+#[derive(Debug, Clone)]
+pub struct GltfNode {
+    pub name: Option<String>,
+    pub transform: Transform,
+    pub mesh: Option<usize>,
+    pub children: Vec<usize>,
+}
+
+/// A GLTF mesh, which is made up of one or more primitives
+#[derive(Debug, Clone)]
+pub struct GltfMesh {
+    pub name: Option<String>,
+    pub primitives: Vec<GltfPrimitive>,
+}
+
+/// A single primitive of a [GltfMesh], loaded as its own [Mesh] asset
+#[derive(Debug, Clone)]
+pub struct GltfPrimitive {
+    pub mesh: Handle<Mesh>,
+    /// The primitive's material. Primitives without a material use the default [StandardMaterial]
+    pub material: Option<Handle<StandardMaterial>>,
+}
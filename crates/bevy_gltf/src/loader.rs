@@ -1,28 +1,60 @@
+use crate::{
+    decode_processed_gltf, is_processed_gltf, DecodedPrimitives, Gltf, GltfMesh, GltfNode,
+    GltfPrimitive,
+};
+use bevy_animation::{
+    AnimationChannel, AnimationClip, Interpolation, Keyframes, SkinnedMesh, MAX_JOINTS,
+};
+use bevy_asset::{AssetIoError, AssetLoader, Handle, LoadContext};
+use bevy_core::Labels;
+use bevy_ecs::Entity;
+use bevy_math::{Mat4, Quat, Vec3};
+use bevy_pbr::prelude::{AlphaMode, PbrComponents, StandardMaterial};
+use bevy_property::{DynamicProperties, Properties};
 use bevy_render::{
-    mesh::{Indices, Mesh, VertexAttribute},
+    color::Color,
+    mesh::{Indices, Mesh, MeshFileError, VertexAttribute},
     pipeline::PrimitiveTopology,
+    texture::{ImageTextureLoader, Texture},
 };
+use bevy_scene::Scene;
+use bevy_transform::prelude::{Children, GlobalTransform, Parent, Transform};
+use bevy_utils::{BoxedFuture, HashMap};
 
 use anyhow::Result;
-use bevy_asset::AssetLoader;
-use gltf::{buffer::Source, mesh::Mode};
-use std::{fs, io, path::Path};
+use gltf::{
+    animation::{self, util::ReadOutputs},
+    buffer::Source,
+    image, material,
+    mesh::Mode,
+};
+use std::path::Path;
 use thiserror::Error;
 
-/// Loads meshes from GLTF files into Mesh assets
+/// Loads GLTF files into [Gltf] assets, which contain the file's scenes, nodes and meshes.
+///
+/// The file's textures, materials, mesh primitives, animations and scenes are loaded as labeled assets:
+/// `Texture{index}`, `Material{index}`, `Mesh{index}/Primitive{index}`, `Animation{index}` and `Scene{index}`. Images
+/// that are stored in their own files are loaded as dependencies of the GLTF file instead.
 ///
-/// NOTE: eventually this will loading into Scenes instead of Meshes
+/// `.bgltf` files that were created by the [GltfProcessor](crate::GltfProcessor) are loaded as well.
 #[derive(Default)]
 pub struct GltfLoader;
 
-impl AssetLoader<Mesh> for GltfLoader {
-    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<Mesh> {
-        let mesh = load_gltf(asset_path, bytes)?;
-        Ok(mesh)
+impl AssetLoader<Gltf> for GltfLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Gltf>> {
+        Box::pin(async move {
+            let gltf = load_gltf(bytes, load_context).await?;
+            Ok(gltf)
+        })
     }
 
     fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["gltf", "glb"];
+        static EXTENSIONS: &[&str] = &["gltf", "glb", "bgltf"];
         EXTENSIONS
     }
 }
@@ -32,16 +64,44 @@ impl AssetLoader<Mesh> for GltfLoader {
 pub enum GltfError {
     #[error("Unsupported primitive mode.")]
     UnsupportedPrimitive { mode: Mode },
+    #[error("Primitive is missing vertex positions.")]
+    MissingPositions { mesh: usize, primitive: usize },
     #[error("Invalid GLTF file.")]
     Gltf(#[from] gltf::Error),
     #[error("Failed to load file.")]
-    Io(#[from] io::Error),
+    AssetIoError(#[from] AssetIoError),
     #[error("Binary blob is missing.")]
     MissingBlob,
     #[error("Failed to decode base64 mesh data.")]
     Base64Decode(#[from] base64::DecodeError),
     #[error("Unsupported buffer format.")]
     BufferFormatUnsupported,
+    #[error("Invalid buffer uri.")]
+    InvalidBufferUri { uri: String },
+    #[error("Buffer view is out of the bounds of its buffer.")]
+    BufferViewOutOfBounds { view: usize },
+    #[error(
+        "Unsupported image format {mime_type:?}. Enable the png or jpeg feature to decode it."
+    )]
+    UnsupportedImageFormat { mime_type: Option<String> },
+    #[error("Failed to decode image.")]
+    ImageDecode(#[source] anyhow::Error),
+    #[error("Animation channel is missing keyframes.")]
+    MissingKeyframes { animation: usize, channel: usize },
+    #[error("Skin has more joints than the supported maximum of {}.", MAX_JOINTS)]
+    TooManyJoints { skin: usize, joints: usize },
+    #[error("Skin joint is not a node of the scene the skin is used in.")]
+    UnresolvedJoint { skin: usize, joint: usize },
+    #[error("Invalid processed GLTF file.")]
+    InvalidProcessedFile,
+    #[error("Invalid processed GLTF mesh.")]
+    MeshFile(#[from] MeshFileError),
+}
+
+/// The joints of a GLTF skin and their inverse bind matrices
+struct GltfSkin {
+    joints: Vec<usize>,
+    inverse_bind_matrices: Vec<Mat4>,
 }
 
 fn get_primitive_topology(mode: Mode) -> Result<PrimitiveTopology, GltfError> {
@@ -55,64 +115,531 @@ fn get_primitive_topology(mode: Mode) -> Result<PrimitiveTopology, GltfError> {
     }
 }
 
-// TODO: this should return a scene
-pub fn load_gltf(asset_path: &Path, bytes: Vec<u8>) -> Result<Mesh, GltfError> {
-    let gltf = gltf::Gltf::from_slice(&bytes)?;
-    let buffer_data = load_buffers(&gltf, asset_path)?;
+/// Loads the scenes, nodes, meshes, materials, images and animations of a GLTF file. Everything but the node
+/// hierarchy is added to `load_context` as labeled assets. External buffers are read relative to the file's path.
+///
+/// `bytes` can also be a file that was processed by the [GltfProcessor](crate::GltfProcessor), whose decoded primitives
+/// are used instead of decoding them again.
+pub async fn load_gltf(bytes: Vec<u8>, load_context: &mut LoadContext) -> Result<Gltf, GltfError> {
+    if is_processed_gltf(&bytes) {
+        let (source, primitives) = decode_processed_gltf(&bytes)?;
+        load_gltf_source(source, primitives, load_context).await
+    } else {
+        load_gltf_source(&bytes, HashMap::default(), load_context).await
+    }
+}
+
+async fn load_gltf_source(
+    bytes: &[u8],
+    mut decoded_primitives: DecodedPrimitives,
+    load_context: &mut LoadContext,
+) -> Result<Gltf, GltfError> {
+    let gltf = gltf::Gltf::from_slice(bytes)?;
+    let buffer_data = load_buffers(&gltf, load_context).await?;
+
+    let mut images = Vec::new();
+    for image in gltf.images() {
+        let handle = match image.source() {
+            // images that are stored in their own files are loaded as dependencies of the GLTF file
+            image::Source::Uri { uri, .. } if !uri.starts_with("data:") => {
+                let image_path = load_context
+                    .path()
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(uri);
+                let extension = image_path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or_default();
+                if ImageTextureLoader::supports_extension(extension) {
+                    Some(load_context.add_dependency(image_path))
+                } else {
+                    log::warn!(
+                        "skipping image {} of {}: unsupported image format",
+                        uri,
+                        load_context.path().display()
+                    );
+                    None
+                }
+            }
+            _ => match load_image(&image, &buffer_data) {
+                Ok(texture) => {
+                    Some(load_context.set(&format!("Texture{}", image.index()), texture))
+                }
+                Err(GltfError::UnsupportedImageFormat { mime_type }) => {
+                    log::warn!(
+                        "skipping image {} of {}: unsupported image format {:?}",
+                        image.index(),
+                        load_context.path().display(),
+                        mime_type
+                    );
+                    None
+                }
+                Err(err) => return Err(err),
+            },
+        };
+        images.push(handle);
+    }
+
+    let mut materials = Vec::new();
+    let mut named_materials = HashMap::default();
+    let mut material_assets = Vec::new();
+    for material in gltf.materials() {
+        let label = format!("Material{}", material.index().unwrap_or_default());
+        let handle = load_context.get_handle(&label);
+        material_assets.push((label, handle.clone(), load_material(&material, &images)));
+        if let Some(name) = material.name() {
+            named_materials.insert(name.to_string(), handle.clone());
+        }
+        materials.push(handle);
+    }
+
+    let mut meshes = Vec::new();
+    let mut named_meshes = HashMap::default();
+    for mesh in gltf.meshes() {
+        let mut primitives = Vec::new();
+        for primitive in mesh.primitives() {
+            let label = format!("Mesh{}/Primitive{}", mesh.index(), primitive.index());
+            let mesh_asset = match decoded_primitives.remove(&(mesh.index(), primitive.index())) {
+                Some(mesh_asset) => mesh_asset,
+                None => load_primitive(&buffer_data, &mesh, &primitive)?,
+            };
+            primitives.push(GltfPrimitive {
+                mesh: load_context.set(&label, mesh_asset),
+                material: primitive
+                    .material()
+                    .index()
+                    .map(|material| materials[material].clone()),
+            });
+        }
+
+        if let Some(name) = mesh.name() {
+            named_meshes.insert(name.to_string(), meshes.len());
+        }
+        meshes.push(GltfMesh {
+            name: mesh.name().map(|name| name.to_string()),
+            primitives,
+        });
+    }
+
+    let skins = gltf
+        .skins()
+        .map(|skin| {
+            let joints = skin.joints().count();
+            if joints > MAX_JOINTS {
+                return Err(GltfError::TooManyJoints {
+                    skin: skin.index(),
+                    joints,
+                });
+            }
+            let reader = skin.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            Ok(GltfSkin {
+                joints: skin.joints().map(|joint| joint.index()).collect(),
+                inverse_bind_matrices: reader
+                    .read_inverse_bind_matrices()
+                    .map(|matrices| {
+                        matrices
+                            .map(|matrix| Mat4::from_cols_array_2d(&matrix))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut animations = Vec::new();
+    let mut named_animations = HashMap::default();
+    for animation in gltf.animations() {
+        let clip = load_animation(&animation, &buffer_data)?;
+        let handle = load_context.set(&format!("Animation{}", animation.index()), clip);
+        if let Some(name) = animation.name() {
+            named_animations.insert(name.to_string(), handle.clone());
+        }
+        animations.push(handle);
+    }
+
+    let mut nodes = Vec::new();
+    let mut named_nodes = HashMap::default();
+    for node in gltf.nodes() {
+        if let Some(name) = node.name() {
+            named_nodes.insert(name.to_string(), nodes.len());
+        }
+        nodes.push(GltfNode {
+            name: node.name().map(|name| name.to_string()),
+            transform: node_transform(&node),
+            mesh: node.mesh().map(|mesh| mesh.index()),
+            children: node.children().map(|child| child.index()).collect(),
+        });
+    }
+
+    let mut scenes = Vec::new();
+    let mut named_scenes = HashMap::default();
     for scene in gltf.scenes() {
-        if let Some(node) = scene.nodes().next() {
-            return Ok(load_node(&buffer_data, &node, 1)?);
+        let scene_asset = load_scene(&scene, &meshes, &material_assets, &skins)?;
+        let handle = load_context.set(&format!("Scene{}", scene.index()), scene_asset);
+        if let Some(name) = scene.name() {
+            named_scenes.insert(name.to_string(), handle.clone());
         }
+        scenes.push(handle);
     }
 
-    // TODO: remove this when full gltf support is added
-    panic!("no mesh found!")
+    for (label, _handle, material) in material_assets {
+        load_context.set(&label, material);
+    }
+
+    Ok(Gltf {
+        default_scene: gltf
+            .default_scene()
+            .or_else(|| gltf.scenes().next())
+            .map(|scene| scenes[scene.index()].clone()),
+        scenes,
+        named_scenes,
+        nodes,
+        named_nodes,
+        meshes,
+        named_meshes,
+        materials,
+        named_materials,
+        images,
+        animations,
+        named_animations,
+    })
 }
 
-fn load_node(buffer_data: &[Vec<u8>], node: &gltf::Node, depth: i32) -> Result<Mesh, GltfError> {
-    if let Some(mesh) = node.mesh() {
-        if let Some(primitive) = mesh.primitives().next() {
-            let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
-            let primitive_topology = get_primitive_topology(primitive.mode())?;
-            let mut mesh = Mesh::new(primitive_topology);
-
-            if let Some(vertex_attribute) = reader
-                .read_positions()
-                .map(|v| VertexAttribute::position(v.collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+pub(crate) fn load_primitive(
+    buffer_data: &[Vec<u8>],
+    mesh: &gltf::Mesh,
+    primitive: &gltf::Primitive,
+) -> Result<Mesh, GltfError> {
+    let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
+    let primitive_topology = get_primitive_topology(primitive.mode())?;
+    let mut mesh_asset = Mesh::new(primitive_topology);
 
-            if let Some(vertex_attribute) = reader
-                .read_normals()
-                .map(|v| VertexAttribute::normal(v.collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+    if let Some(vertex_attribute) = reader
+        .read_positions()
+        .map(|v| VertexAttribute::position(v.collect()))
+    {
+        mesh_asset.attributes.push(vertex_attribute);
+    } else {
+        return Err(GltfError::MissingPositions {
+            mesh: mesh.index(),
+            primitive: primitive.index(),
+        });
+    }
 
-            if let Some(vertex_attribute) = reader
-                .read_tex_coords(0)
-                .map(|v| VertexAttribute::uv(v.into_f32().collect()))
-            {
-                mesh.attributes.push(vertex_attribute);
-            }
+    if let Some(vertex_attribute) = reader
+        .read_normals()
+        .map(|v| VertexAttribute::normal(v.collect()))
+    {
+        mesh_asset.attributes.push(vertex_attribute);
+    }
+
+    if let Some(vertex_attribute) = reader
+        .read_tex_coords(0)
+        .map(|v| VertexAttribute::uv(v.into_f32().collect()))
+    {
+        mesh_asset.attributes.push(vertex_attribute);
+    }
 
-            if let Some(indices) = reader.read_indices() {
-                mesh.indices = Some(Indices::U32(indices.into_u32().collect()));
+    if let Some(vertex_attribute) = reader.read_joints(0).map(|v| {
+        VertexAttribute::joint_indices(
+            v.into_u16()
+                .map(|[a, b, c, d]| [a as f32, b as f32, c as f32, d as f32])
+                .collect(),
+        )
+    }) {
+        mesh_asset.attributes.push(vertex_attribute);
+    }
+
+    if let Some(vertex_attribute) = reader
+        .read_weights(0)
+        .map(|v| VertexAttribute::joint_weights(v.into_f32().collect()))
+    {
+        mesh_asset.attributes.push(vertex_attribute);
+    }
+
+    if let Some(indices) = reader.read_indices() {
+        mesh_asset.indices = Some(Indices::U32(indices.into_u32().collect()));
+    }
+
+    Ok(mesh_asset)
+}
+
+/// Loads an image that is embedded in the GLTF file, either in a buffer view or in a data uri
+fn load_image(image: &gltf::Image, buffer_data: &[Vec<u8>]) -> Result<Texture, GltfError> {
+    let (bytes, mime_type) = match image.source() {
+        image::Source::View { view, mime_type } => {
+            // the buffer may be shorter than the file claims
+            let bytes = buffer_data
+                .get(view.buffer().index())
+                .and_then(|buffer| buffer.get(view.offset()..view.offset() + view.length()))
+                .ok_or(GltfError::BufferViewOutOfBounds { view: view.index() })?
+                .to_vec();
+            (bytes, Some(mime_type))
+        }
+        image::Source::Uri { uri, mime_type } => {
+            let (header, data) = uri.split_at(uri.find(";base64,").ok_or_else(|| {
+                GltfError::UnsupportedImageFormat {
+                    mime_type: mime_type.map(|mime_type| mime_type.to_string()),
+                }
+            })?);
+            let bytes = base64::decode(&data[";base64,".len()..])?;
+            (bytes, mime_type.or_else(|| Some(&header["data:".len()..])))
+        }
+    };
+
+    let extension = match mime_type {
+        Some("image/png") => Some("png"),
+        Some("image/jpeg") => Some("jpeg"),
+        _ => None,
+    };
+    let extension = match extension {
+        Some(extension) if ImageTextureLoader::supports_extension(extension) => extension,
+        _ => {
+            return Err(GltfError::UnsupportedImageFormat {
+                mime_type: mime_type.map(|mime_type| mime_type.to_string()),
+            })
+        }
+    };
+
+    ImageTextureLoader::load_image_bytes(&bytes, extension).map_err(GltfError::ImageDecode)
+}
+
+fn load_material(
+    material: &gltf::Material,
+    images: &[Option<Handle<Texture>>],
+) -> StandardMaterial {
+    let pbr = material.pbr_metallic_roughness();
+    let [r, g, b, a] = pbr.base_color_factor();
+    StandardMaterial {
+        albedo: Color::rgba(r, g, b, a),
+        albedo_texture: pbr
+            .base_color_texture()
+            .and_then(|info| images[info.texture().source().index()].clone()),
+        alpha_mode: match material.alpha_mode() {
+            material::AlphaMode::Opaque => AlphaMode::Opaque,
+            material::AlphaMode::Mask => AlphaMode::Mask(material.alpha_cutoff()),
+            material::AlphaMode::Blend => AlphaMode::Blend,
+        },
+        ..Default::default()
+    }
+}
+
+fn load_animation(
+    animation: &gltf::Animation,
+    buffer_data: &[Vec<u8>],
+) -> Result<AnimationClip, GltfError> {
+    let mut channels = Vec::new();
+    for (index, channel) in animation.channels().enumerate() {
+        let reader = channel.reader(|buffer| Some(&buffer_data[buffer.index()]));
+        let missing_keyframes = || GltfError::MissingKeyframes {
+            animation: animation.index(),
+            channel: index,
+        };
+        let timestamps = reader
+            .read_inputs()
+            .ok_or_else(missing_keyframes)?
+            .collect();
+        let keyframes = match reader.read_outputs().ok_or_else(missing_keyframes)? {
+            ReadOutputs::Translations(values) => {
+                Keyframes::Translation(values.map(Vec3::from).collect())
             }
+            ReadOutputs::Rotations(values) => Keyframes::Rotation(
+                values
+                    .into_f32()
+                    .map(|[x, y, z, w]| Quat::from_xyzw(x, y, z, w))
+                    .collect(),
+            ),
+            ReadOutputs::Scales(values) => Keyframes::Scale(values.map(Vec3::from).collect()),
+            // morph targets are not supported yet
+            ReadOutputs::MorphTargetWeights(_) => continue,
+        };
+        channels.push(AnimationChannel {
+            target: node_label(&channel.target().node()),
+            timestamps,
+            keyframes,
+            interpolation: match channel.sampler().interpolation() {
+                animation::Interpolation::Linear => Interpolation::Linear,
+                animation::Interpolation::Step => Interpolation::Step,
+                animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+            },
+        });
+    }
+
+    Ok(AnimationClip::new(
+        animation.name().map(|name| name.to_string()),
+        channels,
+    ))
+}
 
-            return Ok(mesh);
+fn node_transform(node: &gltf::Node) -> Transform {
+    Transform::new(Mat4::from_cols_array_2d(&node.transform().matrix()))
+}
+
+/// The label of a node's scene entity, which animation channels use to find the entities they animate. Unnamed nodes
+/// are labeled with their index.
+fn node_label(node: &gltf::Node) -> String {
+    node.name()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("Node{}", node.index()))
+}
+
+fn load_scene(
+    scene: &gltf::Scene,
+    meshes: &[GltfMesh],
+    materials: &[(String, Handle<StandardMaterial>, StandardMaterial)],
+    skins: &[GltfSkin],
+) -> Result<Scene, GltfError> {
+    let mut scene_asset = Scene::default();
+    let mut node_entities = HashMap::default();
+    let mut skinned_entities = Vec::new();
+    for node in scene.nodes() {
+        load_node(
+            &node,
+            None,
+            meshes,
+            materials,
+            &mut scene_asset,
+            &mut node_entities,
+            &mut skinned_entities,
+        );
+    }
+
+    // joints can be anywhere in the hierarchy, so skins are resolved once every node has an entity
+    for (entity, skin_index) in skinned_entities {
+        let skin = &skins[skin_index];
+        let joints = skin
+            .joints
+            .iter()
+            .map(|joint| {
+                node_entities
+                    .get(joint)
+                    .map(|joint| Entity::new(*joint))
+                    .ok_or(GltfError::UnresolvedJoint {
+                        skin: skin_index,
+                        joint: *joint,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        let skinned_mesh = SkinnedMesh {
+            joints,
+            inverse_bind_matrices: skin.inverse_bind_matrices.clone(),
+        };
+        scene_asset.entities[entity as usize]
+            .components
+            .push(skinned_mesh.to_dynamic());
+    }
+
+    Ok(scene_asset)
+}
+
+/// Adds the given node, its mesh primitives and its children to the scene. Returns the node's scene entity id.
+fn load_node(
+    node: &gltf::Node,
+    parent: Option<u32>,
+    meshes: &[GltfMesh],
+    materials: &[(String, Handle<StandardMaterial>, StandardMaterial)],
+    scene: &mut Scene,
+    node_entities: &mut HashMap<usize, u32>,
+    skinned_entities: &mut Vec<(u32, usize)>,
+) -> u32 {
+    let entity = scene.entities.len() as u32;
+    scene.entities.push(bevy_scene::Entity {
+        entity,
+        components: Vec::new(),
+    });
+    node_entities.insert(node.index(), entity);
+
+    let mut children = Vec::new();
+    if let Some(mesh) = node.mesh() {
+        for primitive in meshes[mesh.index()].primitives.iter() {
+            let primitive_entity = scene.entities.len() as u32;
+            let mut components = PbrComponents {
+                mesh: primitive.mesh.clone(),
+                material: primitive.material.clone().unwrap_or_default(),
+                ..Default::default()
+            };
+            components.draw.is_transparent = materials.iter().any(|(_label, handle, material)| {
+                *handle == components.material && material.alpha_mode == AlphaMode::Blend
+            });
+            scene.entities.push(bevy_scene::Entity {
+                entity: primitive_entity,
+                components: vec![
+                    components.mesh.to_dynamic(),
+                    components.material.to_dynamic(),
+                    components.main_pass.to_dynamic(),
+                    components.draw.to_dynamic(),
+                    components.render_pipelines.to_dynamic(),
+                    components.transform.to_dynamic(),
+                    components.global_transform.to_dynamic(),
+                    Parent(Entity::new(entity)).to_dynamic(),
+                ],
+            });
+            if let Some(skin) = node.skin() {
+                skinned_entities.push((primitive_entity, skin.index()));
+            }
+            children.push(Entity::new(primitive_entity));
         }
     }
 
-    if let Some(child) = node.children().next() {
-        return Ok(load_node(buffer_data, &child, depth + 1)?);
+    for child in node.children() {
+        let child_entity = load_node(
+            &child,
+            Some(entity),
+            meshes,
+            materials,
+            scene,
+            node_entities,
+            skinned_entities,
+        );
+        children.push(Entity::new(child_entity));
     }
 
-    panic!("failed to find mesh")
+    let mut components: Vec<DynamicProperties> = vec![
+        node_transform(node).to_dynamic(),
+        GlobalTransform::default().to_dynamic(),
+    ];
+    components.push(Labels::from(vec![node_label(node)]).to_dynamic());
+    if let Some(parent) = parent {
+        components.push(Parent(Entity::new(parent)).to_dynamic());
+    }
+    if !children.is_empty() {
+        components.push(Children::with(&children).to_dynamic());
+    }
+
+    scene.entities[entity as usize].components = components;
+    entity
 }
 
-fn load_buffers(gltf: &gltf::Gltf, asset_path: &Path) -> Result<Vec<Vec<u8>>, GltfError> {
+async fn load_buffers(
+    gltf: &gltf::Gltf,
+    load_context: &LoadContext,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffer_data = Vec::new();
+    for (buffer, embedded_data) in gltf.buffers().zip(load_embedded_buffers(gltf)?) {
+        match (embedded_data, buffer.source()) {
+            (Some(data), _) => buffer_data.push(data),
+            (None, Source::Uri(uri)) => {
+                let buffer_path = load_context
+                    .path()
+                    .parent()
+                    .ok_or_else(|| GltfError::InvalidBufferUri {
+                        uri: uri.to_string(),
+                    })?
+                    .join(uri);
+                let buffer_bytes = load_context.read_asset_bytes(buffer_path).await?;
+                buffer_data.push(buffer_bytes);
+            }
+            (None, Source::Bin) => return Err(GltfError::MissingBlob),
+        }
+    }
+
+    Ok(buffer_data)
+}
+
+/// Reads the buffers that are stored in the GLTF file itself, either in its binary chunk or in base64 data uris.
+/// Buffers that are stored in their own files are `None`.
+pub(crate) fn load_embedded_buffers(gltf: &gltf::Gltf) -> Result<Vec<Option<Vec<u8>>>, GltfError> {
     const OCTET_STREAM_URI: &str = "data:application/octet-stream;base64,";
 
     let mut buffer_data = Vec::new();
@@ -121,19 +648,17 @@ fn load_buffers(gltf: &gltf::Gltf, asset_path: &Path) -> Result<Vec<Vec<u8>>, Gl
             Source::Uri(uri) => {
                 if uri.starts_with("data:") {
                     if uri.starts_with(OCTET_STREAM_URI) {
-                        buffer_data.push(base64::decode(&uri[OCTET_STREAM_URI.len()..])?);
+                        buffer_data.push(Some(base64::decode(&uri[OCTET_STREAM_URI.len()..])?));
                     } else {
                         return Err(GltfError::BufferFormatUnsupported);
                     }
                 } else {
-                    let buffer_path = asset_path.parent().unwrap().join(uri);
-                    let buffer_bytes = fs::read(buffer_path)?;
-                    buffer_data.push(buffer_bytes);
+                    buffer_data.push(None);
                 }
             }
             Source::Bin => {
                 if let Some(blob) = gltf.blob.as_deref() {
-                    buffer_data.push(blob.into());
+                    buffer_data.push(Some(blob.into()));
                 } else {
                     return Err(GltfError::MissingBlob);
                 }
@@ -143,3 +668,117 @@ fn load_buffers(gltf: &gltf::Gltf, asset_path: &Path) -> Result<Vec<Vec<u8>>, Gl
 
     Ok(buffer_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::{AssetIo, FileAssetIo};
+    use bevy_property::PropertiesVal;
+    use futures_lite::future;
+    use std::{any::type_name, sync::Arc};
+
+    #[test]
+    fn load_scene_hierarchy() {
+        let asset_io = Arc::new(FileAssetIo::new(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."),
+        ));
+        let path = Path::new("assets/models/cube/cube.gltf");
+        let bytes = future::block_on(asset_io.load_path(path)).unwrap();
+        let mut load_context = LoadContext::with_asset_io(path, asset_io);
+        let gltf = future::block_on(load_gltf(bytes, &mut load_context)).unwrap();
+
+        assert_eq!(gltf.meshes.len(), 1);
+        assert_eq!(
+            gltf.meshes[0].primitives[0].mesh,
+            load_context.get_handle("Mesh0/Primitive0")
+        );
+        assert!(load_context.get::<Mesh>("Mesh0/Primitive0").is_some());
+        assert_eq!(
+            load_context.len(),
+            gltf.images.len() + gltf.materials.len() + 1 + gltf.scenes.len()
+        );
+        assert_eq!(
+            gltf.meshes[0].primitives[0].material,
+            gltf.materials.first().cloned()
+        );
+
+        // the mesh node's primitive is spawned as its own child entity
+        assert_eq!(gltf.default_scene, Some(load_context.get_handle("Scene0")));
+        let scene = load_context.get::<Scene>("Scene0").unwrap();
+        let primitive = scene
+            .entities
+            .iter()
+            .find(|entity| {
+                entity
+                    .components
+                    .iter()
+                    .any(|component| component.type_name == type_name::<Handle<Mesh>>())
+            })
+            .unwrap();
+        let parent = primitive
+            .components
+            .iter()
+            .find(|component| component.type_name == type_name::<Parent>())
+            .unwrap();
+        let parent_entity = parent.prop_val::<Entity>("0").unwrap();
+        assert!(scene
+            .entities
+            .iter()
+            .any(|entity| Entity::new(entity.entity) == *parent_entity));
+    }
+
+    #[test]
+    fn skip_unsupported_images() {
+        let gltf = r#"{
+            "asset": { "version": "2.0" },
+            "images": [
+                { "uri": "data:image/gif;base64,R0lGODlh" },
+                { "uri": "texture.tga" }
+            ]
+        }"#;
+        let mut load_context = LoadContext::new(Path::new("models/unsupported.gltf"));
+        let gltf =
+            future::block_on(load_gltf(gltf.as_bytes().to_vec(), &mut load_context)).unwrap();
+
+        assert_eq!(gltf.images, vec![None, None]);
+        assert_eq!(load_context.len(), 0);
+    }
+
+    #[test]
+    fn reject_truncated_image_buffers() {
+        // the buffer only holds 3 of its 16 bytes
+        let gltf = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 16, "uri": "data:application/octet-stream;base64,AAAA" }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 16 }],
+            "images": [{ "bufferView": 0, "mimeType": "image/png" }]
+        }"#;
+        let mut load_context = LoadContext::new(Path::new("models/truncated.gltf"));
+        let result = future::block_on(load_gltf(gltf.as_bytes().to_vec(), &mut load_context));
+
+        assert!(matches!(
+            result,
+            Err(GltfError::BufferViewOutOfBounds { view: 0 })
+        ));
+    }
+
+    #[test]
+    fn reject_skins_with_too_many_joints() {
+        let joints = vec!["0"; MAX_JOINTS + 1].join(", ");
+        let gltf = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "nodes": [{{}}],
+                "skins": [{{ "joints": [{}] }}]
+            }}"#,
+            joints
+        );
+        let mut load_context = LoadContext::new(Path::new("models/skinned.gltf"));
+        let result = future::block_on(load_gltf(gltf.into_bytes(), &mut load_context));
+
+        assert!(matches!(
+            result,
+            Err(GltfError::TooManyJoints { skin: 0, joints }) if joints == MAX_JOINTS + 1
+        ));
+    }
+}
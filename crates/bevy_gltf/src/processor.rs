@@ -0,0 +1,189 @@
+use crate::{load_embedded_buffers, load_primitive, GltfError};
+use anyhow::Result;
+use bevy_asset::{AssetMeta, AssetProcessor};
+use bevy_render::mesh::{decode_mesh, encode_mesh, Mesh};
+use bevy_utils::HashMap;
+use std::{convert::TryInto, path::Path};
+
+const PROCESSED_GLTF_MAGIC: &[u8; 4] = b"BGLT";
+
+/// Primitives that were decoded ahead of time, keyed by their mesh and primitive index
+pub(crate) type DecodedPrimitives = HashMap<(usize, usize), Mesh>;
+
+/// Decodes the mesh primitives of GLTF files ahead of time into `.bgltf` files, so they don't need to be decoded every
+/// time the file is loaded. A `.bgltf` file stores the source GLTF file along with each of its primitives encoded with
+/// [encode_mesh], and is loaded by the [GltfLoader](crate::GltfLoader).
+///
+/// Primitives are only decoded ahead of time if all of the file's buffers are stored in the file itself, in its binary
+/// chunk or in base64 data uris. Processed files are only invalidated when the source file changes, so primitives of
+/// files with buffers in their own `.bin` files are still decoded when the file is loaded.
+#[derive(Clone, Default)]
+pub struct GltfProcessor;
+
+impl AssetProcessor for GltfProcessor {
+    fn process(&self, _asset_path: &Path, bytes: Vec<u8>, _meta: &AssetMeta) -> Result<Vec<u8>> {
+        let gltf = gltf::Gltf::from_slice(&bytes)?;
+        let mut primitives = Vec::new();
+        if let Some(buffer_data) = load_embedded_buffers(&gltf)?
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+        {
+            for mesh in gltf.meshes() {
+                for primitive in mesh.primitives() {
+                    let mesh_asset = load_primitive(&buffer_data, &mesh, &primitive)?;
+                    primitives.push((mesh.index(), primitive.index(), encode_mesh(&mesh_asset)));
+                }
+            }
+        }
+
+        Ok(encode_processed_gltf(&bytes, &primitives))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["gltf", "glb"];
+        EXTENSIONS
+    }
+
+    fn processed_extension(&self) -> &str {
+        "bgltf"
+    }
+}
+
+fn encode_processed_gltf(source: &[u8], primitives: &[(usize, usize, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(PROCESSED_GLTF_MAGIC);
+    bytes.extend_from_slice(&(source.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(source);
+    bytes.extend_from_slice(&(primitives.len() as u32).to_le_bytes());
+    for (mesh, primitive, mesh_bytes) in primitives.iter() {
+        bytes.extend_from_slice(&(*mesh as u32).to_le_bytes());
+        bytes.extend_from_slice(&(*primitive as u32).to_le_bytes());
+        bytes.extend_from_slice(&(mesh_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(mesh_bytes);
+    }
+    bytes
+}
+
+/// Returns true if the bytes are a `.bgltf` file that was created by the [GltfProcessor]
+pub(crate) fn is_processed_gltf(bytes: &[u8]) -> bool {
+    bytes.starts_with(PROCESSED_GLTF_MAGIC)
+}
+
+/// Splits a `.bgltf` file into its source GLTF file and its decoded primitives
+pub(crate) fn decode_processed_gltf(bytes: &[u8]) -> Result<(&[u8], DecodedPrimitives), GltfError> {
+    let mut reader = ProcessedGltfReader {
+        bytes: &bytes[PROCESSED_GLTF_MAGIC.len()..],
+    };
+    let source_len = reader.read_u32()?;
+    let source = reader.read_bytes(source_len)?;
+    let primitive_count = reader.read_u32()?;
+    let mut primitives = HashMap::default();
+    for _ in 0..primitive_count {
+        let mesh = reader.read_u32()?;
+        let primitive = reader.read_u32()?;
+        let mesh_len = reader.read_u32()?;
+        primitives.insert(
+            (mesh, primitive),
+            decode_mesh(reader.read_bytes(mesh_len)?)?,
+        );
+    }
+
+    Ok((source, primitives))
+}
+
+struct ProcessedGltfReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ProcessedGltfReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], GltfError> {
+        if len > self.bytes.len() {
+            return Err(GltfError::InvalidProcessedFile);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<usize, GltfError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_gltf;
+    use bevy_asset::{AssetIo, FileAssetIo, LoadContext};
+    use futures_lite::future;
+    use std::sync::Arc;
+
+    #[test]
+    fn load_processed_gltf() {
+        // a triangle with its positions stored in a data uri
+        let gltf = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{
+                "byteLength": 36,
+                "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA"
+            }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0, 0, 0],
+                "max": [1, 1, 0]
+            }],
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+        }"#;
+        let processed = GltfProcessor
+            .process(
+                Path::new("models/triangle.gltf"),
+                gltf.as_bytes().to_vec(),
+                &AssetMeta::default(),
+            )
+            .unwrap();
+        let (source, primitives) = decode_processed_gltf(&processed).unwrap();
+        assert_eq!(source, gltf.as_bytes());
+        assert_eq!(primitives.len(), 1);
+
+        let mut load_context = LoadContext::new(Path::new("models/triangle.gltf"));
+        let loaded = future::block_on(load_gltf(processed, &mut load_context)).unwrap();
+        assert_eq!(loaded.meshes.len(), 1);
+        let mesh = load_context.get::<Mesh>("Mesh0/Primitive0").unwrap();
+        assert_eq!(
+            mesh.attributes[0].values.get_bytes(),
+            primitives[&(0, 0)].attributes[0].values.get_bytes()
+        );
+
+        // claims a longer source than the file holds
+        let mut truncated = PROCESSED_GLTF_MAGIC.to_vec();
+        truncated.extend_from_slice(&16u32.to_le_bytes());
+        assert!(matches!(
+            decode_processed_gltf(&truncated),
+            Err(GltfError::InvalidProcessedFile)
+        ));
+    }
+
+    #[test]
+    fn skip_primitives_with_external_buffers() {
+        let asset_io = Arc::new(FileAssetIo::new(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."),
+        ));
+        let path = Path::new("assets/models/cube/cube.gltf");
+        let bytes = future::block_on(asset_io.load_path(path)).unwrap();
+        let processed = GltfProcessor
+            .process(path, bytes, &AssetMeta::default())
+            .unwrap();
+        let (_source, primitives) = decode_processed_gltf(&processed).unwrap();
+        assert!(primitives.is_empty());
+
+        // the primitives are decoded from the cube's buffer file when it is loaded
+        let mut load_context = LoadContext::with_asset_io(path, asset_io);
+        future::block_on(load_gltf(processed, &mut load_context)).unwrap();
+        assert!(load_context.get::<Mesh>("Mesh0/Primitive0").is_some());
+    }
+}
@@ -33,11 +33,21 @@ impl Default for PbrComponents {
                             bind_group: 2,
                             binding: 0,
                         },
+                        // JointMatrices (skinned meshes only)
+                        DynamicBinding {
+                            bind_group: 2,
+                            binding: 1,
+                        },
                         // StandardMaterial_albedo
                         DynamicBinding {
                             bind_group: 3,
                             binding: 0,
                         },
+                        // StandardMaterial_alpha_mode
+                        DynamicBinding {
+                            bind_group: 3,
+                            binding: 3,
+                        },
                     ],
                     ..Default::default()
                 },
@@ -9,11 +9,15 @@ pub use light::*;
 pub use material::*;
 
 pub mod prelude {
-    pub use crate::{entity::*, light::Light, material::StandardMaterial};
+    pub use crate::{
+        entity::*,
+        light::Light,
+        material::{AlphaMode, StandardMaterial},
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Assets};
 use bevy_ecs::IntoQuerySystem;
 use bevy_render::{render_graph::RenderGraph, shader};
 use bevy_type_registry::RegisterType;
@@ -36,5 +40,12 @@ impl Plugin for PbrPlugin {
         let resources = app.resources();
         let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
         add_pbr_graph(&mut render_graph, resources);
+
+        // add default StandardMaterial, which is used by entities (such as glTF primitives) that don't specify one
+        let mut materials = app
+            .resources()
+            .get_mut::<Assets<StandardMaterial>>()
+            .unwrap();
+        materials.add_default(StandardMaterial::default());
     }
 }
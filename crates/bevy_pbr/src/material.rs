@@ -1,5 +1,12 @@
 use bevy_asset::{self, Handle};
-use bevy_render::{color::Color, renderer::RenderResources, shader::ShaderDefs, texture::Texture};
+use bevy_core::Bytes;
+use bevy_render::{
+    color::Color,
+    impl_render_resource_bytes,
+    renderer::{RenderResource, RenderResourceType, RenderResources},
+    shader::{ShaderDef, ShaderDefs},
+    texture::Texture,
+};
 
 /// A material with "standard" properties used in PBR lighting
 #[derive(RenderResources, ShaderDefs)]
@@ -11,6 +18,8 @@ pub struct StandardMaterial {
     #[render_resources(ignore)]
     #[shader_def]
     pub shaded: bool,
+    #[shader_def]
+    pub alpha_mode: AlphaMode,
 
     // this is a manual implementation of the non exhaustive pattern,
     // especially made to allow ..Default::default()
@@ -25,6 +34,7 @@ impl Default for StandardMaterial {
             albedo: Color::rgb(1.0, 1.0, 1.0),
             albedo_texture: None,
             shaded: true,
+            alpha_mode: AlphaMode::Opaque,
             __non_exhaustive: (),
         }
     }
@@ -47,3 +57,44 @@ impl From<Handle<Texture>> for StandardMaterial {
         }
     }
 }
+
+/// Determines how the alpha channel of a [StandardMaterial]'s color is used
+///
+/// NOTE: [AlphaMode::Blend] materials should be drawn with `Draw::is_transparent` set, so they are sorted back-to-front
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaMode {
+    /// Alpha is ignored
+    Opaque,
+    /// Fragments with an alpha value below the given cutoff are discarded
+    Mask(f32),
+    /// Alpha is used to blend the material with whatever is behind it
+    Blend,
+}
+
+impl Default for AlphaMode {
+    fn default() -> Self {
+        AlphaMode::Opaque
+    }
+}
+
+impl ShaderDef for AlphaMode {
+    fn is_defined(&self) -> bool {
+        matches!(self, AlphaMode::Mask(_))
+    }
+}
+
+impl Bytes for AlphaMode {
+    fn write_bytes(&self, buffer: &mut [u8]) {
+        let cutoff = match *self {
+            AlphaMode::Mask(cutoff) => cutoff,
+            _ => 0.0,
+        };
+        cutoff.write_bytes(buffer);
+    }
+
+    fn byte_len(&self) -> usize {
+        std::mem::size_of::<f32>()
+    }
+}
+
+impl_render_resource_bytes!(AlphaMode);
@@ -32,6 +32,12 @@ layout(set = 3, binding = 1) uniform texture2D StandardMaterial_albedo_texture;
 layout(set = 3, binding = 2) uniform sampler StandardMaterial_albedo_texture_sampler;
 # endif
 
+# ifdef STANDARDMATERIAL_ALPHA_MODE
+layout(set = 3, binding = 3) uniform StandardMaterial_alpha_mode {
+    float AlphaCutoff;
+};
+# endif
+
 void main() {
     vec4 output_color = Albedo;
 # ifdef STANDARDMATERIAL_ALBEDO_TEXTURE
@@ -40,6 +46,12 @@ void main() {
         v_Uv);
 # endif
 
+# ifdef STANDARDMATERIAL_ALPHA_MODE
+    if (output_color.a < AlphaCutoff) {
+        discard;
+    }
+# endif
+
 # ifdef STANDARDMATERIAL_SHADED
     vec3 normal = normalize(v_Normal);
     vec3 ambient = vec3(0.05, 0.05, 0.05);
@@ -3,6 +3,10 @@
 layout(location = 0) in vec3 Vertex_Position;
 layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
+# ifdef SKINNED
+layout(location = 3) in vec4 SkinnedVertex_JointIndices;
+layout(location = 4) in vec4 SkinnedVertex_JointWeights;
+# endif
 
 layout(location = 0) out vec3 v_Position;
 layout(location = 1) out vec3 v_Normal;
@@ -16,10 +20,25 @@ layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
 };
 
+# ifdef SKINNED
+layout(set = 2, binding = 1) uniform JointMatrices {
+    mat4 Joints[64];
+};
+# endif
+
 void main() {
-    v_Normal = (Model * vec4(Vertex_Normal, 1.0)).xyz;
-    v_Normal = mat3(Model) * Vertex_Normal;
-    v_Position = (Model * vec4(Vertex_Position, 1.0)).xyz;
+# ifdef SKINNED
+    // joint matrices are in world space, so they replace the Model matrix
+    mat4 model =
+        SkinnedVertex_JointWeights.x * Joints[int(SkinnedVertex_JointIndices.x)] +
+        SkinnedVertex_JointWeights.y * Joints[int(SkinnedVertex_JointIndices.y)] +
+        SkinnedVertex_JointWeights.z * Joints[int(SkinnedVertex_JointIndices.z)] +
+        SkinnedVertex_JointWeights.w * Joints[int(SkinnedVertex_JointIndices.w)];
+# else
+    mat4 model = Model;
+# endif
+    v_Normal = mat3(model) * Vertex_Normal;
+    v_Position = (model * vec4(Vertex_Position, 1.0)).xyz;
     v_Uv = Vertex_Uv;
     gl_Position = ViewProj * vec4(v_Position, 1.0);
 }
@@ -62,6 +62,30 @@ impl DynamicProperties {
             self.push(prop, Some(name));
         }
     }
+
+    /// Renames the `old_name` property to `new_name`. Returns false if there is no `old_name` property.
+    pub fn rename(&mut self, old_name: &str, new_name: &str) -> bool {
+        if let Some(index) = self.prop_indices.remove(old_name) {
+            let cow_name: Cow<'static, str> = Cow::Owned(new_name.to_string());
+            self.prop_names[index] = cow_name.clone();
+            self.prop_indices.insert(cow_name, index);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes the `name` property and returns it
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Property>> {
+        let index = self.prop_indices.remove(name)?;
+        self.prop_names.remove(index);
+        for prop_index in self.prop_indices.values_mut() {
+            if *prop_index > index {
+                *prop_index -= 1;
+            }
+        }
+        Some(self.props.remove(index))
+    }
 }
 
 impl Properties for DynamicProperties {
@@ -1,24 +1,125 @@
-use crate::{impl_property, property_serde::Serializable, Property, PropertyTypeRegistry};
+use crate::{
+    property_serde::{Serializable, TYPE_FIELD, VALUE_FIELD},
+    DeserializeProperty, Property, PropertyTypeRegistry,
+};
 use bevy_ecs::Entity;
 use erased_serde::Deserializer;
-use serde::Deserialize;
-
-impl_property!(Entity, serialize_entity, deserialize_entity);
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Serialize,
+};
+use std::{any::Any, convert::TryFrom};
 
 mod private {
-    use serde::{Deserialize, Serialize};
-    #[derive(Serialize, Deserialize)]
+    use serde::Serialize;
+    #[derive(Serialize)]
     pub(super) struct Entity(pub(super) u32);
 }
 
-fn serialize_entity(entity: &Entity) -> Serializable {
-    Serializable::Owned(Box::new(private::Entity(entity.id())))
+/// Serializes entities with their type, so they can be deserialized inside of [DynamicProperties](crate::DynamicProperties)
+struct EntitySerializer(u32);
+
+impl Serialize for EntitySerializer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry(TYPE_FIELD, std::any::type_name::<Entity>())?;
+        state.serialize_entry(VALUE_FIELD, &private::Entity(self.0))?;
+        state.end()
+    }
+}
+
+/// Reads an entity id, either as the `(id)` newtype written by older versions or as a bare integer
+pub(crate) struct EntityVisitor;
+
+impl<'de> Visitor<'de> for EntityVisitor {
+    type Value = Entity;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("entity id")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let id =
+            u32::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))?;
+        Ok(Entity::new(id))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let id = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(Entity::new(id))
+    }
+}
+
+impl Property for Entity {
+    #[inline]
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_prop(&self) -> Box<dyn Property> {
+        Box::new(*self)
+    }
+
+    #[inline]
+    fn apply(&mut self, value: &dyn Property) {
+        self.set(value);
+    }
+
+    fn set(&mut self, value: &dyn Property) {
+        // entities saved without their type are read back as plain integer ids
+        if let Some(prop) = value.any().downcast_ref::<Self>() {
+            *self = *prop;
+        } else {
+            let mut id = 0u32;
+            id.set(value);
+            *self = Entity::new(id);
+        }
+    }
+
+    fn serializable<'a>(&'a self, _registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
+        Serializable::Owned(Box::new(EntitySerializer(self.id())))
+    }
 }
 
-fn deserialize_entity(
-    deserializer: &mut dyn Deserializer,
-    _registry: &PropertyTypeRegistry,
-) -> Result<Box<dyn Property>, erased_serde::Error> {
-    let entity = private::Entity::deserialize(deserializer)?;
-    Ok(Box::new(Entity::new(entity.0)))
+impl DeserializeProperty for Entity {
+    fn deserialize(
+        deserializer: &mut dyn Deserializer,
+        _property_type_registry: &PropertyTypeRegistry,
+    ) -> Result<Box<dyn Property>, erased_serde::Error> {
+        let entity = serde::Deserializer::deserialize_any(deserializer, EntityVisitor)?;
+        Ok(Box::new(entity))
+    }
 }
@@ -2,3 +2,5 @@ mod impl_property_bevy_ecs;
 mod impl_property_glam;
 mod impl_property_smallvec;
 mod impl_property_std;
+
+pub(crate) use impl_property_bevy_ecs::EntityVisitor;
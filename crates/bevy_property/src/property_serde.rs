@@ -1,4 +1,7 @@
-use crate::{DynamicProperties, Properties, Property, PropertyType, PropertyTypeRegistry};
+use crate::{
+    impl_property::EntityVisitor, DynamicProperties, Properties, Property, PropertyType,
+    PropertyTypeRegistry,
+};
 use de::SeqAccess;
 use serde::{
     de::{self, DeserializeSeed, MapAccess, Visitor},
@@ -10,6 +13,7 @@ pub const TYPE_FIELD: &str = "type";
 pub const MAP_FIELD: &str = "map";
 pub const SEQ_FIELD: &str = "seq";
 pub const VALUE_FIELD: &str = "value";
+pub const VERSION_FIELD: &str = "version";
 
 pub enum Serializable<'a> {
     Owned(Box<dyn erased_serde::Serialize + 'a>),
@@ -114,17 +118,34 @@ fn format_type_name<'a>(registry: &'a PropertyTypeRegistry, type_name: &'a str)
     registry.format_type_name(type_name).unwrap_or(type_name)
 }
 
+/// Writes the version of the type if it has migrations. Properties without a version are read as version 0.
+fn serialize_version<M>(
+    state: &mut M,
+    registry: &PropertyTypeRegistry,
+    type_name: &str,
+) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+{
+    let version = registry.get_version(type_name);
+    if version > 0 {
+        state.serialize_entry(VERSION_FIELD, &version)?;
+    }
+    Ok(())
+}
+
 impl<'a> Serialize for MapSerializer<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_map(Some(2))?;
+        let mut state = serializer.serialize_map(None)?;
 
         state.serialize_entry(
             TYPE_FIELD,
             format_type_name(self.registry, self.properties.type_name()),
         )?;
+        serialize_version(&mut state, self.registry, self.properties.type_name())?;
         state.serialize_entry(
             MAP_FIELD,
             &MapValueSerializer {
@@ -174,11 +195,12 @@ impl<'a> Serialize for SeqSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_map(Some(2))?;
+        let mut state = serializer.serialize_map(None)?;
         state.serialize_entry(
             TYPE_FIELD,
             format_type_name(self.registry, self.properties.type_name()),
         )?;
+        serialize_version(&mut state, self.registry, self.properties.type_name())?;
         state.serialize_entry(
             SEQ_FIELD,
             &SeqValueSerializer {
@@ -473,6 +495,14 @@ impl<'a, 'de> Visitor<'de> for AnyPropVisiter<'a> {
         Ok(Box::new(v.to_string()))
     }
 
+    // entities used to be serialized as an untyped `(id)` newtype, the only sequence that can appear here
+    fn visit_seq<V>(self, seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        Ok(Box::new(EntityVisitor.visit_seq(seq)?))
+    }
+
     fn visit_map<V>(self, map: V) -> Result<Self::Value, V::Error>
     where
         V: MapAccess<'de>,
@@ -497,32 +527,29 @@ where
     V: MapAccess<'de>,
 {
     let mut type_name: Option<String> = None;
+    let mut version = 0;
+    // the version may come after the payload, so migrations run once every key has been read
+    let mut dynamic_properties: Option<DynamicProperties> = None;
     while let Some(key) = map.next_key::<String>()? {
         match key.as_str() {
             TYPE_FIELD => {
                 type_name = Some(map.next_value()?);
             }
-            MAP_FIELD => {
-                let type_name = type_name
-                    .take()
-                    .ok_or_else(|| de::Error::missing_field(TYPE_FIELD))?;
-                let mut dynamic_properties =
-                    map.next_value_seed(MapPropertyDeserializer { registry })?;
-                dynamic_properties.type_name = type_name;
-                return Ok(DynamicPropertiesOrProperty::DynamicProperties(
-                    dynamic_properties,
+            VERSION_FIELD => {
+                version = map.next_value()?;
+            }
+            MAP_FIELD | SEQ_FIELD if dynamic_properties.is_some() => {
+                return Err(de::Error::custom(
+                    "Maps in this location must only have one of the fields \'map\', \'seq\'",
                 ));
             }
+            MAP_FIELD => {
+                dynamic_properties =
+                    Some(map.next_value_seed(MapPropertyDeserializer { registry })?);
+            }
             SEQ_FIELD => {
-                let type_name = type_name
-                    .take()
-                    .ok_or_else(|| de::Error::missing_field(TYPE_FIELD))?;
-                let mut dynamic_properties =
-                    map.next_value_seed(SeqPropertyDeserializer { registry })?;
-                dynamic_properties.type_name = type_name;
-                return Ok(DynamicPropertiesOrProperty::DynamicProperties(
-                    dynamic_properties,
-                ));
+                dynamic_properties =
+                    Some(map.next_value_seed(SeqPropertyDeserializer { registry })?);
             }
             VALUE_FIELD => {
                 let type_name = type_name
@@ -539,5 +566,33 @@ where
         }
     }
 
+    if let Some(mut dynamic_properties) = dynamic_properties {
+        dynamic_properties.type_name =
+            type_name.ok_or_else(|| de::Error::missing_field(TYPE_FIELD))?;
+        migrate(&mut dynamic_properties, version, registry)?;
+        return Ok(DynamicPropertiesOrProperty::DynamicProperties(
+            dynamic_properties,
+        ));
+    }
+
     Err(de::Error::custom("Maps in this location must have the \'type\' field and one of the following fields: \'map\', \'seq\', \'value\'"))
 }
+
+fn migrate<E>(
+    dynamic_properties: &mut DynamicProperties,
+    version: u32,
+    registry: &PropertyTypeRegistry,
+) -> Result<(), E>
+where
+    E: de::Error,
+{
+    let current_version = registry.get_version(&dynamic_properties.type_name);
+    if version > current_version {
+        return Err(E::custom(format!(
+            "{} was saved with version {}, which is newer than its current version {}",
+            dynamic_properties.type_name, version, current_version
+        )));
+    }
+    registry.migrate(dynamic_properties, version);
+    Ok(())
+}
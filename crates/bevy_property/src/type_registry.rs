@@ -1,12 +1,17 @@
-use crate::{DeserializeProperty, Property};
+use crate::{DeserializeProperty, DynamicProperties, Property};
 use bevy_utils::{HashMap, HashSet};
 use std::any::TypeId;
 
+/// Transforms the [DynamicProperties] of one version of a type into the next version of the type
+pub type PropertiesMigrationFn = fn(&mut DynamicProperties);
+
 #[derive(Default)]
 pub struct PropertyTypeRegistry {
     registrations: HashMap<String, PropertyTypeRegistration>,
     short_names: HashMap<String, String>,
     ambigous_names: HashSet<String>,
+    migrations: HashMap<String, Vec<PropertiesMigrationFn>>,
+    renamed_types: HashMap<String, String>,
 }
 
 impl PropertyTypeRegistry {
@@ -32,12 +37,59 @@ impl PropertyTypeRegistry {
             .insert(registration.name.to_string(), registration);
     }
 
-    pub fn get(&self, type_name: &str) -> Option<&PropertyTypeRegistration> {
-        if let Some(long_name) = self.short_names.get(type_name) {
-            self.registrations.get(long_name)
-        } else {
-            self.registrations.get(type_name)
+    /// Registers a migration from the current version of `T` to the next version. The version of a type is the number
+    /// of migrations registered for it, so migrations have to be registered in order, starting with the migration from
+    /// version 0.
+    ///
+    /// Migrations run after the saved properties are deserialized, so they can't fix fields that no longer
+    /// deserialize, like fields whose type was removed or renamed without [register_rename](Self::register_rename).
+    pub fn register_migration<T: Property>(&mut self, migration: PropertiesMigrationFn) {
+        self.migrations
+            .entry(std::any::type_name::<T>().to_string())
+            .or_insert_with(Vec::new)
+            .push(migration);
+    }
+
+    /// Registers a previous name of `T`, so properties saved with the old name are read as `T`
+    pub fn register_rename<T: Property>(&mut self, old_name: &str) {
+        self.renamed_types
+            .insert(old_name.to_string(), std::any::type_name::<T>().to_string());
+    }
+
+    /// Returns the full name of the type with the given short, full or previous name
+    fn get_full_name<'a>(&'a self, type_name: &'a str) -> &'a str {
+        self.short_names
+            .get(type_name)
+            .or_else(|| self.renamed_types.get(type_name))
+            .map(|name| name.as_str())
+            .unwrap_or(type_name)
+    }
+
+    /// Returns the current version of the type, which is the number of migrations registered for it
+    pub fn get_version(&self, type_name: &str) -> u32 {
+        self.migrations
+            .get(self.get_full_name(type_name))
+            .map_or(0, |migrations| migrations.len() as u32)
+    }
+
+    /// Brings properties that were saved with `version` of their type up to date. Properties saved with a previous
+    /// name of their type are renamed, then the migrations registered after `version` are applied in order.
+    pub fn migrate(&self, properties: &mut DynamicProperties, version: u32) {
+        if let Some(type_name) = self.renamed_types.get(&properties.type_name) {
+            properties.type_name = type_name.clone();
         }
+        if let Some(migrations) = self
+            .migrations
+            .get(self.get_full_name(&properties.type_name))
+        {
+            for migration in migrations.iter().skip(version as usize) {
+                migration(properties);
+            }
+        }
+    }
+
+    pub fn get(&self, type_name: &str) -> Option<&PropertyTypeRegistration> {
+        self.registrations.get(self.get_full_name(type_name))
     }
 
     pub fn format_type_name(&self, type_name: &str) -> Option<&str> {
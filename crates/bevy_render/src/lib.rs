@@ -45,12 +45,15 @@ use render_graph::{
     RenderGraph,
 };
 use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
+use shader::ShaderLoader;
 use std::ops::Range;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
-#[cfg(feature = "png")]
+#[cfg(any(feature = "png", feature = "jpeg"))]
 use texture::ImageTextureLoader;
-use texture::TextureResourceSystemState;
+#[cfg(feature = "png")]
+use texture::{ImageTextureProcessor, ImageTextureSaver};
+use texture::{TextureLoader, TextureResourceSystemState};
 
 /// The names of "render" App stages
 pub mod stage {
@@ -80,10 +83,15 @@ impl Default for RenderPlugin {
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        #[cfg(feature = "png")]
+        #[cfg(any(feature = "png", feature = "jpeg"))]
         {
             app.add_asset_loader::<Texture, ImageTextureLoader>();
         }
+        #[cfg(feature = "png")]
+        {
+            app.add_asset_saver::<Texture, ImageTextureSaver>()
+                .add_asset_processor::<ImageTextureProcessor>();
+        }
         #[cfg(feature = "hdr")]
         {
             app.add_asset_loader::<Texture, HdrTextureLoader>();
@@ -96,7 +104,9 @@ impl Plugin for RenderPlugin {
             .add_stage_after(stage::RENDER, stage::POST_RENDER)
             .add_asset::<Mesh>()
             .add_asset::<Texture>()
+            .add_asset_loader::<Texture, TextureLoader>()
             .add_asset::<Shader>()
+            .add_asset_loader::<Shader, ShaderLoader>()
             .add_asset::<PipelineDescriptor>()
             .register_component::<Camera>()
             .register_component::<Draw>()
@@ -1,4 +1,4 @@
-use super::Vertex;
+use super::{SkinnedVertex, Vertex};
 use crate::{
     pipeline::{
         AsVertexBufferDescriptor, PrimitiveTopology, RenderPipelines, VertexBufferDescriptor,
@@ -17,6 +17,7 @@ use thiserror::Error;
 
 pub const VERTEX_BUFFER_ASSET_INDEX: usize = 0;
 pub const INDEX_BUFFER_ASSET_INDEX: usize = 1;
+pub const SKINNED_VERTEX_BUFFER_ASSET_INDEX: usize = 2;
 #[derive(Clone, Debug)]
 pub enum VertexAttributeValues {
     Float(Vec<f32>),
@@ -71,6 +72,8 @@ impl VertexAttribute {
     pub const NORMAL: &'static str = "Vertex_Normal";
     pub const POSITION: &'static str = "Vertex_Position";
     pub const UV: &'static str = "Vertex_Uv";
+    pub const JOINT_INDICES: &'static str = "SkinnedVertex_JointIndices";
+    pub const JOINT_WEIGHTS: &'static str = "SkinnedVertex_JointWeights";
 
     pub fn position(positions: Vec<[f32; 3]>) -> Self {
         VertexAttribute {
@@ -92,6 +95,22 @@ impl VertexAttribute {
             values: VertexAttributeValues::Float2(uvs),
         }
     }
+
+    /// The indices of the (up to four) skeleton joints that influence each vertex
+    pub fn joint_indices(joint_indices: Vec<[f32; 4]>) -> Self {
+        VertexAttribute {
+            name: Self::JOINT_INDICES.into(),
+            values: VertexAttributeValues::Float4(joint_indices),
+        }
+    }
+
+    /// How much each of the vertex's joints (see [VertexAttribute::joint_indices]) influences it
+    pub fn joint_weights(joint_weights: Vec<[f32; 4]>) -> Self {
+        VertexAttribute {
+            name: Self::JOINT_WEIGHTS.into(),
+            values: VertexAttributeValues::Float4(joint_weights),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -128,6 +147,13 @@ impl Mesh {
         }
     }
 
+    /// Returns true if the mesh has joint attributes, which are stored in a separate [SkinnedVertex] buffer
+    pub fn is_skinned(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|a| a.name == VertexAttribute::JOINT_INDICES)
+    }
+
     pub fn get_vertex_buffer_bytes(
         &self,
         vertex_buffer_descriptor: &VertexBufferDescriptor,
@@ -472,7 +498,7 @@ pub mod shape {
 
 fn remove_current_mesh_resources(
     render_resource_context: &dyn RenderResourceContext,
-    handle: Handle<Mesh>,
+    handle: &Handle<Mesh>,
 ) {
     if let Some(RenderResourceId::Buffer(buffer)) =
         render_resource_context.get_asset_resource(handle, VERTEX_BUFFER_ASSET_INDEX)
@@ -486,6 +512,12 @@ fn remove_current_mesh_resources(
         render_resource_context.remove_buffer(buffer);
         render_resource_context.remove_asset_resource(handle, INDEX_BUFFER_ASSET_INDEX);
     }
+    if let Some(RenderResourceId::Buffer(buffer)) =
+        render_resource_context.get_asset_resource(handle, SKINNED_VERTEX_BUFFER_ASSET_INDEX)
+    {
+        render_resource_context.remove_buffer(buffer);
+        render_resource_context.remove_asset_resource(handle, SKINNED_VERTEX_BUFFER_ASSET_INDEX);
+    }
 }
 
 #[derive(Default)]
@@ -508,6 +540,7 @@ pub fn mesh_resource_provider_system(
             // TODO: allow pipelines to specialize on vertex_buffer_descriptor and index_format
             let vertex_buffer_descriptor = Vertex::as_vertex_buffer_descriptor();
             vertex_buffer_descriptors.set(vertex_buffer_descriptor.clone());
+            vertex_buffer_descriptors.set(SkinnedVertex::as_vertex_buffer_descriptor().clone());
             state.vertex_buffer_descriptor = Some(vertex_buffer_descriptor);
             vertex_buffer_descriptor
         }
@@ -517,14 +550,15 @@ pub fn mesh_resource_provider_system(
     for event in state.mesh_event_reader.iter(&mesh_events) {
         match event {
             AssetEvent::Created { handle } => {
-                changed_meshes.insert(*handle);
+                changed_meshes.insert(handle.clone_weak());
             }
             AssetEvent::Modified { handle } => {
-                changed_meshes.insert(*handle);
-                remove_current_mesh_resources(render_resource_context, *handle);
+                changed_meshes.insert(handle.clone_weak());
+                remove_current_mesh_resources(render_resource_context, handle);
             }
             AssetEvent::Removed { handle } => {
-                remove_current_mesh_resources(render_resource_context, *handle);
+                // the mesh was freed, so its gpu buffers are freed as well
+                remove_current_mesh_resources(render_resource_context, handle);
                 // if mesh was modified and removed in the same update, ignore the modification
                 // events are ordered so future modification events are ok
                 changed_meshes.remove(handle);
@@ -556,34 +590,53 @@ pub fn mesh_resource_provider_system(
             );
 
             render_resource_context.set_asset_resource(
-                *changed_mesh_handle,
+                changed_mesh_handle,
                 RenderResourceId::Buffer(vertex_buffer),
                 VERTEX_BUFFER_ASSET_INDEX,
             );
             render_resource_context.set_asset_resource(
-                *changed_mesh_handle,
+                changed_mesh_handle,
                 RenderResourceId::Buffer(index_buffer),
                 INDEX_BUFFER_ASSET_INDEX,
             );
+
+            // skinning attributes are kept out of the main vertex buffer so unskinned meshes keep a small stride
+            if mesh.is_skinned() {
+                let skinned_vertex_bytes = mesh
+                    .get_vertex_buffer_bytes(SkinnedVertex::as_vertex_buffer_descriptor(), true)
+                    .unwrap();
+                let skinned_vertex_buffer = render_resource_context.create_buffer_with_data(
+                    BufferInfo {
+                        buffer_usage: BufferUsage::VERTEX,
+                        ..Default::default()
+                    },
+                    &skinned_vertex_bytes,
+                );
+                render_resource_context.set_asset_resource(
+                    changed_mesh_handle,
+                    RenderResourceId::Buffer(skinned_vertex_buffer),
+                    SKINNED_VERTEX_BUFFER_ASSET_INDEX,
+                );
+            }
         }
     }
 
     // TODO: remove this once batches are pipeline specific and deprecate assigned_meshes draw target
     for (handle, mut render_pipelines) in &mut query.iter() {
-        if let Some(mesh) = meshes.get(&handle) {
+        if let Some(mesh) = meshes.get(handle) {
             for render_pipeline in render_pipelines.pipelines.iter_mut() {
                 render_pipeline.specialization.primitive_topology = mesh.primitive_topology;
             }
         }
 
         if let Some(RenderResourceId::Buffer(vertex_buffer)) =
-            render_resource_context.get_asset_resource(*handle, VERTEX_BUFFER_ASSET_INDEX)
+            render_resource_context.get_asset_resource(&handle, VERTEX_BUFFER_ASSET_INDEX)
         {
             render_pipelines.bindings.set_vertex_buffer(
                 "Vertex",
                 vertex_buffer,
                 render_resource_context
-                    .get_asset_resource(*handle, INDEX_BUFFER_ASSET_INDEX)
+                    .get_asset_resource(&handle, INDEX_BUFFER_ASSET_INDEX)
                     .and_then(|r| {
                         if let RenderResourceId::Buffer(buffer) = r {
                             Some(buffer)
@@ -593,6 +646,16 @@ pub fn mesh_resource_provider_system(
                     }),
             );
         }
+
+        if let Some(RenderResourceId::Buffer(skinned_vertex_buffer)) =
+            render_resource_context.get_asset_resource(&handle, SKINNED_VERTEX_BUFFER_ASSET_INDEX)
+        {
+            render_pipelines.bindings.set_vertex_buffer(
+                "SkinnedVertex",
+                skinned_vertex_buffer,
+                None,
+            );
+        }
     }
 }
 
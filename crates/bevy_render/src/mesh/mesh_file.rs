@@ -0,0 +1,208 @@
+use super::{Indices, Mesh, VertexAttribute, VertexAttributeValues};
+use crate::pipeline::PrimitiveTopology;
+use std::convert::TryInto;
+use thiserror::Error;
+
+const MESH_MAGIC: &[u8; 4] = b"BMSH";
+
+/// An error that occurs when decoding a mesh that was encoded with [encode_mesh]
+#[derive(Error, Debug)]
+pub enum MeshFileError {
+    #[error("Not an encoded mesh.")]
+    InvalidHeader,
+    #[error("Unknown primitive topology.")]
+    UnknownTopology { topology: u32 },
+    #[error("Mesh data is truncated or malformed.")]
+    InvalidData,
+}
+
+/// Encodes a [Mesh] in a binary format that can be decoded with [decode_mesh] without any further processing. This is
+/// used to store meshes that were decoded from other formats, such as GLTF, so they don't need to be decoded again.
+pub fn encode_mesh(mesh: &Mesh) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MESH_MAGIC);
+    write_u32(&mut bytes, mesh.primitive_topology as u32);
+    write_u32(&mut bytes, mesh.attributes.len() as u32);
+    for attribute in mesh.attributes.iter() {
+        write_u32(&mut bytes, attribute.name.len() as u32);
+        bytes.extend_from_slice(attribute.name.as_bytes());
+        let (components, values): (u32, Vec<f32>) = match &attribute.values {
+            VertexAttributeValues::Float(values) => (1, values.clone()),
+            VertexAttributeValues::Float2(values) => {
+                (2, values.iter().flatten().cloned().collect())
+            }
+            VertexAttributeValues::Float3(values) => {
+                (3, values.iter().flatten().cloned().collect())
+            }
+            VertexAttributeValues::Float4(values) => {
+                (4, values.iter().flatten().cloned().collect())
+            }
+        };
+        write_u32(&mut bytes, components);
+        write_u32(&mut bytes, attribute.values.len() as u32);
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    match &mesh.indices {
+        None => write_u32(&mut bytes, 0),
+        Some(Indices::U16(indices)) => {
+            write_u32(&mut bytes, 1);
+            write_u32(&mut bytes, indices.len() as u32);
+            for index in indices.iter() {
+                bytes.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+        Some(Indices::U32(indices)) => {
+            write_u32(&mut bytes, 2);
+            write_u32(&mut bytes, indices.len() as u32);
+            for index in indices.iter() {
+                bytes.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+/// Decodes a [Mesh] that was encoded with [encode_mesh]
+pub fn decode_mesh(bytes: &[u8]) -> Result<Mesh, MeshFileError> {
+    if bytes.len() < MESH_MAGIC.len() || &bytes[0..MESH_MAGIC.len()] != MESH_MAGIC {
+        return Err(MeshFileError::InvalidHeader);
+    }
+
+    let mut reader = MeshReader {
+        bytes: &bytes[MESH_MAGIC.len()..],
+    };
+    let primitive_topology = match reader.read_u32()? {
+        0 => PrimitiveTopology::PointList,
+        1 => PrimitiveTopology::LineList,
+        2 => PrimitiveTopology::LineStrip,
+        3 => PrimitiveTopology::TriangleList,
+        4 => PrimitiveTopology::TriangleStrip,
+        topology => return Err(MeshFileError::UnknownTopology { topology }),
+    };
+    let mut mesh = Mesh::new(primitive_topology);
+
+    let attribute_count = reader.read_u32()?;
+    for _ in 0..attribute_count {
+        let name_len = reader.read_u32()? as usize;
+        let name = String::from_utf8(reader.read_bytes(name_len)?.to_vec())
+            .map_err(|_| MeshFileError::InvalidData)?;
+        let components = reader.read_u32()? as usize;
+        if !(1..=4).contains(&components) {
+            return Err(MeshFileError::InvalidData);
+        }
+        let len = reader.read_u32()? as usize;
+        let values = reader
+            .read_bytes(
+                len.checked_mul(components * 4)
+                    .ok_or(MeshFileError::InvalidData)?,
+            )?
+            .chunks_exact(4)
+            .map(|value| f32::from_le_bytes(value.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let values = match components {
+            1 => VertexAttributeValues::Float(values),
+            2 => VertexAttributeValues::Float2(
+                values.chunks_exact(2).map(|v| [v[0], v[1]]).collect(),
+            ),
+            3 => VertexAttributeValues::Float3(
+                values.chunks_exact(3).map(|v| [v[0], v[1], v[2]]).collect(),
+            ),
+            4 => VertexAttributeValues::Float4(
+                values
+                    .chunks_exact(4)
+                    .map(|v| [v[0], v[1], v[2], v[3]])
+                    .collect(),
+            ),
+            _ => unreachable!(),
+        };
+        mesh.attributes.push(VertexAttribute {
+            name: name.into(),
+            values,
+        });
+    }
+
+    mesh.indices = match reader.read_u32()? {
+        0 => None,
+        1 => {
+            let len = reader.read_u32()? as usize;
+            let indices = reader
+                .read_bytes(len.checked_mul(2).ok_or(MeshFileError::InvalidData)?)?
+                .chunks_exact(2)
+                .map(|index| u16::from_le_bytes(index.try_into().unwrap()))
+                .collect();
+            Some(Indices::U16(indices))
+        }
+        2 => {
+            let len = reader.read_u32()? as usize;
+            let indices = reader
+                .read_bytes(len.checked_mul(4).ok_or(MeshFileError::InvalidData)?)?
+                .chunks_exact(4)
+                .map(|index| u32::from_le_bytes(index.try_into().unwrap()))
+                .collect();
+            Some(Indices::U32(indices))
+        }
+        _ => return Err(MeshFileError::InvalidData),
+    };
+
+    if !reader.bytes.is_empty() {
+        return Err(MeshFileError::InvalidData);
+    }
+    Ok(mesh)
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+struct MeshReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> MeshReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], MeshFileError> {
+        if len > self.bytes.len() {
+            return Err(MeshFileError::InvalidData);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, MeshFileError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_mesh() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleStrip);
+        mesh.attributes.push(VertexAttribute::position(vec![
+            [0.0, 1.0, 2.0],
+            [3.0, 4.0, 5.0],
+        ]));
+        mesh.attributes
+            .push(VertexAttribute::uv(vec![[0.0, 0.5], [1.0, -1.0]]));
+        mesh.indices = Some(Indices::U16(vec![0, 1, 0]));
+
+        let bytes = encode_mesh(&mesh);
+        let decoded = decode_mesh(&bytes).unwrap();
+        assert_eq!(decoded.primitive_topology, mesh.primitive_topology);
+        assert_eq!(decoded.attributes.len(), 2);
+        for (decoded, attribute) in decoded.attributes.iter().zip(mesh.attributes.iter()) {
+            assert_eq!(decoded.name, attribute.name);
+            assert_eq!(decoded.values.get_bytes(), attribute.values.get_bytes());
+        }
+        assert!(matches!(decoded.indices, Some(Indices::U16(indices)) if indices == vec![0, 1, 0]));
+
+        assert!(decode_mesh(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode_mesh(&[]).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 #[allow(clippy::module_inception)]
 mod mesh;
+mod mesh_file;
 mod vertex;
 
 pub use mesh::*;
+pub use mesh_file::*;
 pub use vertex::*;
@@ -12,3 +12,16 @@ pub struct Vertex {
 
 // SAFE: Vertex is repr(C) containing primitives
 unsafe impl Byteable for Vertex {}
+
+/// The skinning attributes of a vertex. These live in their own vertex buffer, which is only created for meshes
+/// that have joints, so unskinned meshes don't pay for them.
+#[repr(C)]
+#[derive(Clone, Copy, AsVertexBufferDescriptor)]
+#[as_crate(bevy_render)]
+pub struct SkinnedVertex {
+    pub joint_indices: [f32; 4],
+    pub joint_weights: [f32; 4],
+}
+
+// SAFE: SkinnedVertex is repr(C) containing primitives
+unsafe impl Byteable for SkinnedVertex {}
@@ -9,7 +9,7 @@ pub trait RenderPass {
     fn get_render_context(&self) -> &dyn RenderContext;
     fn set_index_buffer(&mut self, buffer: BufferId, offset: u64);
     fn set_vertex_buffer(&mut self, start_slot: u32, buffer: BufferId, offset: u64);
-    fn set_pipeline(&mut self, pipeline_handle: Handle<PipelineDescriptor>);
+    fn set_pipeline(&mut self, pipeline_handle: &Handle<PipelineDescriptor>);
     fn set_viewport(&mut self, x: f32, y: f32, w: f32, h: f32, min_depth: f32, max_depth: f32);
     fn set_stencil_reference(&mut self, reference: u32);
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>);
@@ -404,6 +404,52 @@ mod tests {
         assert!(output_nodes("D", &graph).is_empty(), "D has no outputs");
     }
 
+    #[cfg(feature = "graph_export")]
+    #[test]
+    pub fn test_graph_snapshot() {
+        use crate::render_graph::EdgeSnapshot;
+
+        let mut graph = RenderGraph::default();
+        graph.add_node("A", TestNode::new(0, 1));
+        graph.add_node("B", TestNode::new(0, 0));
+        graph.add_node("C", TestNode::new(1, 0));
+
+        graph.add_slot_edge("A", "out_0", "C", "in_0").unwrap();
+        graph.add_node_edge("B", "C").unwrap();
+
+        let snapshot = graph.snapshot();
+        let names = snapshot
+            .nodes
+            .iter()
+            .map(|node| node.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["A", "B", "C"]);
+        assert!(snapshot.nodes[0].type_name.ends_with("TestNode"));
+        assert_eq!(snapshot.nodes[2].inputs[0].name, "in_0");
+        assert_eq!(
+            snapshot.edges,
+            vec![
+                EdgeSnapshot::Slot {
+                    output_node: 0,
+                    output_index: 0,
+                    input_node: 2,
+                    input_index: 0,
+                },
+                EdgeSnapshot::Node {
+                    output_node: 1,
+                    input_node: 2,
+                },
+            ]
+        );
+
+        let dot = snapshot.to_dot();
+        assert!(dot.contains("n0 -> n2 [label=\"out_0 -> in_0\"];"));
+        assert!(dot.contains("n1 -> n2 [style=dashed];"));
+        assert!(snapshot
+            .to_json()
+            .contains("\"resource_type\": \"Texture\""));
+    }
+
     #[test]
     pub fn test_get_node_typed() {
         struct MyNode {
@@ -6,6 +6,8 @@ mod node;
 mod node_slot;
 mod nodes;
 mod schedule;
+#[cfg(feature = "graph_export")]
+mod snapshot;
 mod system;
 
 pub use command::*;
@@ -15,6 +17,8 @@ pub use node::*;
 pub use node_slot::*;
 pub use nodes::*;
 pub use schedule::*;
+#[cfg(feature = "graph_export")]
+pub use snapshot::*;
 pub use system::*;
 
 use thiserror::Error;
@@ -1,11 +1,12 @@
 use super::{Edge, RenderGraphError, ResourceSlotInfo, ResourceSlots};
 use crate::renderer::RenderContext;
-use bevy_ecs::{Commands, Resources, System, World};
+use bevy_ecs::{BoxedSystem, Commands, Resources, World};
 use downcast_rs::{impl_downcast, Downcast};
 use std::{borrow::Cow, fmt::Debug};
 use uuid::Uuid;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "graph_export", derive(serde::Serialize))]
 pub struct NodeId(Uuid);
 
 impl NodeId {
@@ -37,7 +38,7 @@ pub trait Node: Downcast + Send + Sync + 'static {
 impl_downcast!(Node);
 
 pub trait SystemNode: Node {
-    fn get_system(&self, commands: &mut Commands) -> Box<dyn System>;
+    fn get_system(&self, commands: &mut Commands) -> BoxedSystem;
 }
 
 pub struct Edges {
@@ -107,6 +108,7 @@ impl Edges {
 pub struct NodeState {
     pub id: NodeId,
     pub name: Option<Cow<'static, str>>,
+    pub type_name: &'static str,
     pub node: Box<dyn Node>,
     pub input_slots: ResourceSlots,
     pub output_slots: ResourceSlots,
@@ -127,6 +129,7 @@ impl NodeState {
         NodeState {
             id,
             name: None,
+            type_name: std::any::type_name::<T>(),
             input_slots: ResourceSlots::from(node.input()),
             output_slots: ResourceSlots::from(node.output()),
             node: Box::new(node),
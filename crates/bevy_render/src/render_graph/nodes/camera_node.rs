@@ -8,7 +8,9 @@ use crate::{
 };
 use bevy_core::AsBytes;
 
-use bevy_ecs::{Commands, IntoQuerySystem, Local, Query, Res, ResMut, Resources, System, World};
+use bevy_ecs::{
+    BoxedSystem, Commands, IntoQuerySystem, Local, Query, Res, ResMut, Resources, World,
+};
 use bevy_transform::prelude::*;
 use std::borrow::Cow;
 
@@ -43,7 +45,7 @@ impl Node for CameraNode {
 }
 
 impl SystemNode for CameraNode {
-    fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
+    fn get_system(&self, commands: &mut Commands) -> BoxedSystem {
         let system = camera_node_system.system();
         commands.insert_local_resource(
             system.id(),
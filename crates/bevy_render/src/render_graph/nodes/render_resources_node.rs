@@ -9,9 +9,9 @@ use crate::{
     texture,
 };
 
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{Assets, Handle, HandleId};
 use bevy_ecs::{
-    Commands, Entity, IntoQuerySystem, Local, Query, Res, ResMut, Resources, System, World,
+    BoxedSystem, Commands, Entity, IntoQuerySystem, Local, Query, Res, ResMut, Resources, World,
 };
 use bevy_utils::HashMap;
 use renderer::{AssetRenderResourceBindings, BufferId, RenderResourceType, RenderResources};
@@ -392,7 +392,7 @@ impl<T> SystemNode for RenderResourcesNode<T>
 where
     T: renderer::RenderResources,
 {
-    fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
+    fn get_system(&self, commands: &mut Commands) -> BoxedSystem {
         let system = render_resources_node_system::<T>.system();
         commands.insert_local_resource(
             system.id(),
@@ -437,8 +437,8 @@ fn render_resources_node_system<T: RenderResources>(
         uniform_buffer_arrays.initialize(first);
     }
 
-    for entity in query.removed::<T>() {
-        uniform_buffer_arrays.remove_bindings(*entity);
+    for entity in query.removed::<T>().iter() {
+        uniform_buffer_arrays.remove_bindings(entity);
     }
 
     for (entity, uniforms, draw, mut render_pipelines) in &mut query.iter() {
@@ -549,13 +549,13 @@ impl<T> SystemNode for AssetRenderResourcesNode<T>
 where
     T: renderer::RenderResources,
 {
-    fn get_system(&self, commands: &mut Commands) -> Box<dyn System> {
+    fn get_system(&self, commands: &mut Commands) -> BoxedSystem {
         let system = asset_render_resources_node_system::<T>.system();
         commands.insert_local_resource(
             system.id(),
             RenderResourcesNodeState {
                 command_queue: self.command_queue.clone(),
-                uniform_buffer_arrays: UniformBufferArrays::<Handle<T>, T>::default(),
+                uniform_buffer_arrays: UniformBufferArrays::<HandleId, T>::default(),
                 dynamic_uniforms: self.dynamic_uniforms,
             },
         );
@@ -565,7 +565,7 @@ where
 }
 
 fn asset_render_resources_node_system<T: RenderResources>(
-    mut state: Local<RenderResourcesNodeState<Handle<T>, T>>,
+    mut state: Local<RenderResourcesNodeState<HandleId, T>>,
     assets: Res<Assets<T>>,
     mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
@@ -588,9 +588,9 @@ fn asset_render_resources_node_system<T: RenderResources>(
     }
 
     for asset_handle in modified_assets.iter() {
-        let asset = assets.get(&asset_handle).expect(EXPECT_ASSET_MESSAGE);
-        uniform_buffer_arrays.prepare_uniform_buffers(*asset_handle, asset);
-        let mut bindings = asset_render_resource_bindings.get_or_insert_mut(*asset_handle);
+        let asset = assets.get(asset_handle).expect(EXPECT_ASSET_MESSAGE);
+        uniform_buffer_arrays.prepare_uniform_buffers(asset_handle.id, asset);
+        let mut bindings = asset_render_resource_bindings.get_or_insert_mut(asset_handle);
         setup_uniform_texture_resources::<T>(&asset, render_resource_context, &mut bindings);
     }
 
@@ -604,12 +604,12 @@ fn asset_render_resources_node_system<T: RenderResources>(
             0..state.uniform_buffer_arrays.staging_buffer_size as u64,
             &mut |mut staging_buffer, _render_resource_context| {
                 for asset_handle in modified_assets.iter() {
-                    let asset = assets.get(&asset_handle).expect(EXPECT_ASSET_MESSAGE);
+                    let asset = assets.get(asset_handle).expect(EXPECT_ASSET_MESSAGE);
                     let mut render_resource_bindings =
-                        asset_render_resource_bindings.get_or_insert_mut(*asset_handle);
+                        asset_render_resource_bindings.get_or_insert_mut(asset_handle);
                     // TODO: only setup buffer if we haven't seen this handle before
                     state.uniform_buffer_arrays.write_uniform_buffers(
-                        *asset_handle,
+                        asset_handle.id,
                         &asset,
                         state.dynamic_uniforms,
                         render_resource_context,
@@ -627,12 +627,12 @@ fn asset_render_resources_node_system<T: RenderResources>(
     } else {
         let mut staging_buffer: [u8; 0] = [];
         for asset_handle in modified_assets.iter() {
-            let asset = assets.get(&asset_handle).expect(EXPECT_ASSET_MESSAGE);
+            let asset = assets.get(asset_handle).expect(EXPECT_ASSET_MESSAGE);
             let mut render_resource_bindings =
-                asset_render_resource_bindings.get_or_insert_mut(*asset_handle);
+                asset_render_resource_bindings.get_or_insert_mut(asset_handle);
             // TODO: only setup buffer if we haven't seen this handle before
             state.uniform_buffer_arrays.write_uniform_buffers(
-                *asset_handle,
+                asset_handle.id,
                 &asset,
                 state.dynamic_uniforms,
                 render_resource_context,
@@ -646,7 +646,7 @@ fn asset_render_resources_node_system<T: RenderResources>(
         if !draw.is_visible {
             continue;
         }
-        if let Some(asset_bindings) = asset_render_resource_bindings.get(*asset_handle) {
+        if let Some(asset_bindings) = asset_render_resource_bindings.get(asset_handle) {
             render_pipelines.bindings.extend(asset_bindings);
         }
     }
@@ -1,7 +1,7 @@
 use crate::{
     render_graph::{Node, ResourceSlots},
     renderer::{BufferInfo, BufferUsage, RenderContext},
-    texture::{Texture, TextureDescriptor, TEXTURE_ASSET_INDEX},
+    texture::{Extent3d, Texture, TEXTURE_ASSET_INDEX},
 };
 use bevy_app::prelude::{EventReader, Events};
 use bevy_asset::{AssetEvent, Assets};
@@ -32,44 +32,50 @@ impl Node for TextureCopyNode {
             match event {
                 AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
                     if let Some(texture) = textures.get(&handle) {
-                        let texture_descriptor: TextureDescriptor = texture.into();
-                        let width = texture.size.x() as usize;
-                        let aligned_width = get_aligned(texture.size.x());
-                        let format_size = texture.format.pixel_size();
-                        let mut aligned_data =
-                            vec![0; format_size * aligned_width * texture.size.y() as usize];
-                        texture
-                            .data
-                            .chunks_exact(format_size * width)
-                            .enumerate()
-                            .for_each(|(index, row)| {
-                                let offset = index * aligned_width * format_size;
-                                aligned_data[offset..(offset + width * format_size)]
-                                    .copy_from_slice(row);
-                            });
-                        let texture_buffer = render_context.resources().create_buffer_with_data(
-                            BufferInfo {
-                                buffer_usage: BufferUsage::COPY_SRC,
-                                ..Default::default()
-                            },
-                            &aligned_data,
-                        );
-
                         let texture_resource = render_context
                             .resources()
-                            .get_asset_resource(*handle, TEXTURE_ASSET_INDEX)
+                            .get_asset_resource(handle, TEXTURE_ASSET_INDEX)
                             .unwrap();
+                        let levels =
+                            std::iter::once(&texture.data).chain(texture.mip_levels.iter());
+                        for (mip_level, data) in levels.enumerate() {
+                            let (width, height) = texture.mip_level_size(mip_level as u32);
+                            let width = width as usize;
+                            let aligned_width = get_aligned(width as f32);
+                            let format_size = texture.format.pixel_size();
+                            let mut aligned_data =
+                                vec![0; format_size * aligned_width * height as usize];
+                            data.chunks_exact(format_size * width).enumerate().for_each(
+                                |(index, row)| {
+                                    let offset = index * aligned_width * format_size;
+                                    aligned_data[offset..(offset + width * format_size)]
+                                        .copy_from_slice(row);
+                                },
+                            );
+                            let texture_buffer =
+                                render_context.resources().create_buffer_with_data(
+                                    BufferInfo {
+                                        buffer_usage: BufferUsage::COPY_SRC,
+                                        ..Default::default()
+                                    },
+                                    &aligned_data,
+                                );
 
-                        render_context.copy_buffer_to_texture(
-                            texture_buffer,
-                            0,
-                            (format_size * aligned_width) as u32,
-                            texture_resource.get_texture().unwrap(),
-                            [0, 0, 0],
-                            0,
-                            texture_descriptor.size,
-                        );
-                        render_context.resources().remove_buffer(texture_buffer);
+                            render_context.copy_buffer_to_texture(
+                                texture_buffer,
+                                0,
+                                (format_size * aligned_width) as u32,
+                                texture_resource.get_texture().unwrap(),
+                                [0, 0, 0],
+                                mip_level as u32,
+                                Extent3d {
+                                    width: width as u32,
+                                    height,
+                                    depth: 1,
+                                },
+                            );
+                            render_context.resources().remove_buffer(texture_buffer);
+                        }
                     }
                 }
                 AssetEvent::Removed { .. } => {}
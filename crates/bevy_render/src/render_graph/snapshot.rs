@@ -0,0 +1,178 @@
+use super::{Edge, NodeId, RenderGraph, ResourceSlots};
+use crate::renderer::RenderResourceType;
+use bevy_utils::HashMap;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// A snapshot of the nodes of a [RenderGraph], their slots and the edges between them.
+///
+/// Created by [RenderGraph::snapshot] and exported with [RenderGraphSnapshot::to_dot] or
+/// [RenderGraphSnapshot::to_json].
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderGraphSnapshot {
+    /// The nodes of the graph, sorted by name
+    pub nodes: Vec<NodeSnapshot>,
+    pub edges: Vec<EdgeSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeSnapshot {
+    pub id: NodeId,
+    pub name: Option<String>,
+    pub type_name: String,
+    pub inputs: Vec<SlotSnapshot>,
+    pub outputs: Vec<SlotSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlotSnapshot {
+    pub name: String,
+    pub resource_type: RenderResourceType,
+}
+
+/// An edge of a [RenderGraphSnapshot]. Nodes are referred to by their index in [RenderGraphSnapshot::nodes], and
+/// the input node runs after the output node.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub enum EdgeSnapshot {
+    /// Passes the resource in an output slot of one node to an input slot of another
+    Slot {
+        output_node: usize,
+        output_index: usize,
+        input_node: usize,
+        input_index: usize,
+    },
+    /// Only orders two nodes
+    Node {
+        output_node: usize,
+        input_node: usize,
+    },
+}
+
+impl RenderGraph {
+    /// Creates a [RenderGraphSnapshot] of the graph's nodes and edges, for inspecting the graph offline
+    pub fn snapshot(&self) -> RenderGraphSnapshot {
+        let mut node_states = self.iter_nodes().collect::<Vec<_>>();
+        node_states.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+        let indices = node_states
+            .iter()
+            .enumerate()
+            .map(|(index, node_state)| (node_state.id, index))
+            .collect::<HashMap<_, _>>();
+
+        let slots = |slots: &ResourceSlots| {
+            slots
+                .iter()
+                .map(|slot| SlotSnapshot {
+                    name: slot.info.name.to_string(),
+                    resource_type: slot.info.resource_type.clone(),
+                })
+                .collect()
+        };
+        let nodes = node_states
+            .iter()
+            .map(|node_state| NodeSnapshot {
+                id: node_state.id,
+                name: node_state.name.as_ref().map(|name| name.to_string()),
+                type_name: node_state.type_name.to_string(),
+                inputs: slots(&node_state.input_slots),
+                outputs: slots(&node_state.output_slots),
+            })
+            .collect();
+
+        // every edge is stored by both of its nodes, so only the output edges are collected
+        let mut edges = node_states
+            .iter()
+            .flat_map(|node_state| node_state.edges.output_edges.iter())
+            .map(|edge| match *edge {
+                Edge::SlotEdge {
+                    input_node,
+                    input_index,
+                    output_node,
+                    output_index,
+                } => EdgeSnapshot::Slot {
+                    output_node: indices[&output_node],
+                    output_index,
+                    input_node: indices[&input_node],
+                    input_index,
+                },
+                Edge::NodeEdge {
+                    input_node,
+                    output_node,
+                } => EdgeSnapshot::Node {
+                    output_node: indices[&output_node],
+                    input_node: indices[&input_node],
+                },
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+
+        RenderGraphSnapshot { nodes, edges }
+    }
+}
+
+impl RenderGraphSnapshot {
+    /// Renders the graph in the Graphviz DOT format. Slot edges are labeled with the slots they connect, and node
+    /// edges are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph render_graph {{").unwrap();
+        writeln!(dot, "    node [shape=box];").unwrap();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = match node.name {
+                Some(ref name) => format!("{}\n{}", name, node.type_name),
+                None => node.type_name.clone(),
+            };
+            writeln!(dot, "    n{} [label=\"{}\"];", index, escape(&label)).unwrap();
+        }
+
+        for edge in self.edges.iter() {
+            match *edge {
+                EdgeSnapshot::Slot {
+                    output_node,
+                    output_index,
+                    input_node,
+                    input_index,
+                } => {
+                    let label = format!(
+                        "{} -> {}",
+                        self.nodes[output_node].outputs[output_index].name,
+                        self.nodes[input_node].inputs[input_index].name
+                    );
+                    writeln!(
+                        dot,
+                        "    n{} -> n{} [label=\"{}\"];",
+                        output_node,
+                        input_node,
+                        escape(&label)
+                    )
+                    .unwrap();
+                }
+                EdgeSnapshot::Node {
+                    output_node,
+                    input_node,
+                } => {
+                    writeln!(
+                        dot,
+                        "    n{} -> n{} [style=dashed];",
+                        output_node, input_node
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Serializes the graph to pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("render graph snapshots are always serializable")
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
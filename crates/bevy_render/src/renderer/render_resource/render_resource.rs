@@ -7,6 +7,7 @@ pub use bevy_derive::{RenderResource, RenderResources};
 use bevy_math::{Mat4, Vec2, Vec3, Vec4};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "graph_export", derive(serde::Serialize))]
 pub enum RenderResourceType {
     Buffer,
     Texture,
@@ -76,7 +77,7 @@ pub trait RenderResource {
     fn write_buffer_bytes(&self, buffer: &mut [u8]);
     fn buffer_byte_len(&self) -> Option<usize>;
     // TODO: consider making these panic by default, but return non-options
-    fn texture(&self) -> Option<Handle<Texture>>;
+    fn texture(&self) -> Option<&Handle<Texture>>;
 }
 
 pub trait RenderResources: Send + Sync + 'static {
@@ -135,7 +136,7 @@ macro_rules! impl_render_resource_bytes {
                 Some(self.byte_len())
             }
 
-            fn texture(&self) -> Option<Handle<Texture>> {
+            fn texture(&self) -> Option<&Handle<Texture>> {
                 None
             }
         }
@@ -174,7 +175,7 @@ where
         Some(self.byte_len())
     }
 
-    fn texture(&self) -> Option<Handle<Texture>> {
+    fn texture(&self) -> Option<&Handle<Texture>> {
         None
     }
 }
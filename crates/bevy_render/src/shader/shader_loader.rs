@@ -0,0 +1,143 @@
+use super::{Shader, ShaderSource, ShaderStage};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, AssetMeta, AssetProcessor};
+use std::{convert::TryInto, path::Path};
+use thiserror::Error;
+
+const SHADER_MAGIC: &[u8; 4] = b"BSPV";
+const HEADER_SIZE: usize = 8;
+
+/// An error that occurs when loading a shader file
+#[derive(Error, Debug)]
+pub enum ShaderLoaderError {
+    #[error("Shader file extension doesn't match a shader stage.")]
+    UnknownStage { extension: String },
+    #[error("Shader source is not valid UTF-8.")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+    #[error("Not a compiled shader file.")]
+    InvalidHeader,
+}
+
+fn get_shader_stage(extension: &str) -> Result<ShaderStage, ShaderLoaderError> {
+    match extension {
+        "vert" => Ok(ShaderStage::Vertex),
+        "frag" => Ok(ShaderStage::Fragment),
+        "comp" => Ok(ShaderStage::Compute),
+        extension => Err(ShaderLoaderError::UnknownStage {
+            extension: extension.to_string(),
+        }),
+    }
+}
+
+fn encode_shader_stage(stage: ShaderStage) -> u32 {
+    match stage {
+        ShaderStage::Vertex => 0,
+        ShaderStage::Fragment => 1,
+        ShaderStage::Compute => 2,
+    }
+}
+
+/// Encodes a SPIR-V shader as a `.shader` file, which stores the shader's stage along with its SPIR-V words
+pub fn encode_shader(stage: ShaderStage, spirv: &[u32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + spirv.len() * 4);
+    bytes.extend_from_slice(SHADER_MAGIC);
+    bytes.extend_from_slice(&encode_shader_stage(stage).to_le_bytes());
+    for word in spirv.iter() {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// Decodes a `.shader` file that was encoded with [encode_shader]
+pub fn decode_shader(bytes: &[u8]) -> Result<Shader, ShaderLoaderError> {
+    if bytes.len() < HEADER_SIZE || &bytes[0..4] != SHADER_MAGIC || bytes.len() % 4 != 0 {
+        return Err(ShaderLoaderError::InvalidHeader);
+    }
+
+    let stage = match u32::from_le_bytes(bytes[4..8].try_into().unwrap()) {
+        0 => ShaderStage::Vertex,
+        1 => ShaderStage::Fragment,
+        2 => ShaderStage::Compute,
+        _ => return Err(ShaderLoaderError::InvalidHeader),
+    };
+    Ok(Shader::new(
+        stage,
+        ShaderSource::spirv_from_bytes(&bytes[HEADER_SIZE..]),
+    ))
+}
+
+/// Loads GLSL shaders (`.vert`, `.frag` and `.comp` files) and compiled `.shader` files, which are usually created by
+/// the [GlslShaderProcessor]
+#[derive(Clone, Default)]
+pub struct ShaderLoader;
+
+impl AssetLoader<Shader> for ShaderLoader {
+    fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<Shader> {
+        let extension = asset_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        if extension == "shader" {
+            return Ok(decode_shader(&bytes)?);
+        }
+
+        let stage = get_shader_stage(extension)?;
+        Ok(Shader::from_glsl(stage, &String::from_utf8(bytes)?))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["vert", "frag", "comp", "shader"];
+        EXTENSIONS
+    }
+}
+
+/// Compiles GLSL shaders to SPIR-V ahead of time, so they don't need to be compiled every time they are loaded.
+///
+/// Shader defs are applied when GLSL is compiled, which happens when a pipeline is specialized. Shaders are compiled
+/// without any shader defs here, so this processor isn't added by default and should only be added with
+/// `add_asset_processor::<GlslShaderProcessor>()` when the shaders that are loaded from files don't use shader defs.
+#[derive(Clone, Default)]
+pub struct GlslShaderProcessor;
+
+impl AssetProcessor for GlslShaderProcessor {
+    fn process(&self, asset_path: &Path, bytes: Vec<u8>, _meta: &AssetMeta) -> Result<Vec<u8>> {
+        let extension = asset_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let stage = get_shader_stage(extension)?;
+        let shader = Shader::from_glsl(stage, &String::from_utf8(bytes)?);
+        Ok(encode_shader(stage, &shader.get_spirv(None)))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["vert", "frag", "comp"];
+        EXTENSIONS
+    }
+
+    fn processed_extension(&self) -> &str {
+        "shader"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_shader() {
+        let spirv = vec![0x0723_0203, 1, 2, 3];
+        let bytes = encode_shader(ShaderStage::Fragment, &spirv);
+        let shader = decode_shader(&bytes).unwrap();
+        assert_eq!(shader.stage, ShaderStage::Fragment);
+        assert_eq!(shader.source, ShaderSource::Spirv(spirv));
+
+        assert!(decode_shader(&bytes[..HEADER_SIZE - 1]).is_err());
+        assert!(ShaderLoader
+            .from_bytes(Path::new("shaders/a.geom"), Vec::new())
+            .is_err());
+        assert!(GlslShaderProcessor
+            .process(Path::new("shaders/a"), Vec::new(), &AssetMeta::default())
+            .is_err());
+    }
+}
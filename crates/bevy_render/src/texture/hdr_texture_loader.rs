@@ -1,10 +1,11 @@
-use super::{Texture, TextureFormat};
+use super::{Texture, TextureFormat, TextureLoaderSettings};
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoadContext};
 use bevy_math::Vec2;
+use bevy_utils::BoxedFuture;
 use std::path::Path;
 
-/// Loads HDR textures as Texture assets
+/// Loads HDR textures as Texture assets. The texture's [TextureLoaderSettings] can be set in its meta file.
 #[derive(Clone, Default)]
 pub struct HdrTextureLoader;
 
@@ -38,6 +39,19 @@ impl AssetLoader<Texture> for HdrTextureLoader {
         ))
     }
 
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Texture>> {
+        Box::pin(async move {
+            let settings = load_context.settings::<TextureLoaderSettings>()?;
+            let mut texture = self.from_bytes(load_context.path(), bytes)?;
+            settings.apply(&mut texture);
+            Ok(texture)
+        })
+    }
+
     fn extensions(&self) -> &[&str] {
         static EXTENSIONS: &[&str] = &["hdr"];
         EXTENSIONS
@@ -1,33 +1,86 @@
-use super::{Texture, TextureFormat};
+use super::{Texture, TextureFormat, TextureLoaderSettings};
 use anyhow::Result;
-use bevy_asset::AssetLoader;
+use bevy_asset::{AssetLoader, LoadContext};
 use bevy_math::Vec2;
+use bevy_utils::BoxedFuture;
 use std::path::Path;
+use thiserror::Error;
 
 /// Loader for images that can be read by the `image` crate.
 ///
-/// Reads only PNG images for now.
+/// Reads PNG images when the `png` feature is enabled, and JPEG images when the `jpeg` feature is enabled. The
+/// texture's [TextureLoaderSettings] can be set in its meta file.
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
 impl AssetLoader<Texture> for ImageTextureLoader {
     fn from_bytes(&self, asset_path: &Path, bytes: Vec<u8>) -> Result<Texture> {
-        use bevy_core::AsBytes;
-
         // Find the image type we expect. A file with the extension "png" should
         // probably load as a PNG.
 
-        let ext = asset_path.extension().unwrap().to_str().unwrap();
+        let ext = asset_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        ImageTextureLoader::load_image_bytes(&bytes, ext)
+    }
+
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Texture>> {
+        Box::pin(async move {
+            let settings = load_context.settings::<TextureLoaderSettings>()?;
+            let mut texture = self.from_bytes(load_context.path(), bytes)?;
+            settings.apply(&mut texture);
+            Ok(texture)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        EXTENSIONS
+    }
+}
+
+static EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "png")]
+    "png",
+    #[cfg(feature = "jpeg")]
+    "jpg",
+    #[cfg(feature = "jpeg")]
+    "jpeg",
+];
+
+/// An error that occurs when decoding an image into a [Texture]
+#[derive(Error, Debug)]
+pub enum ImageTextureLoaderError {
+    #[error("Unsupported image format.")]
+    UnsupportedFormat { extension: String },
+}
+
+impl ImageTextureLoader {
+    /// Returns true if images with the given file extension can be decoded with the enabled image format features
+    pub fn supports_extension(extension: &str) -> bool {
+        EXTENSIONS
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(extension))
+    }
+
+    /// Decodes the bytes of an image file into a [Texture]. `extension` is the file extension that corresponds to
+    /// the image's format, such as "png".
+    pub fn load_image_bytes(bytes: &[u8], extension: &str) -> Result<Texture> {
+        use bevy_core::AsBytes;
 
         // NOTE: If more formats are added they can be added here.
-        let img_format = if ext.eq_ignore_ascii_case("png") {
-            image::ImageFormat::Png
-        } else {
-            panic!(
-                "Unexpected image format {:?} for file {}, this is an error in `bevy_render`.",
-                ext,
-                asset_path.display()
-            )
+        let img_format = match image::ImageFormat::from_extension(extension) {
+            Some(format) if Self::supports_extension(extension) => format,
+            _ => {
+                return Err(ImageTextureLoaderError::UnsupportedFormat {
+                    extension: extension.to_string(),
+                }
+                .into())
+            }
         };
 
         // Load the image in the expected format.
@@ -36,7 +89,7 @@ impl AssetLoader<Texture> for ImageTextureLoader {
         // needs to be added, so the image data needs to be converted in those
         // cases.
 
-        let dyn_img = image::load_from_memory_with_format(bytes.as_slice(), img_format)?;
+        let dyn_img = image::load_from_memory_with_format(bytes, img_format)?;
 
         let width;
         let height;
@@ -149,9 +202,4 @@ impl AssetLoader<Texture> for ImageTextureLoader {
             format,
         ))
     }
-
-    fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["png"];
-        EXTENSIONS
-    }
 }
@@ -0,0 +1,109 @@
+use super::{Texture, TextureFormat};
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use std::path::Path;
+use thiserror::Error;
+
+/// Saves [Texture]s as PNG images that the [ImageTextureLoader](super::ImageTextureLoader) can read.
+///
+/// PNG images have no notion of color spaces or channel order, so textures are loaded back with the format the
+/// loader picks for the image: BGRA textures are saved as RGBA images, and all 8 bit RGBA textures are loaded as
+/// [TextureFormat::Rgba8UnormSrgb].
+#[derive(Clone, Default)]
+pub struct ImageTextureSaver;
+
+impl AssetSaver<Texture> for ImageTextureSaver {
+    fn to_bytes(&self, _asset_path: &Path, texture: &Texture) -> Result<Vec<u8>> {
+        ImageTextureSaver::save_png_bytes(texture)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["png"];
+        EXTENSIONS
+    }
+}
+
+/// An error that occurs when encoding a [Texture] as an image
+#[derive(Error, Debug)]
+pub enum ImageTextureSaverError {
+    #[error("Texture format can't be saved as an image.")]
+    UnsupportedFormat { format: TextureFormat },
+    #[error("Texture data doesn't match its size and format.")]
+    InvalidData,
+}
+
+impl ImageTextureSaver {
+    /// Encodes a [Texture] as the bytes of a PNG image
+    pub fn save_png_bytes(texture: &Texture) -> Result<Vec<u8>> {
+        let width = texture.size.x() as u32;
+        let height = texture.size.y() as u32;
+        if texture.data.len() != width as usize * height as usize * texture.format.pixel_size() {
+            return Err(ImageTextureSaverError::InvalidData.into());
+        }
+
+        let (color_type, data) = match texture.format {
+            TextureFormat::R8Unorm => (image::ColorType::L8, texture.data.clone()),
+            TextureFormat::Rg8Unorm => (image::ColorType::La8, texture.data.clone()),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+                (image::ColorType::Rgba8, texture.data.clone())
+            }
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+                let mut data = texture.data.clone();
+                for pixel in data.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+                (image::ColorType::Rgba8, data)
+            }
+            // texture data is native endian, but PNG images store 16 bit samples as big endian
+            TextureFormat::R16Uint => (image::ColorType::L16, to_big_endian(&texture.data)),
+            TextureFormat::Rg16Uint => (image::ColorType::La16, to_big_endian(&texture.data)),
+            TextureFormat::Rgba16Uint => (image::ColorType::Rgba16, to_big_endian(&texture.data)),
+            format => return Err(ImageTextureSaverError::UnsupportedFormat { format }.into()),
+        };
+
+        let mut bytes = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut bytes).encode(&data, width, height, color_type)?;
+        Ok(bytes)
+    }
+}
+
+fn to_big_endian(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(2)
+        .flat_map(|sample| {
+            u16::from_ne_bytes([sample[0], sample[1]])
+                .to_be_bytes()
+                .to_vec()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::ImageTextureLoader;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn save_and_load_png() {
+        let texture = Texture::new(
+            Vec2::new(2.0, 1.0),
+            vec![0, 1, 2, 3, 4, 5, 6, 7],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let bytes = ImageTextureSaver::save_png_bytes(&texture).unwrap();
+        let loaded = ImageTextureLoader::load_image_bytes(&bytes, "png").unwrap();
+        assert_eq!(loaded.data, texture.data);
+        assert_eq!(loaded.size, texture.size);
+        assert_eq!(loaded.format, texture.format);
+
+        let mut data = Vec::new();
+        for sample in &[1u16, 256, 65535, 4] {
+            data.extend_from_slice(&sample.to_ne_bytes());
+        }
+        let texture = Texture::new(Vec2::new(2.0, 1.0), data, TextureFormat::Rg16Uint);
+        let bytes = ImageTextureSaver::save_png_bytes(&texture).unwrap();
+        let loaded = ImageTextureLoader::load_image_bytes(&bytes, "png").unwrap();
+        assert_eq!(loaded.data, texture.data);
+        assert_eq!(loaded.format, texture.format);
+    }
+}
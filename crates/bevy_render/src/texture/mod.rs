@@ -1,18 +1,24 @@
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
-#[cfg(feature = "png")]
 mod image_texture_loader;
+#[cfg(feature = "png")]
+mod image_texture_saver;
 mod sampler_descriptor;
 #[allow(clippy::module_inception)]
 mod texture;
 mod texture_descriptor;
 mod texture_dimension;
+mod texture_loader_settings;
+mod texture_processor;
 
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
-#[cfg(feature = "png")]
 pub use image_texture_loader::*;
+#[cfg(feature = "png")]
+pub use image_texture_saver::*;
 pub use sampler_descriptor::*;
 pub use texture::*;
 pub use texture_descriptor::*;
 pub use texture_dimension::*;
+pub use texture_loader_settings::*;
+pub use texture_processor::*;
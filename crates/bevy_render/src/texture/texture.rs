@@ -16,6 +16,11 @@ pub struct Texture {
     pub data: Vec<u8>,
     pub size: Vec2,
     pub format: TextureFormat,
+    /// The sampler the texture is sampled with
+    pub sampler: SamplerDescriptor,
+    /// The data of the texture's mip levels after the first one, which is `data`. Each level is half the size of the
+    /// previous one. See [Texture::generate_mipmaps].
+    pub mip_levels: Vec<Vec<u8>>,
 }
 
 impl Default for Texture {
@@ -24,6 +29,8 @@ impl Default for Texture {
             data: Default::default(),
             size: Default::default(),
             format: TextureFormat::Rgba8UnormSrgb,
+            sampler: Default::default(),
+            mip_levels: Vec::new(),
         }
     }
 }
@@ -35,7 +42,13 @@ impl Texture {
             data.len(),
             "Pixel data, size and format have to match",
         );
-        Self { data, size, format }
+        Self {
+            data,
+            size,
+            format,
+            sampler: Default::default(),
+            mip_levels: Vec::new(),
+        }
     }
 
     pub fn new_fill(size: Vec2, pixel: &[u8], format: TextureFormat) -> Self {
@@ -65,12 +78,75 @@ impl Texture {
 
     pub fn resize(&mut self, size: Vec2) {
         self.size = size;
+        self.mip_levels.clear();
         let width = size.x() as usize;
         let height = size.y() as usize;
         self.data
             .resize(width * height * self.format.pixel_size(), 0);
     }
 
+    /// The width and height of the given mip level
+    pub fn mip_level_size(&self, level: u32) -> (u32, u32) {
+        (
+            (self.size.x() as u32 >> level).max(1),
+            (self.size.y() as u32 >> level).max(1),
+        )
+    }
+
+    /// Replaces the texture's mip levels with box filtered versions of its data, down to a size of 1x1. sRGB colors
+    /// are averaged in linear space. Only formats with 8 bits per channel are supported, other textures are left
+    /// without mip levels.
+    pub fn generate_mipmaps(&mut self) {
+        self.mip_levels.clear();
+        let color_channels = match self.format {
+            TextureFormat::R8Unorm | TextureFormat::Rg8Unorm => 0,
+            TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm => 0,
+            TextureFormat::Rgba8UnormSrgb | TextureFormat::Bgra8UnormSrgb => 3,
+            format => {
+                log::warn!("Can't generate mipmaps for {:?} textures", format);
+                return;
+            }
+        };
+        let channels = self.format.pixel_size();
+        let (width, height) = self.mip_level_size(0);
+        let level_count = 32 - width.max(height).leading_zeros();
+        for level in 1..level_count {
+            let (src_width, src_height) = self.mip_level_size(level - 1);
+            let (dst_width, dst_height) = self.mip_level_size(level);
+            let src = self.mip_levels.last().unwrap_or(&self.data);
+            let mut dst = vec![0; (dst_width * dst_height) as usize * channels];
+            for y in 0..dst_height {
+                for x in 0..dst_width {
+                    // odd sizes repeat the last row or column of the previous level
+                    let src_xs = [2 * x, (2 * x + 1).min(src_width - 1)];
+                    let src_ys = [2 * y, (2 * y + 1).min(src_height - 1)];
+                    for channel in 0..channels {
+                        let mut sum = 0.0;
+                        for src_y in src_ys.iter() {
+                            for src_x in src_xs.iter() {
+                                let index = ((src_y * src_width + src_x) as usize) * channels;
+                                let value = src[index + channel] as f32 / 255.0;
+                                sum += if channel < color_channels {
+                                    srgb_to_linear(value)
+                                } else {
+                                    value
+                                };
+                            }
+                        }
+                        let value = if channel < color_channels {
+                            linear_to_srgb(sum / 4.0)
+                        } else {
+                            sum / 4.0
+                        };
+                        dst[((y * dst_width + x) as usize) * channels + channel] =
+                            (value * 255.0).round() as u8;
+                    }
+                }
+            }
+            self.mip_levels.push(dst);
+        }
+    }
+
     pub fn texture_resource_system(
         mut state: ResMut<TextureResourceSystemState>,
         render_resource_context: Res<Box<dyn RenderResourceContext>>,
@@ -82,14 +158,15 @@ impl Texture {
         for event in state.event_reader.iter(&texture_events) {
             match event {
                 AssetEvent::Created { handle } => {
-                    changed_textures.insert(*handle);
+                    changed_textures.insert(handle.clone_weak());
                 }
                 AssetEvent::Modified { handle } => {
-                    changed_textures.insert(*handle);
-                    Self::remove_current_texture_resources(render_resource_context, *handle);
+                    changed_textures.insert(handle.clone_weak());
+                    Self::remove_current_texture_resources(render_resource_context, handle);
                 }
                 AssetEvent::Removed { handle } => {
-                    Self::remove_current_texture_resources(render_resource_context, *handle);
+                    // the texture is no longer referenced anywhere, so its gpu texture and sampler can be freed
+                    Self::remove_current_texture_resources(render_resource_context, handle);
                     // if texture was modified and removed in the same update, ignore the modification
                     // events are ordered so future modification events are ok
                     changed_textures.remove(handle);
@@ -106,12 +183,12 @@ impl Texture {
                 let sampler_resource = render_resource_context.create_sampler(&sampler_descriptor);
 
                 render_resource_context.set_asset_resource(
-                    *texture_handle,
+                    texture_handle,
                     RenderResourceId::Texture(texture_resource),
                     TEXTURE_ASSET_INDEX,
                 );
                 render_resource_context.set_asset_resource(
-                    *texture_handle,
+                    texture_handle,
                     RenderResourceId::Sampler(sampler_resource),
                     SAMPLER_ASSET_INDEX,
                 );
@@ -121,7 +198,7 @@ impl Texture {
 
     fn remove_current_texture_resources(
         render_resource_context: &dyn RenderResourceContext,
-        handle: Handle<Texture>,
+        handle: &Handle<Texture>,
     ) {
         if let Some(RenderResourceId::Texture(resource)) =
             render_resource_context.get_asset_resource(handle, TEXTURE_ASSET_INDEX)
@@ -138,6 +215,22 @@ impl Texture {
     }
 }
 
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
 #[derive(Default)]
 pub struct TextureResourceSystemState {
     event_reader: EventReader<AssetEvent<Texture>>,
@@ -145,7 +238,7 @@ pub struct TextureResourceSystemState {
 
 impl RenderResource for Option<Handle<Texture>> {
     fn resource_type(&self) -> Option<RenderResourceType> {
-        self.map(|_texture| RenderResourceType::Texture)
+        self.as_ref().map(|_texture| RenderResourceType::Texture)
     }
 
     fn write_buffer_bytes(&self, _buffer: &mut [u8]) {}
@@ -154,8 +247,8 @@ impl RenderResource for Option<Handle<Texture>> {
         None
     }
 
-    fn texture(&self) -> Option<Handle<Texture>> {
-        *self
+    fn texture(&self) -> Option<&Handle<Texture>> {
+        self.as_ref()
     }
 }
 
@@ -170,7 +263,38 @@ impl RenderResource for Handle<Texture> {
         None
     }
 
-    fn texture(&self) -> Option<Handle<Texture>> {
-        Some(*self)
+    fn texture(&self) -> Option<&Handle<Texture>> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_mipmaps() {
+        let mut texture = Texture::new(
+            Vec2::new(3.0, 2.0),
+            vec![0, 255, 0, 255, 100, 100, 0, 0, 0, 255, 100, 100],
+            TextureFormat::Rg8Unorm,
+        );
+        texture.generate_mipmaps();
+        assert_eq!(texture.mip_levels.len(), 1);
+        assert_eq!(texture.mip_level_size(1), (1, 1));
+        assert_eq!(texture.mip_levels[0], vec![0, 191]);
+        assert_eq!(TextureDescriptor::from(&texture).mip_level_count, 2);
+
+        // sRGB colors are averaged in linear space
+        let mut texture = Texture::new(
+            Vec2::new(2.0, 1.0),
+            vec![0, 0, 0, 0, 255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        texture.generate_mipmaps();
+        assert_eq!(texture.mip_levels, vec![vec![188, 188, 188, 128]]);
+
+        texture.resize(Vec2::new(4.0, 4.0));
+        assert!(texture.mip_levels.is_empty());
     }
 }
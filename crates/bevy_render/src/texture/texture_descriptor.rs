@@ -19,7 +19,7 @@ impl From<&Texture> for TextureDescriptor {
                 height: texture.size.y() as u32,
                 depth: 1,
             },
-            mip_level_count: 1,
+            mip_level_count: 1 + texture.mip_levels.len() as u32,
             sample_count: 1,
             dimension: TextureDimension::D2,
             format: texture.format,
@@ -0,0 +1,64 @@
+use super::{AddressMode, FilterMode, SamplerDescriptor, Texture, TextureFormat};
+use serde::{Deserialize, Serialize};
+
+/// Settings for loading a texture, which can be set per texture in its `.meta` file:
+///
+/// ```ron
+/// (
+///     settings: (
+///         srgb: false,
+///         mag_filter: Linear,
+///         generate_mipmaps: true,
+///     ),
+/// )
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextureLoaderSettings {
+    /// Whether the texture's colors are in sRGB space. This should be disabled for textures that don't contain
+    /// colors, such as normal maps.
+    pub srgb: bool,
+    pub address_mode: AddressMode,
+    pub mag_filter: FilterMode,
+    pub min_filter: FilterMode,
+    /// Whether to generate mip levels for the texture, which are sampled with the `mipmap_filter`
+    pub generate_mipmaps: bool,
+    pub mipmap_filter: FilterMode,
+}
+
+impl Default for TextureLoaderSettings {
+    fn default() -> Self {
+        let sampler = SamplerDescriptor::default();
+        TextureLoaderSettings {
+            srgb: true,
+            address_mode: sampler.address_mode_u,
+            mag_filter: sampler.mag_filter,
+            min_filter: sampler.min_filter,
+            generate_mipmaps: false,
+            mipmap_filter: sampler.mipmap_filter,
+        }
+    }
+}
+
+impl TextureLoaderSettings {
+    /// Applies the settings to a loaded texture
+    pub fn apply(&self, texture: &mut Texture) {
+        if !self.srgb {
+            texture.format = match texture.format {
+                TextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8UnormSrgb => TextureFormat::Bgra8Unorm,
+                format => format,
+            };
+        }
+
+        texture.sampler.address_mode_u = self.address_mode;
+        texture.sampler.address_mode_v = self.address_mode;
+        texture.sampler.address_mode_w = self.address_mode;
+        texture.sampler.mag_filter = self.mag_filter;
+        texture.sampler.min_filter = self.min_filter;
+        texture.sampler.mipmap_filter = self.mipmap_filter;
+        if self.generate_mipmaps {
+            texture.generate_mipmaps();
+        }
+    }
+}
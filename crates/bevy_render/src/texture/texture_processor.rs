@@ -0,0 +1,212 @@
+use super::{Texture, TextureFormat, TextureLoaderSettings};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext};
+use bevy_math::Vec2;
+use bevy_utils::BoxedFuture;
+use std::{convert::TryInto, path::Path};
+use thiserror::Error;
+
+#[cfg(feature = "png")]
+use super::ImageTextureLoader;
+#[cfg(feature = "png")]
+use bevy_asset::{AssetMeta, AssetProcessor};
+
+const TEXTURE_MAGIC: &[u8; 4] = b"BTEX";
+const HEADER_SIZE: usize = 16;
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// The formats that decoded textures can be stored in
+const TEXTURE_FORMATS: &[TextureFormat] = &[
+    TextureFormat::R8Unorm,
+    TextureFormat::Rg8Unorm,
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::R16Uint,
+    TextureFormat::Rg16Uint,
+    TextureFormat::Rgba16Uint,
+    TextureFormat::Rgba32Float,
+];
+
+/// An error that occurs when encoding or decoding a `.texture` file
+#[derive(Error, Debug)]
+pub enum TextureFileError {
+    #[error("Not a texture file.")]
+    InvalidHeader,
+    #[error("Texture format can't be stored in a texture file.")]
+    UnsupportedFormat { format: TextureFormat },
+    #[error("Unknown texture format.")]
+    UnknownFormat { format: u32 },
+    #[error("Texture data doesn't match its size and format.")]
+    InvalidData,
+}
+
+/// Encodes a [Texture] as a `.texture` file, which stores the texture's decoded data so it can be loaded without
+/// decoding it again. Each row is delta encoded against the previous pixel and the result is deflate compressed, which
+/// keeps `.texture` files close to the size of the PNG they were decoded from.
+pub fn encode_texture(texture: &Texture) -> Result<Vec<u8>, TextureFileError> {
+    if !TEXTURE_FORMATS.contains(&texture.format) {
+        return Err(TextureFileError::UnsupportedFormat {
+            format: texture.format,
+        });
+    }
+
+    let mut data = texture.data.clone();
+    let pixel_size = texture.format.pixel_size();
+    let row_size = texture.size.x() as usize * pixel_size;
+    if row_size > 0 {
+        for row in data.chunks_mut(row_size) {
+            // iterate backwards so each byte is subtracted from the original value of its neighbor
+            for i in (pixel_size..row.len()).rev() {
+                row[i] = row[i].wrapping_sub(row[i - pixel_size]);
+            }
+        }
+    }
+    let compressed = miniz_oxide::deflate::compress_to_vec(&data, COMPRESSION_LEVEL);
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + compressed.len());
+    bytes.extend_from_slice(TEXTURE_MAGIC);
+    bytes.extend_from_slice(&(texture.format as u32).to_le_bytes());
+    bytes.extend_from_slice(&(texture.size.x() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(texture.size.y() as u32).to_le_bytes());
+    bytes.extend_from_slice(&compressed);
+    Ok(bytes)
+}
+
+/// Decodes a `.texture` file that was encoded with [encode_texture]
+pub fn decode_texture(bytes: Vec<u8>) -> Result<Texture, TextureFileError> {
+    if bytes.len() < HEADER_SIZE || &bytes[0..4] != TEXTURE_MAGIC {
+        return Err(TextureFileError::InvalidHeader);
+    }
+
+    let read_u32 =
+        |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    let format = read_u32(4);
+    let format = TEXTURE_FORMATS
+        .iter()
+        .find(|texture_format| **texture_format as u32 == format)
+        .cloned()
+        .ok_or(TextureFileError::UnknownFormat { format })?;
+    let width = read_u32(8);
+    let height = read_u32(12);
+
+    let mut data = miniz_oxide::inflate::decompress_to_vec(&bytes[HEADER_SIZE..])
+        .map_err(|_| TextureFileError::InvalidData)?;
+    let pixel_size = format.pixel_size();
+    let row_size = width as usize * pixel_size;
+    if data.len() != row_size * height as usize {
+        return Err(TextureFileError::InvalidData);
+    }
+    if row_size > 0 {
+        for row in data.chunks_mut(row_size) {
+            for i in pixel_size..row.len() {
+                row[i] = row[i].wrapping_add(row[i - pixel_size]);
+            }
+        }
+    }
+
+    Ok(Texture::new(
+        Vec2::new(width as f32, height as f32),
+        data,
+        format,
+    ))
+}
+
+/// Loads `.texture` files, which are usually created by the [ImageTextureProcessor]. The texture's
+/// [TextureLoaderSettings] are read from the meta file of the source image.
+#[derive(Clone, Default)]
+pub struct TextureLoader;
+
+impl AssetLoader<Texture> for TextureLoader {
+    fn from_bytes(&self, _asset_path: &Path, bytes: Vec<u8>) -> Result<Texture> {
+        Ok(decode_texture(bytes)?)
+    }
+
+    fn load<'a>(
+        &'a self,
+        bytes: Vec<u8>,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Texture>> {
+        Box::pin(async move {
+            let settings = load_context.settings::<TextureLoaderSettings>()?;
+            let mut texture = self.from_bytes(load_context.path(), bytes)?;
+            settings.apply(&mut texture);
+            Ok(texture)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["texture"];
+        EXTENSIONS
+    }
+}
+
+/// Decodes images ahead of time into `.texture` files, so they don't need to be decoded every time they are loaded.
+/// The [TextureLoaderSettings] are applied when the `.texture` file is loaded.
+///
+/// `.texture` files store the decoded pixels with lossless compression (see [encode_texture]). They aren't GPU block
+/// compressed, so the texture uses as much GPU memory as when it is loaded from the source image.
+#[cfg(feature = "png")]
+#[derive(Clone, Default)]
+pub struct ImageTextureProcessor;
+
+#[cfg(feature = "png")]
+impl AssetProcessor for ImageTextureProcessor {
+    fn process(&self, asset_path: &Path, bytes: Vec<u8>, _meta: &AssetMeta) -> Result<Vec<u8>> {
+        let extension = asset_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let texture = ImageTextureLoader::load_image_bytes(&bytes, extension)?;
+        Ok(encode_texture(&texture)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["png"];
+        EXTENSIONS
+    }
+
+    fn processed_extension(&self) -> &str {
+        "texture"
+    }
+
+    fn version(&self) -> u32 {
+        // version 1 compresses the texture data
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_texture() {
+        let texture = Texture::new(
+            Vec2::new(2.0, 1.0),
+            vec![0, 1, 2, 3, 4, 5, 6, 7],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let bytes = encode_texture(&texture).unwrap();
+        let decoded = decode_texture(bytes.clone()).unwrap();
+        assert_eq!(decoded.data, texture.data);
+        assert_eq!(decoded.size, texture.size);
+        assert_eq!(decoded.format, texture.format);
+
+        assert!(decode_texture(bytes[..HEADER_SIZE + 4].to_vec()).is_err());
+        assert!(decode_texture(vec![0; HEADER_SIZE]).is_err());
+    }
+
+    #[test]
+    fn compress_texture_data() {
+        // a gradient, which compresses well once it is delta encoded
+        let data = (0..64 * 64)
+            .flat_map(|i| vec![(i % 64) as u8, (i / 64) as u8, 0, 255])
+            .collect::<Vec<_>>();
+        let texture = Texture::new(Vec2::new(64.0, 64.0), data, TextureFormat::Rgba8Unorm);
+        let bytes = encode_texture(&texture).unwrap();
+        assert!(bytes.len() < texture.data.len() / 10);
+        assert_eq!(decode_texture(bytes).unwrap().data, texture.data);
+    }
+}
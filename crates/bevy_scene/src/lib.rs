@@ -1,14 +1,20 @@
 mod loaded_scenes;
+mod save_game;
 mod scene;
+mod scene_saver;
 mod scene_spawner;
 pub mod serde;
 
 pub use loaded_scenes::*;
+pub use save_game::*;
 pub use scene::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
-    pub use crate::{Scene, SceneSpawner};
+    pub use crate::{
+        InstanceId, SaveGame, Scene, SceneInstanceReady, SceneSpawner, SnapshotFilter,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -24,6 +30,8 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<Scene>()
             .add_asset_loader::<Scene, SceneLoader>()
+            .add_asset_saver::<Scene, SceneSaver>()
+            .add_event::<SceneInstanceReady>()
             .init_resource::<SceneSpawner>()
             .add_stage_after(stage::EVENT_UPDATE, SCENE_STAGE)
             .add_system_to_stage(SCENE_STAGE, scene_spawner_system.thread_local_system());
@@ -0,0 +1,295 @@
+use crate::{
+    serde::{SaveGameDeserializer, SaveGameSerializer},
+    serialize_ron, Scene, SceneSpawnError,
+};
+use bevy_ecs::{Archetype, Component, Entity, EntityMap, Resource, Resources, World};
+use bevy_property::PropertyTypeRegistry;
+use bevy_type_registry::TypeRegistry;
+use bevy_utils::HashSet;
+use serde::de::DeserializeSeed;
+use std::{any::TypeId, path::Path};
+use thiserror::Error;
+
+/// The version of the save game format written by [SaveGame::serialize_ron]
+pub const SAVE_GAME_VERSION: u32 = 1;
+
+/// Selects the entities, components and resources that are captured in a [Scene] or [SaveGame]. The default filter
+/// includes every entity and every registered component and resource.
+#[derive(Default, Clone, Debug)]
+pub struct SnapshotFilter {
+    required_components: Vec<TypeId>,
+    excluded_components: HashSet<TypeId>,
+    excluded_resources: HashSet<TypeId>,
+}
+
+impl SnapshotFilter {
+    /// Only includes entities that have a `T` component. This is usually a marker component like `Saved`.
+    /// Mapped components like `Parent` must not refer to entities the filter leaves out, or the [SaveGame] fails to
+    /// restore.
+    pub fn with<T: Component>(mut self) -> Self {
+        self.required_components.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Leaves `T` components out
+    pub fn without_component<T: Component>(mut self) -> Self {
+        self.excluded_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Leaves the `T` resource out
+    pub fn without_resource<T: Resource>(mut self) -> Self {
+        self.excluded_resources.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn includes_entity(&self, archetype: &Archetype, index: usize) -> bool {
+        self.required_components
+            .iter()
+            .all(|ty| archetype.has_component(*ty, index))
+    }
+
+    pub fn includes_component(&self, ty: TypeId) -> bool {
+        !self.excluded_components.contains(&ty)
+    }
+
+    pub fn includes_resource(&self, ty: TypeId) -> bool {
+        !self.excluded_resources.contains(&ty)
+    }
+
+    /// Returns the entities of `world` the filter includes. Despawn these before restoring a [SaveGame] that
+    /// replaces them.
+    pub fn entities(&self, world: &World) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        for archetype in world.archetypes() {
+            for (index, entity) in archetype.iter_entities().enumerate() {
+                if self.includes_entity(archetype, index) {
+                    entities.push(*entity);
+                }
+            }
+        }
+        entities
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SaveGameError {
+    #[error(
+        "Save game version {version} is newer than the supported version {}.",
+        SAVE_GAME_VERSION
+    )]
+    UnsupportedVersion { version: u32 },
+    #[error("Failed to serialize or deserialize the save game.")]
+    Ron(#[from] ron::Error),
+    #[error("Failed to read or write the save game file.")]
+    Io(#[from] std::io::Error),
+}
+
+/// A snapshot of the registered components and resources of a [World], used to save and restore the state of a game
+pub struct SaveGame {
+    pub version: u32,
+    pub scene: Scene,
+}
+
+impl SaveGame {
+    /// Captures the entities and resources `filter` includes. Only registered components and resources are saved.
+    pub fn snapshot(world: &World, resources: &Resources, filter: &SnapshotFilter) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let mut scene = Scene::from_world_filtered(world, &type_registry.component.read(), filter);
+        scene.add_resources(resources, &type_registry.resource.read(), filter);
+        SaveGame {
+            version: SAVE_GAME_VERSION,
+            scene,
+        }
+    }
+
+    /// Spawns the saved entities as new entities and applies the saved resources. Entity references in mapped
+    /// components are remapped to the new entities. Returns the map from saved entities to spawned entities.
+    ///
+    /// If restoring fails, for example because a mapped component refers to an entity the [SnapshotFilter] left
+    /// out, the spawned entities are despawned again and no resources are changed.
+    pub fn restore(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+    ) -> Result<EntityMap, SceneSpawnError> {
+        let mut entity_map = EntityMap::default();
+        if let Err(err) = self.scene.write_to_world(world, resources, &mut entity_map) {
+            // every entity in the map was spawned by this restore
+            for entity in entity_map.values() {
+                let _ = world.despawn(entity);
+            }
+            return Err(err);
+        }
+        Ok(entity_map)
+    }
+
+    pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
+        serialize_ron(SaveGameSerializer {
+            save_game: self,
+            registry,
+        })
+    }
+
+    pub fn deserialize_ron(
+        bytes: &[u8],
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Self, SaveGameError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let save_game = SaveGameDeserializer {
+            property_type_registry: registry,
+        }
+        .deserialize(&mut deserializer)?;
+        if save_game.version > SAVE_GAME_VERSION {
+            return Err(SaveGameError::UnsupportedVersion {
+                version: save_game.version,
+            });
+        }
+        Ok(save_game)
+    }
+
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        path: P,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<(), SaveGameError> {
+        std::fs::write(path, self.serialize_ron(registry)?)?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Self, SaveGameError> {
+        let bytes = std::fs::read(path)?;
+        Self::deserialize_ron(&bytes, registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SaveGame, SnapshotFilter};
+    use crate::SceneSpawnError;
+    use bevy_ecs::{Resources, World};
+    use bevy_property::{DynamicProperties, Properties};
+    use bevy_transform::prelude::Parent;
+    use bevy_type_registry::TypeRegistry;
+
+    #[derive(Properties, Default)]
+    struct Health {
+        value: f32,
+    }
+
+    #[derive(Properties, Default)]
+    struct Score {
+        value: u32,
+    }
+
+    fn resources() -> Resources {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register::<Health>();
+        type_registry.component.write().register_mapped::<Parent>();
+        type_registry.resource.write().register::<Score>();
+        {
+            let mut property_registry = type_registry.property.write();
+            property_registry.register::<Health>();
+            property_registry.register::<Parent>();
+            property_registry.register::<bevy_ecs::Entity>();
+            property_registry.register::<Score>();
+        }
+        let mut resources = Resources::default();
+        resources.insert(type_registry);
+        resources.insert(Score::default());
+        resources
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let resources = resources();
+        resources.get_mut::<Score>().unwrap().value = 42;
+        let mut world = World::default();
+        let parent = world.spawn((Health { value: 1.0 },));
+        world.spawn((Health { value: 2.0 }, Parent(parent)));
+        // entities without Health aren't saved
+        world.spawn((Parent(parent),));
+
+        let filter = SnapshotFilter::default().with::<Health>();
+        assert_eq!(filter.entities(&world).len(), 2);
+        let save_game = SaveGame::snapshot(&world, &resources, &filter);
+        let ron = {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            let registry = type_registry.property.read();
+            save_game.serialize_ron(&registry).unwrap()
+        };
+
+        let resources = self::resources();
+        let save_game = {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            let registry = type_registry.property.read();
+            SaveGame::deserialize_ron(ron.as_bytes(), &registry).unwrap()
+        };
+        let mut world = World::default();
+        // the restored entities don't have the same ids as the saved entities
+        world.spawn(());
+        let entity_map = save_game.restore(&mut world, &resources).unwrap();
+
+        assert_eq!(resources.get::<Score>().unwrap().value, 42);
+        assert_eq!(entity_map.keys().count(), 2);
+        let saved_parent = bevy_ecs::Entity::new(parent.id());
+        let restored_parent = entity_map.get(saved_parent).unwrap();
+        assert_ne!(restored_parent, parent);
+        assert_eq!(world.get::<Health>(restored_parent).unwrap().value, 1.0);
+        let (child_health, child_parent) = world
+            .query::<(&Health, &Parent)>()
+            .iter()
+            .map(|(health, parent)| (health.value, parent.0))
+            .next()
+            .unwrap();
+        assert_eq!(child_health, 2.0);
+        assert_eq!(child_parent, restored_parent);
+    }
+
+    #[test]
+    fn failed_restore_despawns_entities() {
+        let resources = resources();
+        let mut world = World::default();
+        let parent = world.spawn(());
+        world.spawn((Health { value: 1.0 },));
+        world.spawn((Health { value: 2.0 }, Parent(parent)));
+
+        // the saved child refers to a parent that wasn't saved
+        let save_game = SaveGame::snapshot(
+            &world,
+            &resources,
+            &SnapshotFilter::default().with::<Health>(),
+        );
+        resources.get_mut::<Score>().unwrap().value = 7;
+        let mut world = World::default();
+        assert!(save_game.restore(&mut world, &resources).is_err());
+        assert_eq!(world.query::<()>().iter().count(), 0);
+        assert_eq!(resources.get::<Score>().unwrap().value, 7);
+    }
+
+    #[test]
+    fn failed_restore_keeps_resources() {
+        let resources = resources();
+        resources.get_mut::<Score>().unwrap().value = 42;
+        let mut world = World::default();
+        world.spawn((Health { value: 1.0 },));
+        let mut save_game = SaveGame::snapshot(&world, &resources, &SnapshotFilter::default());
+        // the second resource isn't registered
+        let mut level = DynamicProperties::map();
+        level.type_name = "Level".to_string();
+        level.set("value", 3u32);
+        save_game.scene.resources.push(level);
+
+        resources.get_mut::<Score>().unwrap().value = 7;
+        let mut world = World::default();
+        assert!(matches!(
+            save_game.restore(&mut world, &resources),
+            Err(SceneSpawnError::UnregisteredResource { type_name }) if type_name == "Level"
+        ));
+        assert_eq!(world.query::<()>().iter().count(), 0);
+        assert_eq!(resources.get::<Score>().unwrap().value, 7);
+    }
+}
@@ -1,13 +1,17 @@
-use crate::serde::SceneSerializer;
+use crate::{serde::SceneSerializer, SceneSpawnError, SnapshotFilter};
 use anyhow::Result;
-use bevy_ecs::World;
+use bevy_ecs::{EntityMap, Resources, World};
 use bevy_property::{DynamicProperties, PropertyTypeRegistry};
-use bevy_type_registry::ComponentRegistry;
+use bevy_type_registry::{ComponentRegistry, ResourceRegistry, TypeRegistry};
 use serde::Serialize;
 
+/// The version of the scene format written by [Scene::serialize_ron]. Scenes without a version are read as version 0.
+pub const SCENE_FORMAT_VERSION: u32 = 1;
+
 #[derive(Default)]
 pub struct Scene {
     pub entities: Vec<Entity>,
+    pub resources: Vec<DynamicProperties>,
 }
 
 pub struct Entity {
@@ -17,33 +21,129 @@ pub struct Entity {
 
 impl Scene {
     pub fn from_world(world: &World, component_registry: &ComponentRegistry) -> Self {
+        Self::from_world_filtered(world, component_registry, &SnapshotFilter::default())
+    }
+
+    /// Creates a scene from the entities and components of `world` that pass `filter`
+    pub fn from_world_filtered(
+        world: &World,
+        component_registry: &ComponentRegistry,
+        filter: &SnapshotFilter,
+    ) -> Self {
         let mut scene = Scene::default();
         for archetype in world.archetypes() {
-            let mut entities = Vec::new();
             for (index, entity) in archetype.iter_entities().enumerate() {
-                if index == entities.len() {
-                    entities.push(Entity {
-                        entity: entity.id(),
-                        components: Vec::new(),
-                    })
+                if !filter.includes_entity(archetype, index) {
+                    continue;
                 }
-                for type_info in archetype.types() {
+
+                let mut components = Vec::new();
+                for type_info in archetype.types().iter().chain(archetype.sparse_set_types()) {
+                    if !archetype.has_component(type_info.id(), index)
+                        || !filter.includes_component(type_info.id())
+                    {
+                        continue;
+                    }
                     if let Some(component_registration) = component_registry.get(&type_info.id()) {
                         let properties =
                             component_registration.get_component_properties(&archetype, index);
 
-                        entities[index].components.push(properties.to_dynamic());
+                        components.push(properties.to_dynamic());
                     }
                 }
-            }
 
-            scene.entities.extend(entities.drain(..));
+                scene.entities.push(Entity {
+                    entity: entity.id(),
+                    components,
+                });
+            }
         }
 
         scene
     }
 
-    // TODO: move to AssetSaver when it is implemented
+    /// Adds the registered resources that pass `filter` to the scene
+    pub fn add_resources(
+        &mut self,
+        resources: &Resources,
+        resource_registry: &ResourceRegistry,
+        filter: &SnapshotFilter,
+    ) {
+        for resource_registration in resource_registry.iter() {
+            if !filter.includes_resource(resource_registration.ty) {
+                continue;
+            }
+            if let Some(properties) = resource_registration.get_resource_properties(resources) {
+                self.resources.push(properties);
+            }
+        }
+    }
+
+    /// Writes the entities of the scene to `world`, reusing the entities `entity_map` already maps scene entities
+    /// to and adding the new ones to it. The resources of the scene are applied to the existing resources. Resources
+    /// are checked before anything is written, so if one of them can't be applied, none of them are.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let resource_registry = type_registry.resource.read();
+        let mut resource_registrations = Vec::with_capacity(self.resources.len());
+        for resource in self.resources.iter() {
+            let resource_registration = resource_registry
+                .get_with_name(&resource.type_name)
+                .ok_or_else(|| SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name.to_string(),
+                })?;
+            if !resource_registration.contains_resource(resources) {
+                return Err(SceneSpawnError::NonExistentResource {
+                    type_name: resource.type_name.to_string(),
+                });
+            }
+            resource_registrations.push((resource_registration, resource));
+        }
+
+        let component_registry = type_registry.component.read();
+        let mut written_components = Vec::new();
+        for scene_entity in self.entities.iter() {
+            let entity = entity_map
+                .get_or_insert_with(bevy_ecs::Entity::new(scene_entity.entity), || {
+                    world.reserve_entity()
+                });
+            for component in scene_entity.components.iter() {
+                let component_registration = component_registry
+                    .get_with_name(&component.type_name)
+                    .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                        type_name: component.type_name.to_string(),
+                    })?;
+                if world.has_component_type(entity, component_registration.ty) {
+                    if component.type_name == "Camera" {
+                        continue;
+                    }
+                    component_registration.apply_component_to_entity(world, entity, component);
+                } else {
+                    component_registration
+                        .add_component_to_entity(world, resources, entity, component);
+                }
+                written_components.push((entity, component_registration));
+            }
+        }
+
+        // only the components copied from the scene refer to the scene's entities. components added to its entities
+        // since, like the Parent of an instance spawned as a child, already refer to world entities.
+        for (entity, component_registration) in written_components {
+            component_registration.map_component_entities(world, entity, entity_map)?;
+        }
+
+        for (resource_registration, resource) in resource_registrations {
+            resource_registration.apply_to_resource(resources, resource);
+        }
+
+        Ok(())
+    }
+
     pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
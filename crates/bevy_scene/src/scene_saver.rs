@@ -0,0 +1,34 @@
+use crate::Scene;
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use bevy_ecs::{FromResources, Resources};
+use bevy_property::PropertyTypeRegistry;
+use bevy_type_registry::TypeRegistry;
+use parking_lot::RwLock;
+use std::{path::Path, sync::Arc};
+
+/// Saves [Scene]s as `.scn` files in the RON format the [SceneLoader](crate::SceneLoader) reads
+pub struct SceneSaver {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+}
+
+impl FromResources for SceneSaver {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        SceneSaver {
+            property_type_registry: type_registry.property.clone(),
+        }
+    }
+}
+
+impl AssetSaver<Scene> for SceneSaver {
+    fn to_bytes(&self, _asset_path: &Path, scene: &Scene) -> Result<Vec<u8>> {
+        let registry = self.property_type_registry.read();
+        Ok(scene.serialize_ron(&registry)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["scn"];
+        EXTENSIONS
+    }
+}
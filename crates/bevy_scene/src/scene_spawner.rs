@@ -1,32 +1,53 @@
 use crate::Scene;
 use bevy_app::prelude::*;
 use bevy_asset::{AssetEvent, Assets, Handle};
-use bevy_ecs::{Resources, World};
-use bevy_type_registry::TypeRegistry;
+use bevy_ecs::{Entity, EntityMap, MapEntitiesError, Resources, World};
+use bevy_transform::prelude::{Children, Parent};
 use bevy_utils::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
 struct InstanceInfo {
-    entity_map: HashMap<u32, bevy_ecs::Entity>,
+    /// Maps the entities of the scene to the entities they were spawned as
+    entity_map: EntityMap,
+    scene_handle: Handle<Scene>,
+    /// The entity the root entities of the instance are children of
+    parent: Option<Entity>,
 }
 
+/// Identifies an instance of a [Scene] spawned by the [SceneSpawner]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct InstanceId(Uuid);
+pub struct InstanceId(Uuid);
 
 impl InstanceId {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         InstanceId(Uuid::new_v4())
     }
 }
 
+/// Sent once the entities of a scene instance have been spawned
+#[derive(Debug, Clone)]
+pub struct SceneInstanceReady {
+    pub instance_id: InstanceId,
+    /// The entity the instance was spawned as a child of
+    pub parent: Option<Entity>,
+}
+
+struct QueuedInstance {
+    instance_id: InstanceId,
+    scene_handle: Handle<Scene>,
+    parent: Option<Entity>,
+}
+
 #[derive(Default)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: EventReader<AssetEvent<Scene>>,
-    scenes_to_instance: Vec<Handle<Scene>>,
+    scenes_to_instance: Vec<QueuedInstance>,
     scenes_to_despawn: Vec<Handle<Scene>>,
+    instances_to_despawn: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
@@ -35,111 +56,223 @@ pub enum SceneSpawnError {
     UnregisteredComponent { type_name: String },
     #[error("Scene does not exist. Perhaps it is still loading?")]
     NonExistentScene { handle: Handle<Scene> },
+    #[error("Scene contains an unregistered resource.")]
+    UnregisteredResource { type_name: String },
+    #[error("Scene contains a resource that doesn't exist. Registering it with register_resource initializes it.")]
+    NonExistentResource { type_name: String },
+    #[error("Scene contains a reference to an entity that isn't part of the scene.")]
+    MapEntities(#[from] MapEntitiesError),
+    #[error("The entity the scene was spawned as a child of doesn't exist.")]
+    NonExistentParent { parent: Entity },
 }
 
 impl SceneSpawner {
-    pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
-        self.scenes_to_instance.push(scene_handle);
+    /// Spawns an instance of the scene once it is loaded and returns the id of the instance
+    pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
+        self.queue_instance(scene_handle, None)
     }
 
+    /// Spawns an instance of the scene once it is loaded, with the root entities of the scene as children of
+    /// `parent`. The instance can then be positioned with the parent's transform.
+    pub fn spawn_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) -> InstanceId {
+        self.queue_instance(scene_handle, Some(parent))
+    }
+
+    fn queue_instance(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        parent: Option<Entity>,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.scenes_to_instance.push(QueuedInstance {
+            instance_id,
+            scene_handle,
+            parent,
+        });
+        instance_id
+    }
+
+    /// Despawns all instances of the scene
     pub fn despawn(&mut self, scene_handle: Handle<Scene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    /// Despawns a single instance of a scene
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_despawn.push(instance_id);
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<Scene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(*entity); // Ignore the result, despawn only cares if it exists.
-                    }
+                if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+                    Self::despawn_instance_entities(world, &instance);
                 }
             }
-
-            self.spawned_scenes.remove(&scene_handle);
         }
+        self.scenes_to_instance
+            .retain(|queued| queued.scene_handle != scene_handle);
         Ok(())
     }
 
+    pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: InstanceId) {
+        if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+            Self::despawn_instance_entities(world, &instance);
+            if let Some(instance_ids) = self.spawned_scenes.get_mut(&instance.scene_handle) {
+                instance_ids.retain(|id| *id != instance_id);
+            }
+        }
+        self.scenes_to_instance
+            .retain(|queued| queued.instance_id != instance_id);
+    }
+
+    fn despawn_instance_entities(world: &mut World, instance: &InstanceInfo) {
+        let entities = instance.entity_map.values().collect::<Vec<_>>();
+        for entity in entities.iter() {
+            let _ = world.despawn(*entity); // Ignore the result, despawn only cares if it exists.
+        }
+        if let Some(parent) = instance.parent {
+            if let Ok(mut children) = world.get_mut::<Children>(parent) {
+                children.retain(|child| !entities.contains(child));
+            }
+        }
+    }
+
+    /// Returns true once the entities of the instance have been spawned
+    pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
+        self.spawned_instances.contains_key(&instance_id)
+    }
+
+    /// Iterates over the entities of a spawned instance
+    pub fn iter_instance_entities(
+        &self,
+        instance_id: InstanceId,
+    ) -> Option<impl Iterator<Item = Entity> + '_> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| instance.entity_map.values())
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: Handle<Scene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
+        self.spawn_instance_sync(world, resources, instance_id, scene_handle, None)?;
+        Ok(instance_id)
+    }
+
+    pub fn spawn_as_child_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: Handle<Scene>,
+        parent: Entity,
+    ) -> Result<InstanceId, SceneSpawnError> {
+        let instance_id = InstanceId::new();
+        self.spawn_instance_sync(world, resources, instance_id, scene_handle, Some(parent))?;
+        Ok(instance_id)
+    }
+
+    /// Spawns the instance and sends a [SceneInstanceReady] event. The event is skipped if there is no
+    /// `Events<SceneInstanceReady>` resource, which the [ScenePlugin](crate::ScenePlugin) adds.
+    fn spawn_instance_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        instance_id: InstanceId,
+        scene_handle: Handle<Scene>,
+        parent: Option<Entity>,
+    ) -> Result<(), SceneSpawnError> {
         let mut instance_info = InstanceInfo {
-            entity_map: HashMap::default(),
+            entity_map: EntityMap::default(),
+            scene_handle: scene_handle.clone_weak(),
+            parent,
         };
-        Self::spawn_internal(world, resources, scene_handle, &mut instance_info)?;
+        if let Err(err) = Self::spawn_internal(world, resources, &scene_handle, &mut instance_info)
+        {
+            // don't leave a partially written instance behind
+            Self::despawn_instance_entities(world, &instance_info);
+            return Err(err);
+        }
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_scenes
             .entry(scene_handle)
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
+        if let Some(mut events) = resources.get_mut::<Events<SceneInstanceReady>>() {
+            events.send(SceneInstanceReady {
+                instance_id,
+                parent,
+            });
+        }
         Ok(())
     }
 
     fn spawn_internal(
         world: &mut World,
         resources: &Resources,
-        scene_handle: Handle<Scene>,
+        scene_handle: &Handle<Scene>,
         instance_info: &mut InstanceInfo,
     ) -> Result<(), SceneSpawnError> {
-        let type_registry = resources.get::<TypeRegistry>().unwrap();
-        let component_registry = type_registry.component.read();
         let scenes = resources.get::<Assets<Scene>>().unwrap();
         let scene = scenes
-            .get(&scene_handle)
+            .get(scene_handle)
             .ok_or_else(|| SceneSpawnError::NonExistentScene {
-                handle: scene_handle,
+                handle: scene_handle.clone_weak(),
             })?;
+        if let Some(parent) = instance_info.parent {
+            if !world.contains(parent) {
+                return Err(SceneSpawnError::NonExistentParent { parent });
+            }
+        }
+        scene.write_to_world(world, resources, &mut instance_info.entity_map)?;
 
-        for scene_entity in scene.entities.iter() {
-            let entity = *instance_info
-                .entity_map
-                .entry(scene_entity.entity)
-                .or_insert_with(|| world.reserve_entity());
-            for component in scene_entity.components.iter() {
-                let component_registration = component_registry
-                    .get_with_name(&component.type_name)
-                    .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
-                        type_name: component.type_name.to_string(),
-                    })?;
-                if world.has_component_type(entity, component_registration.ty) {
-                    if component.type_name != "Camera" {
-                        component_registration.apply_component_to_entity(world, entity, component);
-                    }
-                } else {
-                    component_registration
-                        .add_component_to_entity(world, resources, entity, component);
+        if let Some(parent) = instance_info.parent {
+            for entity in instance_info.entity_map.values() {
+                if world.get::<Parent>(entity).is_err() {
+                    world.insert_one(entity, Parent(parent)).unwrap();
                 }
             }
         }
         Ok(())
     }
 
+    /// Writes the modified scenes to their instances again. Instances that fail to update are despawned, and the
+    /// first error is returned once every instance was updated.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handles: &[Handle<Scene>],
     ) -> Result<(), SceneSpawnError> {
+        let mut result = Ok(());
+        let mut failed_instances = Vec::new();
         for scene_handle in scene_handles {
             if let Some(spawned_instances) = self.spawned_scenes.get(scene_handle) {
                 for instance_id in spawned_instances.iter() {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_internal(world, resources, *scene_handle, instance_info)?;
+                        if let Err(err) =
+                            Self::spawn_internal(world, resources, scene_handle, instance_info)
+                        {
+                            failed_instances.push(*instance_id);
+                            result = result.and(Err(err));
+                        }
                     }
                 }
             }
         }
-        Ok(())
+        for instance_id in failed_instances {
+            self.despawn_instance_sync(world, instance_id);
+        }
+        result
     }
 
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
@@ -148,9 +281,16 @@ impl SceneSpawner {
         for scene_handle in scenes_to_despawn {
             self.despawn_sync(world, scene_handle)?;
         }
+
+        let instances_to_despawn = std::mem::take(&mut self.instances_to_despawn);
+        for instance_id in instances_to_despawn {
+            self.despawn_instance_sync(world, instance_id);
+        }
         Ok(())
     }
 
+    /// Spawns the queued instances whose scenes are loaded. Instances that fail to spawn are dropped, and the first
+    /// error is returned once every queued instance was handled.
     pub fn spawn_queued_scenes(
         &mut self,
         world: &mut World,
@@ -158,17 +298,24 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.scenes_to_instance);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_sync(world, resources, scene_handle) {
+        let mut result = Ok(());
+        for queued in scenes_to_spawn {
+            match self.spawn_instance_sync(
+                world,
+                resources,
+                queued.instance_id,
+                queued.scene_handle.clone(),
+                queued.parent,
+            ) {
                 Ok(_) => {}
                 Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.scenes_to_instance.push(scene_handle)
+                    self.scenes_to_instance.push(queued)
                 }
-                Err(err) => return Err(err),
+                Err(err) => result = result.and(Err(err)),
             }
         }
 
-        Ok(())
+        result
     }
 }
 
@@ -183,14 +330,309 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     {
         if let AssetEvent::Modified { handle } = event {
             if scene_spawner.spawned_scenes.contains_key(handle) {
-                updated_spawned_scenes.push(*handle);
+                updated_spawned_scenes.push(handle.clone_weak());
             }
         }
     }
 
-    scene_spawner.despawn_queued_scenes(world).unwrap();
-    scene_spawner.spawn_queued_scenes(world, resources).unwrap();
-    scene_spawner
-        .update_spawned_scenes(world, resources, &updated_spawned_scenes)
-        .unwrap();
+    if let Err(err) = scene_spawner.despawn_queued_scenes(world) {
+        log::error!("Failed to despawn scene: {}", err);
+    }
+    if let Err(err) = scene_spawner.spawn_queued_scenes(world, resources) {
+        log::error!("Failed to spawn scene: {}", err);
+    }
+    if let Err(err) = scene_spawner.update_spawned_scenes(world, resources, &updated_spawned_scenes)
+    {
+        log::error!("Failed to update spawned scene: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SceneInstanceReady, SceneSpawnError, SceneSpawner};
+    use crate::Scene;
+    use bevy_app::Events;
+    use bevy_asset::Assets;
+    use bevy_ecs::{Resources, World};
+    use bevy_transform::prelude::{Children, Parent};
+    use bevy_type_registry::TypeRegistry;
+
+    #[test]
+    fn spawned_scenes_map_entities() {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register_mapped::<Parent>();
+        type_registry
+            .component
+            .write()
+            .register_mapped::<Children>();
+
+        let mut scene_world = World::default();
+        let parent = scene_world.spawn(());
+        let child = scene_world.spawn((Parent(parent),));
+        scene_world
+            .insert_one(parent, Children::with(&[child]))
+            .unwrap();
+        let scene = Scene::from_world(&scene_world, &type_registry.component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(type_registry);
+
+        let mut world = World::default();
+        // the spawned entities don't have the same ids as the scene's entities
+        world.spawn(());
+        world.spawn(());
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_sync(&mut world, &resources, scene_handle)
+            .unwrap();
+
+        let (spawned_child, spawned_parent) = world
+            .query::<(bevy_ecs::Entity, &Parent)>()
+            .iter()
+            .map(|(entity, parent)| (entity, parent.0))
+            .next()
+            .unwrap();
+        assert_ne!(spawned_parent, parent);
+        let children = world.get::<Children>(spawned_parent).unwrap();
+        assert_eq!(&children[..], &[spawned_child]);
+    }
+
+    #[test]
+    fn recycled_scene_entities_map_entities() {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register_mapped::<Parent>();
+
+        let mut scene_world = World::default();
+        let despawned = scene_world.spawn(());
+        scene_world.despawn(despawned).unwrap();
+        let parent = scene_world.spawn(());
+        assert_eq!(parent.id(), despawned.id());
+        assert_ne!(parent, despawned);
+        scene_world.spawn((Parent(parent),));
+        let scene = Scene::from_world(&scene_world, &type_registry.component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(type_registry);
+
+        let mut world = World::default();
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner
+            .spawn_sync(&mut world, &resources, scene_handle)
+            .unwrap();
+
+        let spawned_parent = world.query::<&Parent>().iter().next().unwrap().0;
+        assert!(scene_spawner
+            .iter_instance_entities(instance_id)
+            .unwrap()
+            .any(|entity| entity == spawned_parent));
+    }
+
+    #[test]
+    fn failed_instances_are_despawned() {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register_mapped::<Parent>();
+
+        // the parent isn't part of the scene
+        let mut scene_world = World::default();
+        let outside = scene_world.spawn((1u32,));
+        scene_world.spawn((Parent(outside),));
+        scene_world.despawn(outside).unwrap();
+        let scene = Scene::from_world(&scene_world, &type_registry.component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(type_registry);
+
+        let mut world = World::default();
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner.spawn(scene_handle);
+        assert!(scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .is_err());
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        assert_eq!(world.iter().count(), 0);
+    }
+
+    #[test]
+    fn spawn_as_child_of_despawned_parent() {
+        let mut scene_world = World::default();
+        scene_world.spawn((1u32,));
+        let scene = Scene::from_world(&scene_world, &TypeRegistry::default().component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(TypeRegistry::default());
+
+        let mut world = World::default();
+        let anchor = world.spawn(());
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner.spawn_as_child(scene_handle, anchor);
+        world.despawn(anchor).unwrap();
+        assert!(matches!(
+            scene_spawner.spawn_queued_scenes(&mut world, &resources),
+            Err(SceneSpawnError::NonExistentParent { parent }) if parent == anchor
+        ));
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        assert_eq!(world.iter().count(), 0);
+    }
+
+    #[test]
+    fn spawn_as_child_and_despawn_instance() {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register_mapped::<Parent>();
+
+        let mut scene_world = World::default();
+        let root = scene_world.spawn(());
+        scene_world.spawn((Parent(root),));
+        let scene = Scene::from_world(&scene_world, &type_registry.component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(type_registry);
+        resources.insert(Events::<SceneInstanceReady>::default());
+
+        let mut world = World::default();
+        let anchor = world.spawn(());
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner.spawn_as_child(scene_handle.clone(), anchor);
+        let other_instance_id = scene_spawner.spawn(scene_handle);
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .unwrap();
+        assert!(scene_spawner.instance_is_ready(instance_id));
+
+        {
+            let events = resources.get::<Events<SceneInstanceReady>>().unwrap();
+            let ready = events.get_reader().iter(&events).collect::<Vec<_>>();
+            assert_eq!(ready.len(), 2);
+            assert_eq!(ready[0].instance_id, instance_id);
+            assert_eq!(ready[0].parent, Some(anchor));
+        }
+
+        // only the root entity of the instance is parented to the anchor
+        let instance_entities = scene_spawner
+            .iter_instance_entities(instance_id)
+            .unwrap()
+            .collect::<Vec<_>>();
+        let anchor_children = instance_entities
+            .iter()
+            .cloned()
+            .filter(|entity| world.get::<Parent>(*entity).unwrap().0 == anchor)
+            .collect::<Vec<_>>();
+        assert_eq!(anchor_children.len(), 1);
+        world
+            .insert_one(anchor, Children::with(&anchor_children))
+            .unwrap();
+
+        scene_spawner.despawn_instance(instance_id);
+        scene_spawner.despawn_queued_scenes(&mut world).unwrap();
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        assert!(scene_spawner.instance_is_ready(other_instance_id));
+        for entity in instance_entities {
+            assert!(!world.contains(entity));
+        }
+        assert!(world.get::<Children>(anchor).unwrap().is_empty());
+        assert_eq!(
+            scene_spawner
+                .iter_instance_entities(other_instance_id)
+                .unwrap()
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn reload_child_instance() {
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register_mapped::<Parent>();
+
+        let mut scene_world = World::default();
+        let root = scene_world.spawn(());
+        scene_world.spawn((Parent(root),));
+        let scene = Scene::from_world(&scene_world, &type_registry.component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(type_registry);
+
+        let mut world = World::default();
+        // the anchor has the same id as the root entity of the scene
+        let anchor = world.spawn(());
+        assert_eq!(anchor.id(), root.id());
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner.spawn_as_child(scene_handle.clone(), anchor);
+        scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .unwrap();
+        scene_spawner
+            .update_spawned_scenes(&mut world, &resources, &[scene_handle])
+            .unwrap();
+
+        assert!(scene_spawner.instance_is_ready(instance_id));
+        let mut parents = world
+            .query::<&Parent>()
+            .iter()
+            .map(|parent| parent.0)
+            .collect::<Vec<_>>();
+        parents.sort_by_key(|parent| parent.id());
+        assert_eq!(parents.len(), 2);
+        assert_eq!(parents[0], anchor);
+        assert!(scene_spawner
+            .iter_instance_entities(instance_id)
+            .unwrap()
+            .any(|entity| entity == parents[1]));
+    }
+
+    #[test]
+    fn reload_child_instance_of_despawned_parent() {
+        let mut scene_world = World::default();
+        scene_world.spawn((1u32,));
+        let scene = Scene::from_world(&scene_world, &TypeRegistry::default().component.read());
+
+        let mut resources = Resources::default();
+        let mut scenes = Assets::<Scene>::default();
+        let scene_handle = scenes.add(scene);
+        resources.insert(scenes);
+        resources.insert(TypeRegistry::default());
+
+        let mut world = World::default();
+        let anchor = world.spawn(());
+        let other_anchor = world.spawn(());
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner.spawn_as_child(scene_handle.clone(), anchor);
+        let other_instance_id = scene_spawner.spawn_as_child(scene_handle.clone(), other_anchor);
+        scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .unwrap();
+
+        // reloading keeps the instances whose parent still exists
+        world.despawn(anchor).unwrap();
+        assert!(matches!(
+            scene_spawner.update_spawned_scenes(&mut world, &resources, &[scene_handle]),
+            Err(SceneSpawnError::NonExistentParent { parent }) if parent == anchor
+        ));
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        assert!(scene_spawner.instance_is_ready(other_instance_id));
+        let parents = world
+            .query::<&Parent>()
+            .iter()
+            .map(|parent| parent.0)
+            .collect::<Vec<_>>();
+        assert_eq!(parents, vec![other_anchor]);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Entity, Scene};
+use crate::{Entity, SaveGame, Scene, SCENE_FORMAT_VERSION};
 use anyhow::Result;
 use bevy_property::{
     property_serde::{DynamicPropertiesDeserializer, DynamicPropertiesSerializer},
@@ -26,8 +26,38 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 3)?;
+        state.serialize_field(SCENE_FIELD_VERSION, &SCENE_FORMAT_VERSION)?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -92,12 +122,114 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        let mut scene = Scene::default();
-        scene.entities = deserializer.deserialize_seq(SceneEntitySeqVisiter {
+        // scenes without a format version used to be a plain list of entities, so both forms are accepted
+        deserializer.deserialize_any(SceneVisiter {
             property_type_registry: self.property_type_registry,
-        })?;
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Version,
+    Entities,
+    Resources,
+}
 
-        Ok(scene)
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_VERSION: &str = "version";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+
+struct SceneVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisiter<'a> {
+    type Value = Scene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities = SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        }
+        .visit_seq(seq)?;
+        Ok(Scene {
+            entities,
+            resources: Vec::new(),
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut entities = None;
+        let mut resources = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_VERSION));
+                    }
+                    let scene_version = map.next_value::<u32>()?;
+                    if scene_version > SCENE_FORMAT_VERSION {
+                        return Err(Error::custom(format!(
+                            "scene format version {} is newer than the supported version {}",
+                            scene_version, SCENE_FORMAT_VERSION
+                        )));
+                    }
+                    version = Some(scene_version);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        Ok(Scene {
+            entities,
+            resources: resources.unwrap_or_default(),
+        })
+    }
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        })
     }
 }
 
@@ -252,3 +384,232 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisiter<'a> {
         Ok(dynamic_properties)
     }
 }
+
+pub struct SaveGameSerializer<'a> {
+    pub save_game: &'a SaveGame,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 2)?;
+        state.serialize_field(SAVE_GAME_FIELD_VERSION, &self.save_game.version)?;
+        state.serialize_field(
+            SAVE_GAME_FIELD_SCENE,
+            &SceneSerializer::new(&self.save_game.scene, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+pub struct SaveGameDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = SaveGame;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_FIELD_VERSION, SAVE_GAME_FIELD_SCENE],
+            SaveGameVisiter {
+                property_type_registry: self.property_type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Scene,
+}
+
+pub const SAVE_GAME_STRUCT: &str = "SaveGame";
+pub const SAVE_GAME_FIELD_VERSION: &str = "version";
+pub const SAVE_GAME_FIELD_SCENE: &str = "scene";
+
+struct SaveGameVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameVisiter<'a> {
+    type Value = SaveGame;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("save game")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_VERSION));
+                    }
+                    version = Some(map.next_value::<u32>()?);
+                }
+                SaveGameField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value_seed(SceneDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_VERSION))?;
+        let scene = scene.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_SCENE))?;
+        Ok(SaveGame { version, scene })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SceneDeserializer;
+    use crate::Scene;
+    use bevy_ecs::Entity;
+    use bevy_property::{
+        DynamicProperties, Properties, PropertiesVal, Property, PropertyTypeRegistry,
+    };
+    use serde::de::DeserializeSeed;
+
+    fn deserialize_scene(ron: &str, registry: &PropertyTypeRegistry) -> Result<Scene, ron::Error> {
+        let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+        SceneDeserializer {
+            property_type_registry: registry,
+        }
+        .deserialize(&mut deserializer)
+    }
+
+    #[test]
+    fn deserialize_entity_list_scene() {
+        let registry = PropertyTypeRegistry::default();
+        let scene = deserialize_scene("[(entity: 0, components: [])]", &registry).unwrap();
+        assert_eq!(scene.entities.len(), 1);
+        assert!(scene.resources.is_empty());
+
+        let scene = deserialize_scene(
+            "(entities: [(entity: 0, components: [])], resources: [])",
+            &registry,
+        )
+        .unwrap();
+        assert_eq!(scene.entities.len(), 1);
+    }
+
+    #[derive(Properties, Default)]
+    struct Player {
+        name: String,
+        health: f32,
+    }
+
+    #[test]
+    fn migrate_old_components() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Player>();
+        registry.register_rename::<Player>("Character");
+        // version 1 renamed the hp field and removed the level field
+        registry.register_migration::<Player>(|properties: &mut DynamicProperties| {
+            properties.rename("hp", "health");
+            properties.remove("level");
+        });
+        // version 2 counts health in tenths
+        registry.register_migration::<Player>(|properties: &mut DynamicProperties| {
+            if let Some(health) = properties.prop_val::<f32>("health").cloned() {
+                properties.set("health", health * 10.0);
+            }
+        });
+
+        let old_scene = r#"[
+            (
+                entity: 0,
+                components: [
+                    {
+                        "type": "Character",
+                        "map": {
+                            "level": 3,
+                            "hp": 10.0,
+                            "name": "Alice",
+                        },
+                    },
+                ],
+            ),
+        ]"#;
+        let scene = deserialize_scene(old_scene, &registry).unwrap();
+        let player = &scene.entities[0].components[0];
+        assert_eq!(player.type_name, std::any::type_name::<Player>());
+        assert_eq!(player.prop_val::<f32>("health"), Some(&100.0));
+        assert_eq!(player.prop_val::<String>("name").unwrap(), "Alice");
+        assert!(player.prop("level").is_none());
+        assert!(player.prop("hp").is_none());
+
+        // migrations only apply to older versions
+        let scene = deserialize_scene(&scene.serialize_ron(&registry).unwrap(), &registry).unwrap();
+        let player = &scene.entities[0].components[0];
+        assert_eq!(player.prop_val::<f32>("health"), Some(&100.0));
+
+        // the version can come after the properties
+        let current_scene = r#"[
+            (entity: 0, components: [{"type": "Player", "map": {"health": 5.0}, "version": 2}]),
+        ]"#;
+        let scene = deserialize_scene(current_scene, &registry).unwrap();
+        let player = &scene.entities[0].components[0];
+        assert_eq!(player.prop_val::<f32>("health"), Some(&5.0));
+
+        let newer_scene = r#"(
+            version: 1,
+            entities: [(entity: 0, components: [{"type": "Player", "version": 3, "map": {}}])],
+        )"#;
+        assert!(deserialize_scene(newer_scene, &registry).is_err());
+        assert!(deserialize_scene("(version: 2, entities: [])", &registry).is_err());
+    }
+
+    #[derive(Properties)]
+    struct Target {
+        entity: Entity,
+    }
+
+    #[test]
+    fn deserialize_old_entities() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Target>();
+        registry.register::<Entity>();
+        // older scenes wrote entities as an untyped newtype, hand written scenes may use a bare id
+        let scene = format!(
+            r#"[
+                (entity: 0, components: [{{"type": "Target", "map": {{"entity": (5)}}}}]),
+                (entity: 1, components: [{{"type": "Target", "map": {{"entity": 6}}}}]),
+                (entity: 2, components: [{{"type": "Target", "map": {{"entity": {{"type": "{}", "value": 7}}}}}}]),
+            ]"#,
+            std::any::type_name::<Entity>()
+        );
+        let scene = deserialize_scene(&scene, &registry).unwrap();
+        let ids = scene
+            .entities
+            .iter()
+            .map(|entity| {
+                let mut target = Target {
+                    entity: Entity::new(0),
+                };
+                target.apply(&entity.components[0]);
+                target.entity.id()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![5, 6, 7]);
+    }
+}
@@ -111,9 +111,9 @@ impl TextureAtlas {
         self.textures.is_empty()
     }
 
-    pub fn get_texture_index(&self, texture: Handle<Texture>) -> Option<usize> {
+    pub fn get_texture_index(&self, texture: &Handle<Texture>) -> Option<usize> {
         self.texture_handles
             .as_ref()
-            .and_then(|texture_handles| texture_handles.get(&texture).cloned())
+            .and_then(|texture_handles| texture_handles.get(texture).cloned())
     }
 }
@@ -122,7 +122,7 @@ impl TextureAtlasBuilder {
                     packed_location.width() as f32,
                     packed_location.height() as f32,
                 );
-            texture_handles.insert(*texture_handle, texture_rects.len());
+            texture_handles.insert(texture_handle.clone_weak(), texture_rects.len());
             texture_rects.push(Rect { min, max });
             self.place_texture(&mut atlas_texture, texture, packed_location);
         }
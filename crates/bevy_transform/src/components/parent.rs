@@ -1,4 +1,4 @@
-use bevy_ecs::{Entity, FromResources};
+use bevy_ecs::{Entity, EntityMap, FromResources, MapEntities, MapEntitiesError};
 use bevy_property::Properties;
 use std::ops::{Deref, DerefMut};
 
@@ -15,6 +15,13 @@ impl FromResources for Parent {
     }
 }
 
+impl MapEntities for Parent {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.0 = entity_map.get(self.0)?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PreviousParent(pub Option<Entity>);
 
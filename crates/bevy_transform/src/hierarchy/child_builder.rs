@@ -1,5 +1,7 @@
 use crate::prelude::{Children, Parent, PreviousParent};
-use bevy_ecs::{Commands, CommandsInternal, Component, DynamicBundle, Entity, WorldWriter};
+use bevy_ecs::{
+    Commands, CommandsInternal, Component, DynamicBundle, Entity, EntityCommands, WorldWriter,
+};
 use smallvec::SmallVec;
 
 pub struct InsertChildren {
@@ -158,6 +160,41 @@ impl BuildChildren for Commands {
     }
 }
 
+impl<'a> BuildChildren for EntityCommands<'a> {
+    fn with_children(&mut self, spawn_children: impl FnOnce(&mut ChildBuilder)) -> &mut Self {
+        let parent = self.id();
+        {
+            let mut commands = self.commands().commands.lock();
+            let current_entity = commands.current_entity.take();
+            let push_children = {
+                let mut builder = ChildBuilder {
+                    commands: &mut commands,
+                    push_children: PushChildren {
+                        children: SmallVec::default(),
+                        parent,
+                    },
+                };
+                spawn_children(&mut builder);
+                builder.push_children
+            };
+
+            commands.current_entity = current_entity;
+            commands.write_world(push_children);
+        }
+        self
+    }
+
+    fn push_children(&mut self, parent: Entity, children: &[Entity]) -> &mut Self {
+        self.commands().push_children(parent, children);
+        self
+    }
+
+    fn insert_children(&mut self, parent: Entity, index: usize, children: &[Entity]) -> &mut Self {
+        self.commands().insert_children(parent, index, children);
+        self
+    }
+}
+
 impl<'a> BuildChildren for ChildBuilder<'a> {
     fn with_children(&mut self, spawn_children: impl FnOnce(&mut ChildBuilder)) -> &mut Self {
         let current_entity = self.commands.current_entity.expect("Cannot add children because the 'current entity' is not set. You should spawn an entity first.");
@@ -250,6 +287,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_children_of_entity() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+
+        let parent = commands.spawn((1,)).id();
+        let other = commands.spawn((2,)).id();
+        let mut child = None;
+        commands
+            .entity(parent)
+            .with_children(|parent| {
+                parent.spawn((3,)).for_current_entity(|e| child = Some(e));
+            })
+            .with(4u32);
+
+        commands.apply(&mut world, &mut resources);
+        let child = child.expect("child should exist");
+        let expected_children: SmallVec<[Entity; 8]> = smallvec![child];
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.clone(),
+            expected_children
+        );
+        assert_eq!(*world.get::<Parent>(child).unwrap(), Parent(parent));
+        assert_eq!(*world.get::<u32>(parent).unwrap(), 4);
+        assert!(world.get::<Children>(other).is_err());
+        assert_eq!(commands.current_entity(), Some(other));
+    }
+
     #[test]
     fn push_and_insert_children() {
         let mut world = World::default();
@@ -1,5 +1,5 @@
 use crate::components::*;
-use bevy_ecs::{Commands, Entity, IntoQuerySystem, Query, System, Without};
+use bevy_ecs::{BoxedSystem, Commands, Entity, IntoQuerySystem, Query, Without};
 use bevy_utils::HashMap;
 use smallvec::SmallVec;
 
@@ -98,7 +98,7 @@ pub fn parent_update_system(
     });
 }
 
-pub fn hierarchy_maintenance_systems() -> Vec<Box<dyn System>> {
+pub fn hierarchy_maintenance_systems() -> Vec<BoxedSystem> {
     vec![
         missing_previous_parent_system.system(),
         parent_update_system.system(),
@@ -9,9 +9,9 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_type_registry::RegisterType;
-use prelude::{Children, Parent, Transform};
+use prelude::{Children, GlobalTransform, Parent, Transform};
 
-pub(crate) fn transform_systems() -> Vec<Box<dyn System>> {
+pub(crate) fn transform_systems() -> Vec<BoxedSystem> {
     let mut systems = Vec::with_capacity(5);
 
     systems.append(&mut hierarchy::hierarchy_maintenance_systems());
@@ -25,9 +25,10 @@ pub struct TransformPlugin;
 
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.register_component::<Children>()
-            .register_component::<Parent>()
+        app.register_mapped_component::<Children>()
+            .register_mapped_component::<Parent>()
             .register_component::<Transform>()
+            .register_component::<GlobalTransform>()
             // add transform systems to startup so the first update is "correct"
             .add_startup_systems(transform_systems())
             .add_systems_to_stage(stage::POST_UPDATE, transform_systems());
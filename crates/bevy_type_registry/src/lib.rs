@@ -5,6 +5,7 @@ pub use register_type::*;
 pub use type_registry::*;
 
 use bevy_app::prelude::*;
+use bevy_ecs::Entity;
 use bevy_property::DynamicProperties;
 
 #[derive(Default)]
@@ -13,6 +14,7 @@ pub struct TypeRegistryPlugin;
 impl Plugin for TypeRegistryPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<TypeRegistry>()
-            .register_property::<DynamicProperties>();
+            .register_property::<DynamicProperties>()
+            .register_property::<Entity>();
     }
 }
@@ -1,18 +1,35 @@
 use crate::TypeRegistry;
 use bevy_app::AppBuilder;
-use bevy_ecs::{Component, FromResources};
-use bevy_property::{DeserializeProperty, Properties, Property};
+use bevy_ecs::{Component, FromResources, MapEntities, Resource};
+use bevy_property::{DeserializeProperty, Properties, PropertiesMigrationFn, Property};
 
 pub trait RegisterType {
     fn register_component<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + Component + FromResources;
+    fn register_mapped_component<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources + MapEntities;
+    /// Registers a resource so it can be saved to and loaded from scenes. The resource is initialized if it
+    /// doesn't exist yet.
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources;
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + FromResources;
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty;
+    /// Registers a migration from the current version of `T` to the next version, which is applied when older
+    /// versions of `T` are deserialized. Migrations have to be registered in order.
+    fn register_migration<T>(&mut self, migration: PropertiesMigrationFn) -> &mut Self
+    where
+        T: Property;
+    /// Registers a previous name of `T`, so `T`s saved with the old name can still be deserialized
+    fn register_rename<T>(&mut self, old_name: &str) -> &mut Self
+    where
+        T: Property;
 }
 
 impl RegisterType for AppBuilder {
@@ -28,6 +45,33 @@ impl RegisterType for AppBuilder {
         self
     }
 
+    fn register_mapped_component<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Component + FromResources + MapEntities,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.component.write().register_mapped::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        self
+    }
+
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource + FromResources,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.resource.write().register::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        if !self.app.resources.contains::<T>() {
+            self.init_resource::<T>();
+        }
+        self
+    }
+
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + Component + FromResources,
@@ -49,4 +93,32 @@ impl RegisterType for AppBuilder {
         }
         self
     }
+
+    fn register_migration<T>(&mut self, migration: PropertiesMigrationFn) -> &mut Self
+    where
+        T: Property,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry
+                .property
+                .write()
+                .register_migration::<T>(migration);
+        }
+        self
+    }
+
+    fn register_rename<T>(&mut self, old_name: &str) -> &mut Self
+    where
+        T: Property,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry
+                .property
+                .write()
+                .register_rename::<T>(old_name);
+        }
+        self
+    }
 }
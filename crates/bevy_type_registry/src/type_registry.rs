@@ -1,5 +1,10 @@
-use bevy_ecs::{Archetype, Component, Entity, FromResources, Resources, World};
-use bevy_property::{Properties, Property, PropertyTypeRegistration, PropertyTypeRegistry};
+use bevy_ecs::{
+    Archetype, Component, Entity, EntityMap, FromResources, MapEntities, MapEntitiesError,
+    Resource, Resources, World,
+};
+use bevy_property::{
+    DynamicProperties, Properties, Property, PropertyTypeRegistration, PropertyTypeRegistry,
+};
 use bevy_utils::{HashMap, HashSet};
 use parking_lot::RwLock;
 use std::{any::TypeId, sync::Arc};
@@ -8,6 +13,7 @@ use std::{any::TypeId, sync::Arc};
 pub struct TypeRegistry {
     pub property: Arc<RwLock<PropertyTypeRegistry>>,
     pub component: Arc<RwLock<ComponentRegistry>>,
+    pub resource: Arc<RwLock<ResourceRegistry>>,
 }
 
 #[derive(Default)]
@@ -23,7 +29,19 @@ impl ComponentRegistry {
     where
         T: Properties + Component + FromResources,
     {
-        let registration = ComponentRegistration::of::<T>();
+        self.add_registration(ComponentRegistration::of::<T>());
+    }
+
+    /// Registers a component that refers to other entities, so its references are mapped when it is copied to
+    /// another world
+    pub fn register_mapped<T>(&mut self)
+    where
+        T: Properties + Component + FromResources + MapEntities,
+    {
+        self.add_registration(ComponentRegistration::of_mapped::<T>());
+    }
+
+    fn add_registration(&mut self, registration: ComponentRegistration) {
         let short_name = registration.short_name.to_string();
         self.full_names
             .insert(registration.long_name.to_string(), registration.ty);
@@ -41,6 +59,10 @@ impl ComponentRegistry {
         self.registrations.get(type_id)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &ComponentRegistration> {
+        self.registrations.values()
+    }
+
     pub fn get_with_full_name(&self, full_name: &str) -> Option<&ComponentRegistration> {
         self.full_names
             .get(full_name)
@@ -65,12 +87,15 @@ impl ComponentRegistry {
     }
 }
 
+type MapEntitiesFn = fn(&mut World, Entity, &EntityMap) -> Result<(), MapEntitiesError>;
+
 #[derive(Clone)]
 pub struct ComponentRegistration {
     pub ty: TypeId,
     component_add_fn: fn(&mut World, resources: &Resources, Entity, &dyn Property),
     component_apply_fn: fn(&mut World, Entity, &dyn Property),
     component_properties_fn: fn(&Archetype, usize) -> &dyn Properties,
+    component_map_entities_fn: Option<MapEntitiesFn>,
     pub short_name: String,
     pub long_name: &'static str,
 }
@@ -95,15 +120,29 @@ impl ComponentRegistration {
             component_properties_fn: |archetype: &Archetype, index: usize| {
                 // the type has been looked up by the caller, so this is safe
                 unsafe {
-                    let ptr = archetype.get::<T>().unwrap().as_ptr().add(index);
+                    let ptr = archetype.get_component::<T>(index).unwrap().as_ptr();
                     ptr.as_ref().unwrap()
                 }
             },
+            component_map_entities_fn: None,
             short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
             long_name: std::any::type_name::<T>(),
         }
     }
 
+    pub fn of_mapped<T: Properties + Component + FromResources + MapEntities>() -> Self {
+        let mut registration = Self::of::<T>();
+        registration.component_map_entities_fn = Some(
+            |world: &mut World, entity: Entity, entity_map: &EntityMap| {
+                if let Ok(mut component) = world.get_mut::<T>(entity) {
+                    component.map_entities(entity_map)?;
+                }
+                Ok(())
+            },
+        );
+        registration
+    }
+
     pub fn add_component_to_entity(
         &self,
         world: &mut World,
@@ -130,4 +169,125 @@ impl ComponentRegistration {
     ) -> &'a dyn Properties {
         (self.component_properties_fn)(archetype, entity_index)
     }
+
+    /// Maps the entity references of this component on `entity`. Does nothing if the component wasn't registered
+    /// with [ComponentRegistry::register_mapped].
+    pub fn map_component_entities(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        entity_map: &EntityMap,
+    ) -> Result<(), MapEntitiesError> {
+        match self.component_map_entities_fn {
+            Some(map_entities) => map_entities(world, entity, entity_map),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ResourceRegistry {
+    pub registrations: HashMap<TypeId, ResourceRegistration>,
+    pub short_names: HashMap<String, TypeId>,
+    pub full_names: HashMap<String, TypeId>,
+    pub ambigous_names: HashSet<String>,
+}
+
+impl ResourceRegistry {
+    pub fn register<T>(&mut self)
+    where
+        T: Properties + Resource + FromResources,
+    {
+        let registration = ResourceRegistration::of::<T>();
+        let short_name = registration.short_name.to_string();
+        self.full_names
+            .insert(registration.long_name.to_string(), registration.ty);
+        if self.short_names.contains_key(&short_name) || self.ambigous_names.contains(&short_name) {
+            // name is ambiguous. fall back to long names for all ambiguous types
+            self.short_names.remove(&short_name);
+            self.ambigous_names.insert(short_name);
+        } else {
+            self.short_names.insert(short_name, registration.ty);
+        }
+        self.registrations.insert(registration.ty, registration);
+    }
+
+    pub fn get(&self, type_id: &TypeId) -> Option<&ResourceRegistration> {
+        self.registrations.get(type_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ResourceRegistration> {
+        self.registrations.values()
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<&ResourceRegistration> {
+        self.full_names
+            .get(full_name)
+            .and_then(|id| self.registrations.get(id))
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<&ResourceRegistration> {
+        self.short_names
+            .get(short_name)
+            .and_then(|id| self.registrations.get(id))
+    }
+
+    pub fn get_with_name(&self, type_name: &str) -> Option<&ResourceRegistration> {
+        let mut registration = self.get_with_short_name(type_name);
+        if registration.is_none() {
+            registration = self.get_with_full_name(type_name);
+            if registration.is_none() && self.ambigous_names.contains(type_name) {
+                panic!("Type name is ambiguous: {}", type_name);
+            }
+        }
+        registration
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceRegistration {
+    pub ty: TypeId,
+    resource_properties_fn: fn(&Resources) -> Option<DynamicProperties>,
+    resource_apply_fn: fn(&Resources, &dyn Property) -> bool,
+    resource_contains_fn: fn(&Resources) -> bool,
+    pub short_name: String,
+    pub long_name: &'static str,
+}
+
+impl ResourceRegistration {
+    pub fn of<T: Properties + Resource + FromResources>() -> Self {
+        Self {
+            ty: TypeId::of::<T>(),
+            resource_properties_fn: |resources: &Resources| {
+                resources.get::<T>().map(|resource| resource.to_dynamic())
+            },
+            resource_apply_fn: |resources: &Resources, property: &dyn Property| match resources
+                .get_mut::<T>()
+            {
+                Some(mut resource) => {
+                    resource.apply(property);
+                    true
+                }
+                None => false,
+            },
+            resource_contains_fn: |resources: &Resources| resources.contains::<T>(),
+            short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
+            long_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Returns the properties of the resource, or `None` if `resources` doesn't contain it
+    pub fn get_resource_properties(&self, resources: &Resources) -> Option<DynamicProperties> {
+        (self.resource_properties_fn)(resources)
+    }
+
+    /// Returns true if `resources` contains the resource
+    pub fn contains_resource(&self, resources: &Resources) -> bool {
+        (self.resource_contains_fn)(resources)
+    }
+
+    /// Applies `property` to the resource. Returns false if `resources` doesn't contain the resource.
+    pub fn apply_to_resource(&self, resources: &Resources, property: &dyn Property) -> bool {
+        (self.resource_apply_fn)(resources, property)
+    }
 }
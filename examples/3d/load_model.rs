@@ -11,32 +11,16 @@ fn main() {
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
 ) {
+    // load the first scene of a glTF file, which contains its node hierarchy and an entity for each mesh primitive
+    let scene_handle: Handle<Scene> = asset_server
+        .load("assets/models/monkey/Monkey.gltf#Scene0")
+        .unwrap();
+    scene_spawner.spawn(scene_handle);
+
     // add entities to the world
     commands
-        // mesh
-        .spawn(PbrComponents {
-            // load a mesh from glTF
-            mesh: asset_server
-                .load("assets/models/monkey/Monkey.gltf")
-                .unwrap(),
-            // create a material for the mesh
-            material: materials.add(Color::rgb(0.5, 0.4, 0.3).into()),
-            transform: Transform::from_translation(Vec3::new(-1.5, 0.0, 0.0)),
-            ..Default::default()
-        })
-        // mesh
-        .spawn(PbrComponents {
-            // load a mesh from binary glTF
-            mesh: asset_server
-                .load("assets/models/monkey/Monkey.glb")
-                .unwrap(),
-            // create a material for the mesh
-            material: materials.add(Color::rgb(0.5, 0.4, 0.3).into()),
-            transform: Transform::from_translation(Vec3::new(1.5, 0.0, 0.0)),
-            ..Default::default()
-        })
         // light
         .spawn(LightComponents {
             transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
@@ -32,7 +32,7 @@ fn setup(
 
     // this material renders the texture normally
     let material_handle = materials.add(StandardMaterial {
-        albedo_texture: Some(texture_handle),
+        albedo_texture: Some(texture_handle.clone()),
         shaded: false,
         ..Default::default()
     });
@@ -40,7 +40,7 @@ fn setup(
     // this material modulates the texture to make it red (and slightly transparent)
     let red_material_handle = materials.add(StandardMaterial {
         albedo: Color::rgba(1.0, 0.0, 0.0, 0.5),
-        albedo_texture: Some(texture_handle),
+        albedo_texture: Some(texture_handle.clone()),
         shaded: false,
         ..Default::default()
     });
@@ -57,7 +57,7 @@ fn setup(
     commands
         // textured quad - normal
         .spawn(PbrComponents {
-            mesh: quad_handle,
+            mesh: quad_handle.clone(),
             material: material_handle,
             transform: Transform::from_translation_rotation(
                 Vec3::new(0.0, 0.0, 1.5),
@@ -71,7 +71,7 @@ fn setup(
         })
         // textured quad - modulated
         .spawn(PbrComponents {
-            mesh: quad_handle,
+            mesh: quad_handle.clone(),
             material: red_material_handle,
             transform: Transform::from_translation_rotation(
                 Vec3::new(0.0, 0.0, 0.0),
@@ -53,7 +53,7 @@ fn setup(
     commands
         // parent cube
         .spawn(PbrComponents {
-            mesh: cube_handle,
+            mesh: cube_handle.clone(),
             material: materials.add(StandardMaterial {
                 shaded: false,
                 ..Default::default()
@@ -66,7 +66,7 @@ fn setup(
             // child cubes
             parent
                 .spawn(PbrComponents {
-                    mesh: cube_handle,
+                    mesh: cube_handle.clone(),
                     material: materials.add(StandardMaterial {
                         shaded: false,
                         ..Default::default()
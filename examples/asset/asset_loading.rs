@@ -12,8 +12,9 @@ fn main() {
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    mut gltfs: ResMut<Assets<Gltf>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
 ) {
     // You can load all assets in a folder like this. They will be loaded in parallel without blocking
     asset_server
@@ -21,21 +22,33 @@ fn setup(
         .unwrap();
 
     // Then any asset in the folder can be accessed like this:
-    let monkey_handle = asset_server
+    let _monkey_handle: Handle<Gltf> = asset_server
         .get_handle("assets/models/monkey/Monkey.gltf")
         .unwrap();
 
+    // Files that contain multiple assets (such as glTF files) label each of them. Labeled assets can be accessed
+    // using a "#Label" suffix, even before the file has finished loading
+    let monkey_scene_handle: Handle<Scene> = asset_server
+        .get_handle("assets/models/monkey/Monkey.gltf#Scene0")
+        .unwrap();
+    scene_spawner.spawn(monkey_scene_handle);
+
     // You can load individual assets like this:
-    let cube_handle = asset_server.load("assets/models/cube/cube.gltf").unwrap();
+    let _cube_handle: Handle<Gltf> = asset_server.load("assets/models/cube/cube.gltf").unwrap();
+    // Loading a labeled asset loads the whole file it is contained in
+    let _cube_mesh_handle: Handle<Mesh> = asset_server
+        .load("assets/models/cube/cube.gltf#Mesh0/Primitive0")
+        .unwrap();
 
     // Assets are loaded in the background by default, which means they might not be available immediately after calling load().
     // If you need immediate access you can load assets synchronously like this:
     let sphere_handle = asset_server
-        .load_sync(&mut meshes, "assets/models/sphere/sphere.gltf")
+        .load_sync(&mut gltfs, "assets/models/sphere/sphere.gltf")
         .unwrap();
-    // All assets end up in their Assets<T> collection once they are done loading:
-    let sphere = meshes.get(&sphere_handle).unwrap();
-    println!("{:?}", sphere.primitive_topology);
+    // All assets end up in their Assets<T> collection once they are done loading. Labeled assets are added to their
+    // collection in the next update, even if the file was loaded synchronously:
+    let sphere = gltfs.get(&sphere_handle).unwrap();
+    println!("sphere.gltf contains {} mesh(es)", sphere.meshes.len());
 
     // You can also add assets directly to their Assets<T> storage:
     let material_handle = materials.add(StandardMaterial {
@@ -45,23 +58,9 @@ fn setup(
 
     // Add entities to the world:
     commands
-        // monkey
-        .spawn(PbrComponents {
-            mesh: monkey_handle,
-            material: material_handle,
-            transform: Transform::from_translation(Vec3::new(-3.0, 0.0, 0.0)),
-            ..Default::default()
-        })
-        // cube
-        .spawn(PbrComponents {
-            mesh: cube_handle,
-            material: material_handle,
-            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-            ..Default::default()
-        })
         // sphere
         .spawn(PbrComponents {
-            mesh: sphere_handle,
+            mesh: sphere.meshes[0].primitives[0].mesh.clone(),
             material: material_handle,
             transform: Transform::from_translation(Vec3::new(3.0, 0.0, 0.0)),
             ..Default::default()
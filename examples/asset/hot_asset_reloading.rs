@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 /// Hot reloading allows you to modify assets on disk and they will be "live reloaded" while your game is running.
 /// This lets you immediately see the results of your changes without restarting the game.
-/// This example illustrates hot reloading mesh changes.
+/// This example illustrates hot reloading glTF scene changes.
 fn main() {
     App::build()
         .add_default_plugins()
@@ -13,33 +13,22 @@ fn main() {
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
 ) {
-    // Load our mesh:
-    let mesh_handle = asset_server
-        .load("assets/models/monkey/Monkey.gltf")
+    // Load the first scene of our glTF file and spawn it:
+    let scene_handle: Handle<Scene> = asset_server
+        .load("assets/models/monkey/Monkey.gltf#Scene0")
         .unwrap();
+    scene_spawner.spawn(scene_handle);
 
     // Tell the asset server to watch for asset changes on disk:
     asset_server.watch_for_changes().unwrap();
 
-    // Any changes to the mesh will be reloaded automatically! Try making a change to Monkey.gltf.
+    // Any changes to the glTF file will be reloaded automatically! Try making a change to Monkey.gltf.
     // You should see the changes immediately show up in your app.
 
-    // Create a material for the mesh:
-    let material_handle = materials.add(StandardMaterial {
-        albedo: Color::rgb(0.5, 0.4, 0.3),
-        ..Default::default()
-    });
-
     // Add entities to the world:
     commands
-        // mesh
-        .spawn(PbrComponents {
-            mesh: mesh_handle,
-            material: material_handle,
-            ..Default::default()
-        })
         // light
         .spawn(LightComponents {
             transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
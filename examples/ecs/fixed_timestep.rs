@@ -0,0 +1,30 @@
+use bevy::{core::FixedTimesteps, prelude::*};
+
+const FIXED_UPDATE: &str = "fixed_update";
+
+fn main() {
+    App::build()
+        .add_default_plugins()
+        // this stage runs at a fixed rate of 2 steps per second, no matter how fast the app updates
+        .add_stage_after(stage::UPDATE, FIXED_UPDATE)
+        .set_run_criteria(
+            FIXED_UPDATE,
+            FixedTimestep::steps_per_second(2.0).with_label(FIXED_UPDATE),
+        )
+        .add_system(frame_update.system())
+        .add_system_to_stage(FIXED_UPDATE, fixed_update.system())
+        .run();
+}
+
+fn frame_update(time: Res<Time>) {
+    println!("frame update: {:.4}", time.delta_seconds);
+}
+
+fn fixed_update(fixed_timesteps: Res<FixedTimesteps>) {
+    let fixed_timestep = fixed_timesteps.get(FIXED_UPDATE).unwrap();
+    println!(
+        "fixed update: step {:.4}, alpha {:.4}",
+        fixed_timestep.step(),
+        fixed_timestep.alpha()
+    );
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+/// This example shows how to use a `State` to run systems only in certain states of the app, like menus and
+/// gameplay, and how to push a pause state on top of the current state.
+fn main() {
+    App::build()
+        .add_default_plugins()
+        .add_state(AppState::Menu)
+        .on_state_enter(AppState::Menu, enter_menu.system())
+        .on_state_update(AppState::Menu, menu.system())
+        .on_state_exit(AppState::Menu, exit_menu.system())
+        .on_state_update(AppState::InGame, gameplay.system())
+        .on_state_resume(AppState::InGame, resume_game.system())
+        .on_state_enter(AppState::Paused, enter_pause.system())
+        .on_state_update(AppState::Paused, pause_menu.system())
+        .on_state_exit(AppState::Paused, exit_pause.system())
+        .run();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AppState {
+    Menu,
+    InGame,
+    Paused,
+}
+
+fn enter_menu() {
+    println!("entered the menu. press enter to start");
+}
+
+fn menu(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<AppState>>) {
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        state.set_next(AppState::InGame).unwrap();
+    }
+}
+
+fn exit_menu() {
+    println!("exited the menu");
+}
+
+fn gameplay(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<AppState>>) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        // pushing the pause state keeps the game state on the stack, so it isn't exited
+        state.push(AppState::Paused).unwrap();
+    }
+}
+
+fn enter_pause() {
+    println!("paused. press escape to resume");
+}
+
+fn pause_menu(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<AppState>>) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        state.pop().unwrap();
+    }
+}
+
+fn exit_pause() {
+    println!("exited the pause menu");
+}
+
+fn resume_game() {
+    println!("resumed the game");
+}
@@ -11,13 +11,28 @@ fn main() {
         .add_resource(Scoreboard { score: 0 })
         .add_resource(ClearColor(Color::rgb(0.7, 0.7, 0.7)))
         .add_startup_system(setup.system())
-        .add_system(paddle_movement_system.system())
-        .add_system(ball_collision_system.system())
-        .add_system(ball_movement_system.system())
+        .add_state(GameState::Playing)
+        .on_state_update(GameState::Playing, paddle_movement_system.system())
+        .on_state_update(GameState::Playing, ball_collision_system.system())
+        .on_state_update(GameState::Playing, ball_movement_system.system())
+        .on_state_update(GameState::Playing, pause_system.system())
+        .on_state_update(GameState::Playing, victory_system.system())
+        // pausing pushes the paused state on top of the playing state, so the game isn't exited while it's paused
+        .on_state_enter(GameState::Paused, enter_pause_system.system())
+        .on_state_update(GameState::Paused, unpause_system.system())
+        .on_state_exit(GameState::Paused, exit_message_system.system())
+        .on_state_enter(GameState::Won, enter_won_system.system())
         .add_system(scoreboard_system.system())
         .run();
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Playing,
+    Paused,
+    Won,
+}
+
 struct Paddle {
     speed: f32,
 }
@@ -35,6 +50,9 @@ enum Collider {
     Scorable,
 }
 
+/// A message that is shown in the middle of the screen, like the pause screen
+struct Message;
+
 fn setup(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
@@ -94,7 +112,7 @@ fn setup(
     commands
         // left
         .spawn(SpriteComponents {
-            material: wall_material,
+            material: wall_material.clone(),
             transform: Transform::from_translation(Vec3::new(-bounds.x() / 2.0, 0.0, 0.0)),
             sprite: Sprite::new(Vec2::new(wall_thickness, bounds.y() + wall_thickness)),
             ..Default::default()
@@ -102,7 +120,7 @@ fn setup(
         .with(Collider::Solid)
         // right
         .spawn(SpriteComponents {
-            material: wall_material,
+            material: wall_material.clone(),
             transform: Transform::from_translation(Vec3::new(bounds.x() / 2.0, 0.0, 0.0)),
             sprite: Sprite::new(Vec2::new(wall_thickness, bounds.y() + wall_thickness)),
             ..Default::default()
@@ -110,7 +128,7 @@ fn setup(
         .with(Collider::Solid)
         // bottom
         .spawn(SpriteComponents {
-            material: wall_material,
+            material: wall_material.clone(),
             transform: Transform::from_translation(Vec3::new(0.0, -bounds.y() / 2.0, 0.0)),
             sprite: Sprite::new(Vec2::new(bounds.x() + wall_thickness, wall_thickness)),
             ..Default::default()
@@ -187,7 +205,68 @@ fn ball_movement_system(time: Res<Time>, mut ball_query: Query<(&Ball, &mut Tran
     }
 }
 
-fn scoreboard_system(scoreboard: Res<Scoreboard>, mut query: Query<&mut Text>) {
+fn pause_system(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        state.push(GameState::Paused).unwrap();
+    }
+}
+
+fn unpause_system(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        state.pop().unwrap();
+    }
+}
+
+fn victory_system(mut state: ResMut<State<GameState>>, mut collider_query: Query<&Collider>) {
+    let bricks_left = collider_query
+        .iter()
+        .iter()
+        .any(|collider| matches!(collider, Collider::Scorable));
+    if !bricks_left {
+        state.set_next(GameState::Won).unwrap();
+    }
+}
+
+fn enter_pause_system(commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_message(commands, &asset_server, "Paused");
+}
+
+fn enter_won_system(commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_message(commands, &asset_server, "You won!");
+}
+
+fn spawn_message(mut commands: Commands, asset_server: &AssetServer, message: &str) {
+    commands
+        .spawn(TextComponents {
+            text: Text {
+                font: asset_server.load("assets/fonts/FiraSans-Bold.ttf").unwrap(),
+                value: message.to_string(),
+                style: TextStyle {
+                    color: Color::rgb(0.8, 0.2, 0.2),
+                    font_size: 60.0,
+                },
+            },
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(250.0),
+                    left: Val::Px(350.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with(Message);
+}
+
+fn exit_message_system(mut commands: Commands, mut query: Query<(Entity, &Message)>) {
+    for (entity, _message) in &mut query.iter() {
+        commands.despawn(entity);
+    }
+}
+
+fn scoreboard_system(scoreboard: Res<Scoreboard>, mut query: Query<Without<Message, &mut Text>>) {
     for mut text in &mut query.iter() {
         text.value = format!("Score: {}", scoreboard.score);
     }
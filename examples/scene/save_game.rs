@@ -0,0 +1,67 @@
+use bevy::{prelude::*, type_registry::TypeRegistry};
+
+/// This example illustrates saving the state of a game and restoring it later
+fn main() {
+    App::build()
+        .add_default_plugins()
+        // Components and resources need to be registered to be saved. Registering a resource also initializes it.
+        .register_component::<Player>()
+        .register_resource::<Score>()
+        .add_startup_system(save_game_system.thread_local_system())
+        .run();
+}
+
+#[derive(Properties, Default)]
+struct Player {
+    pub name: String,
+    pub health: f32,
+}
+
+#[derive(Properties, Default)]
+struct Score {
+    pub value: u32,
+}
+
+fn save_game_system(world: &mut World, resources: &mut Resources) {
+    world.spawn((Player {
+        name: "Alice".to_string(),
+        health: 100.0,
+    },));
+    world.spawn((Player {
+        name: "Bob".to_string(),
+        health: 50.0,
+    },));
+    resources.get_mut::<Score>().unwrap().value = 10;
+
+    // A SnapshotFilter selects what is saved. Here only entities with a Player component are saved, which leaves
+    // out cameras, windows and other entities the game creates itself.
+    let filter = SnapshotFilter::default().with::<Player>();
+    let save_game = SaveGame::snapshot(world, resources, &filter);
+
+    // Save games can be written to files with SaveGame::save. Here we just print the serialized save game.
+    let serialized = {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let registry = type_registry.property.read();
+        save_game.serialize_ron(&registry).unwrap()
+    };
+    println!("{}", serialized);
+
+    // The game goes on...
+    resources.get_mut::<Score>().unwrap().value = 20;
+    for entity in filter.entities(world) {
+        world.despawn(entity).unwrap();
+    }
+
+    // ...until the save game is loaded again. The saved entities are spawned as new entities.
+    let save_game = {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let registry = type_registry.property.read();
+        SaveGame::deserialize_ron(serialized.as_bytes(), &registry).unwrap()
+    };
+    save_game.restore(world, resources).unwrap();
+
+    println!("Score: {}", resources.get::<Score>().unwrap().value);
+    for player in &mut world.query::<&Player>().iter() {
+        println!("{}: {}", player.name, player.health);
+    }
+}
@@ -11,6 +11,8 @@ fn main() {
         // The core Bevy plugins already register their components, so you only need this step for custom components.
         .register_component::<ComponentA>()
         .register_component::<ComponentB>()
+        // When a registered component changes after scenes were saved, `register_rename` and `register_migration`
+        // upgrade the components of older scenes while they load.
         .add_startup_system(save_scene_system.thread_local_system())
         .add_startup_system(load_scene_system.system())
         .add_startup_system(infotext_system.system())
@@ -42,15 +42,15 @@ fn button_system(
         match *interaction {
             Interaction::Clicked => {
                 text.value = "Press".to_string();
-                *material = button_materials.pressed;
+                *material = button_materials.pressed.clone();
             }
             Interaction::Hovered => {
                 text.value = "Hover".to_string();
-                *material = button_materials.hovered;
+                *material = button_materials.hovered.clone();
             }
             Interaction::None => {
                 text.value = "Button".to_string();
-                *material = button_materials.normal;
+                *material = button_materials.normal.clone();
             }
         }
     }
@@ -75,7 +75,7 @@ fn setup(
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
-            material: button_materials.normal,
+            material: button_materials.normal.clone(),
             ..Default::default()
         })
         .with_children(|parent| {
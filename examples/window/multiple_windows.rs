@@ -25,7 +25,7 @@ fn setup(
     mut create_window_events: ResMut<Events<CreateWindow>>,
     mut active_cameras: ResMut<ActiveCameras>,
     mut render_graph: ResMut<RenderGraph>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut scene_spawner: ResMut<SceneSpawner>,
     asset_server: Res<AssetServer>,
     msaa: Res<Msaa>,
 ) {
@@ -152,25 +152,14 @@ fn setup(
 
     // SETUP SCENE
 
-    // load the mesh
-    let mesh_handle = asset_server
-        .load("assets/models/monkey/Monkey.gltf")
+    // load the model's first scene and spawn it
+    let scene_handle: Handle<Scene> = asset_server
+        .load("assets/models/monkey/Monkey.gltf#Scene0")
         .unwrap();
-
-    // create a material for the mesh
-    let material_handle = materials.add(StandardMaterial {
-        albedo: Color::rgb(0.5, 0.4, 0.3),
-        ..Default::default()
-    });
+    scene_spawner.spawn(scene_handle);
 
     // add entities to the world
     commands
-        // mesh
-        .spawn(PbrComponents {
-            mesh: mesh_handle,
-            material: material_handle,
-            ..Default::default()
-        })
         // light
         .spawn(LightComponents {
             transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
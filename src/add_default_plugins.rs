@@ -24,6 +24,9 @@ impl AddDefaultPlugins for AppBuilder {
         #[cfg(feature = "bevy_pbr")]
         self.add_plugin(bevy_pbr::PbrPlugin::default());
 
+        #[cfg(feature = "bevy_animation")]
+        self.add_plugin(bevy_animation::AnimationPlugin::default());
+
         #[cfg(feature = "bevy_ui")]
         self.add_plugin(bevy_ui::UiPlugin::default());
 
@@ -55,6 +55,9 @@ pub use bevy_transform as transform;
 pub use bevy_type_registry as type_registry;
 pub use bevy_window as window;
 
+#[cfg(feature = "bevy_animation")]
+pub use bevy_animation as animation;
+
 #[cfg(feature = "bevy_audio")]
 pub use bevy_audio as audio;
 
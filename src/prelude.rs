@@ -4,9 +4,15 @@ pub use crate::{
     type_registry::RegisterType, window::prelude::*, AddDefaultPlugins,
 };
 
+#[cfg(feature = "bevy_animation")]
+pub use crate::animation::prelude::*;
+
 #[cfg(feature = "bevy_audio")]
 pub use crate::audio::prelude::*;
 
+#[cfg(feature = "bevy_gltf")]
+pub use crate::gltf::prelude::*;
+
 #[cfg(feature = "bevy_pbr")]
 pub use crate::pbr::prelude::*;
 
@@ -18,11 +18,12 @@ crates=(
     bevy_transform
     bevy_window
     bevy_render
-    bevy_gltf
+    bevy_animation
     bevy_input
     bevy_gilrs
     bevy_pbr
     bevy_scene
+    bevy_gltf
     bevy_sprite
     bevy_text
     bevy_ui